@@ -0,0 +1,93 @@
+//! Derive macros for the [lineartree](https://docs.rs/lineartree) crate.
+//!
+//! Do not use this crate directly, enable the `derive` feature
+//! of `lineartree` instead.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Ident};
+
+/// Derive `lineartree::IntoTree` for a recursive struct.
+///
+/// The struct must have named fields, one of which holds the children
+/// as a `Vec<Self>`. The field is either named `children` or marked
+/// with the `#[tree(children)]` attribute.
+#[proc_macro_derive(IntoTree, attributes(tree))]
+pub fn derive_into_tree(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_into_tree(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn expand_into_tree(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let field = children_field(input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::lineartree::IntoTree for #name #ty_generics #where_clause {
+            fn take_children(&mut self) -> ::std::vec::Vec<Self> {
+                ::std::mem::take(&mut self.#field)
+            }
+
+            fn set_children(&mut self, children: ::std::vec::Vec<Self>) {
+                self.#field = children;
+            }
+        }
+    })
+}
+
+fn children_field(input: &DeriveInput) -> syn::Result<Ident> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    Span::call_site(),
+                    "IntoTree requires named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "IntoTree can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut marked = None;
+    for field in fields {
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("tree"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("children") {
+                    marked = field.ident.clone();
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported tree attribute"))
+                }
+            })?;
+        }
+    }
+
+    marked
+        .or_else(|| {
+            fields
+                .iter()
+                .filter_map(|field| field.ident.clone())
+                .find(|ident| ident == "children")
+        })
+        .ok_or_else(|| {
+            Error::new(
+                Span::call_site(),
+                "IntoTree requires a `children` field or a field marked with #[tree(children)]",
+            )
+        })
+}
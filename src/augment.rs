@@ -0,0 +1,209 @@
+use super::{node_not_found, NodeRef, RemovalReport, Result, Tree};
+
+/// Tree maintaining an aggregate value for each subtree.
+///
+/// The aggregate of a node is computed by a user provided `combine`
+/// function from the aggregates of its children and its own content,
+/// like the size of a directory from the sizes of its entries. This
+/// generalizes subtree counts, sums, minimums and so on.
+///
+/// Aggregates are updated incrementally: every change made through the
+/// methods of this type recomputes only the aggregates of the changed
+/// node and of its ancestors. For this reason, the underlying tree can
+/// only be read with [AugmentedTree::tree].
+///
+/// ```ignore
+/// // Total size of each directory
+/// let mut tree = AugmentedTree::new(|children: &[u64], file: &File| {
+///     file.size + children.iter().sum::<u64>()
+/// });
+/// let root = tree.root(File { size: 0 })?;
+/// tree.child_node(root, File { size: 10 })?;
+///
+/// assert_eq!(tree.aggregate(root), Some(&10));
+/// ```
+pub struct AugmentedTree<T, A, F> {
+    tree: Tree<T>,
+    aggregates: Vec<Option<A>>,
+    combine: F,
+}
+
+impl<T, A: Clone, F: Fn(&[A], &T) -> A> AugmentedTree<T, A, F> {
+    /// Create a new empty tree.
+    ///
+    /// *Arguments:*
+    /// * `combine` - Function computing the aggregate of a node from the
+    ///   aggregates of its children, in order, and its content.
+    ///
+    /// *Returns:* The new tree.
+    pub fn new(combine: F) -> Self {
+        Self::from_tree(Tree::new(), combine)
+    }
+
+    /// Compute the aggregates of an existing tree.
+    ///
+    /// *Arguments:*
+    /// * `tree` - The tree to augment.
+    /// * `combine` - Function computing the aggregate of a node from the
+    ///   aggregates of its children, in order, and its content.
+    ///
+    /// *Returns:* The augmented tree.
+    pub fn from_tree(tree: Tree<T>, combine: F) -> Self {
+        let mut augmented = Self {
+            aggregates: Vec::with_capacity(tree.nodes.len()),
+            tree,
+            combine,
+        };
+
+        let tops: Vec<NodeRef> = augmented
+            .tree
+            .iter_all_nodes()
+            .filter(|node_ref| augmented.live_parent(*node_ref).is_none())
+            .collect();
+        for top_ref in tops {
+            let order: Vec<NodeRef> = augmented
+                .tree
+                .depth_first_of(top_ref, true)
+                .unwrap()
+                .collect();
+            for node_ref in order.into_iter().rev() {
+                augmented.recompute(node_ref);
+            }
+        }
+        augmented
+    }
+
+    /// Get the aggregate of a node.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    ///
+    /// *Returns:* The aggregate of the subtree or `None` if
+    ///            the node does not exist.
+    pub fn aggregate(&self, node_ref: NodeRef) -> Option<&A> {
+        self.tree.get(node_ref)?;
+        self.aggregates.get(node_ref.index())?.as_ref()
+    }
+
+    /// Get the content of a node.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    ///
+    /// *Returns:* The content or `None` if the node does not exist.
+    pub fn get(&self, node_ref: NodeRef) -> Option<&T> {
+        self.tree.get(node_ref)
+    }
+
+    /// Change the content of a node and update the aggregates.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    /// * `update_fn` - Function changing the content.
+    ///
+    /// *Returns:* Result indicating whether the operations was successful.
+    ///            Returns an error if the node does not exist.
+    pub fn update(&mut self, node_ref: NodeRef, update_fn: impl FnOnce(&mut T)) -> Result<()> {
+        match self.tree.get_mut(node_ref) {
+            None => return Err(node_not_found(node_ref)),
+            Some(content) => update_fn(content),
+        }
+        self.propagate(node_ref);
+        Ok(())
+    }
+
+    /// Create a root node, see [Tree::root].
+    pub fn root(&mut self, content: T) -> Result<NodeRef> {
+        let root_ref = self.tree.root(content)?;
+        self.recompute(root_ref);
+        Ok(root_ref)
+    }
+
+    /// Create a node without parent, see [Tree::node].
+    pub fn node(&mut self, content: T) -> NodeRef {
+        let node_ref = self.tree.node(content);
+        self.recompute(node_ref);
+        node_ref
+    }
+
+    /// Create a node and append it to a parent node, see [Tree::child_node].
+    pub fn child_node(&mut self, parent: NodeRef, content: T) -> Result<NodeRef> {
+        let child_ref = self.node(content);
+        self.append_child(parent, child_ref)?;
+        Ok(child_ref)
+    }
+
+    /// Add child node to a node, see [Tree::append_child].
+    pub fn append_child(&mut self, parent_ref: NodeRef, child_ref: NodeRef) -> Result<()> {
+        let old_parent = self.live_parent(child_ref);
+        self.tree.append_child(parent_ref, child_ref)?;
+        if let Some(old_parent_ref) = old_parent {
+            self.propagate(old_parent_ref);
+        }
+        self.propagate(parent_ref);
+        Ok(())
+    }
+
+    /// Remove a node from the tree, see [Tree::remove].
+    ///
+    /// The children of the removed node are left without parent
+    /// and keep their aggregates.
+    pub fn remove(&mut self, node_ref: NodeRef) -> Result<RemovalReport> {
+        let parent = self.live_parent(node_ref);
+        let report = self.tree.remove(node_ref)?;
+        self.aggregates[node_ref.index()] = None;
+        if let Some(parent_ref) = parent {
+            self.propagate(parent_ref);
+        }
+        Ok(report)
+    }
+
+    /// Get the underlying tree.
+    pub fn tree(&self) -> &Tree<T> {
+        &self.tree
+    }
+
+    /// Drop the aggregates and get the underlying tree back.
+    pub fn into_tree(self) -> Tree<T> {
+        self.tree
+    }
+
+    fn live_parent(&self, node_ref: NodeRef) -> Option<NodeRef> {
+        self.tree
+            .get_node(node_ref)?
+            .parent
+            .filter(|parent_ref| self.tree.get_node(*parent_ref).is_some())
+    }
+
+    /// Recompute the aggregate of a node and of all its ancestors.
+    fn propagate(&mut self, node_ref: NodeRef) {
+        let mut next = Some(node_ref);
+        while let Some(node_ref) = next {
+            self.recompute(node_ref);
+            next = self.live_parent(node_ref);
+        }
+    }
+
+    /// Recompute the aggregate of a node from the ones of its children.
+    ///
+    /// Stale entries of moved or removed children are skipped.
+    fn recompute(&mut self, node_ref: NodeRef) {
+        let children: Vec<A> = self
+            .tree
+            .children_of(node_ref)
+            .filter(|child_ref| self.live_parent(**child_ref) == Some(node_ref))
+            .filter_map(|child_ref| self.aggregates.get(child_ref.index())?.clone())
+            .collect();
+        let aggregate = (self.combine)(&children, self.tree.get(node_ref).unwrap());
+
+        if self.aggregates.len() <= node_ref.index() {
+            self.aggregates.resize(self.tree.nodes.len(), None);
+        }
+        self.aggregates[node_ref.index()] = Some(aggregate);
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
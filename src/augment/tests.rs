@@ -0,0 +1,45 @@
+use super::*;
+
+fn sum(children: &[i32], content: &i32) -> i32 {
+    content + children.iter().sum::<i32>()
+}
+
+#[test]
+fn maintains_sums() {
+    let mut tree = AugmentedTree::new(sum);
+    let root = tree.root(1).unwrap();
+    let a = tree.child_node(root, 2).unwrap();
+    let b = tree.child_node(a, 3).unwrap();
+    let c = tree.child_node(root, 4).unwrap();
+    assert_eq!(tree.aggregate(root), Some(&10));
+    assert_eq!(tree.aggregate(a), Some(&5));
+
+    tree.update(b, |content| *content = 10).unwrap();
+    assert_eq!(tree.aggregate(a), Some(&12));
+    assert_eq!(tree.aggregate(root), Some(&17));
+
+    tree.append_child(c, a).unwrap();
+    assert_eq!(tree.aggregate(c), Some(&16));
+    assert_eq!(tree.aggregate(root), Some(&17));
+
+    tree.remove(a).unwrap();
+    assert_eq!(tree.aggregate(a), None);
+    assert_eq!(tree.aggregate(c), Some(&4));
+    assert_eq!(tree.aggregate(root), Some(&5));
+    assert_eq!(tree.aggregate(b), Some(&10));
+}
+
+#[test]
+fn from_tree() {
+    let mut tree = Tree::new();
+    let root = tree.root("a").unwrap();
+    let child = tree.child_node(root, "b").unwrap();
+    tree.child_node(child, "c").unwrap();
+    let detached = tree.node("d");
+
+    let count = |children: &[usize], _: &&str| 1 + children.iter().sum::<usize>();
+    let tree = AugmentedTree::from_tree(tree, count);
+    assert_eq!(tree.aggregate(root), Some(&3));
+    assert_eq!(tree.aggregate(child), Some(&2));
+    assert_eq!(tree.aggregate(detached), Some(&1));
+}
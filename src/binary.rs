@@ -0,0 +1,482 @@
+use std::fmt;
+use std::iter::FusedIterator;
+
+use super::{invalid_input, node_not_found, not_found, NodeRef, Result, Tree, TreeError};
+
+const LEFT: usize = 0;
+const RIGHT: usize = 1;
+
+// BinaryTree
+// ==================================================================
+/// Represent a binary tree structure.
+///
+/// This is a thin wrapper around [Tree] which uses the same arena to
+/// store the nodes, but where each node has at most a left and a right
+/// child. A node can have a right child without having a left one.
+///
+/// The underlying [Tree] can be accessed with [BinaryTree::as_tree]
+/// to use all the generic functionality, like depth-first iteration
+/// or mapping. There the children of a node are listed left first.
+#[derive(Debug, Clone)]
+pub struct BinaryTree<T> {
+    tree: Tree<T>,
+    links: Vec<[Option<NodeRef>; 2]>,
+}
+
+impl<T> BinaryTree<T> {
+    /// Create new empty binary tree structure.
+    ///
+    /// *Returns:* [BinaryTree] struct.
+    pub fn new() -> Self {
+        Self {
+            tree: Tree::new(),
+            links: Vec::new(),
+        }
+    }
+
+    /// Create a root node.
+    ///
+    /// There can be only one root node in a tree, and calling this function
+    /// twice will result in an error.
+    ///
+    /// *Arguments:*
+    /// * `content` - The item to be set as content of the root node.
+    ///
+    /// *Returns:* A [NodeRef] object referencing the created node.
+    pub fn root(&mut self, content: T) -> Result<NodeRef> {
+        if self.tree.root.is_some() {
+            return Err(TreeError::new("Another root node already exists."));
+        }
+
+        let node_ref = self.node(content);
+        self.tree.set_root(node_ref, false)?;
+
+        Ok(node_ref)
+    }
+
+    /// Create a node.
+    ///
+    /// *Arguments:*
+    /// * `content` - The item to be set as content of the node.
+    ///
+    /// *Returns:* A [NodeRef] object referencing the created node.
+    pub fn node(&mut self, content: T) -> NodeRef {
+        let node_ref = self.tree.node(content);
+        if node_ref.index() < self.links.len() {
+            self.links[node_ref.index()] = [None, None];
+        } else {
+            self.links.push([None, None]);
+        }
+        node_ref
+    }
+
+    /// Get the number of nodes in the tree.
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    /// Check whether the tree contains no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    /// Get immutable reference to the node content.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] object indicating which node
+    ///   content to retrieve.
+    ///
+    /// *Returns:* Reference to the object contained in the node
+    ///            or `None` if the `node_ref` is invalid.
+    pub fn get(&self, node_ref: NodeRef) -> Option<&T> {
+        self.tree.get(node_ref)
+    }
+
+    /// Get mutable reference to the node content.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] object indicating which node
+    ///   content to retrieve.
+    ///
+    /// *Returns:* Mutable reference to the object contained in the node
+    ///            or `None` if the `node_ref` is invalid.
+    pub fn get_mut(&mut self, node_ref: NodeRef) -> Option<&mut T> {
+        self.tree.get_mut(node_ref)
+    }
+
+    /// Get reference to root node.
+    ///
+    /// *Returns:* Reference to the root node or `None` if no root node exists.
+    pub fn get_root_ref(&self) -> Option<NodeRef> {
+        self.tree.get_root_ref()
+    }
+
+    /// Get reference to the parent node.
+    ///
+    /// *Arguments:*
+    /// * `child_ref` - [NodeRef] of the child node.
+    ///
+    /// *Returns:* A reference to the parent node or `None` if no
+    ///            parent exists. Returns error if the child does not exist.
+    pub fn get_parent(&self, child_ref: NodeRef) -> Result<Option<NodeRef>> {
+        self.tree.get_parent(child_ref)
+    }
+
+    /// Get reference to the left child of a node.
+    ///
+    /// *Arguments:*
+    /// * `parent_ref` - [NodeRef] of the parent node.
+    ///
+    /// *Returns:* A reference to the left child or `None` if the node
+    ///            has no left child. Returns error if the parent does not exist.
+    pub fn left(&self, parent_ref: NodeRef) -> Result<Option<NodeRef>> {
+        self.get_child(parent_ref, LEFT)
+    }
+
+    /// Get reference to the right child of a node.
+    ///
+    /// *Arguments:*
+    /// * `parent_ref` - [NodeRef] of the parent node.
+    ///
+    /// *Returns:* A reference to the right child or `None` if the node
+    ///            has no right child. Returns error if the parent does not exist.
+    pub fn right(&self, parent_ref: NodeRef) -> Result<Option<NodeRef>> {
+        self.get_child(parent_ref, RIGHT)
+    }
+
+    /// Set the left child of a node.
+    ///
+    /// If the node already had a left child, it gets detached
+    /// from the node and returned.
+    ///
+    /// *Arguments:*
+    /// * `parent_ref` - [NodeRef] of the parent node.
+    /// * `child_ref` - [NodeRef] of the new left child node.
+    ///
+    /// *Returns:* The previous left child, if any. Returns an error if one
+    ///            of the node references is invalid, if the child already
+    ///            has a parent or if it is an ancestor of the parent.
+    pub fn set_left(&mut self, parent_ref: NodeRef, child_ref: NodeRef) -> Result<Option<NodeRef>> {
+        self.set_child(parent_ref, child_ref, LEFT)
+    }
+
+    /// Set the right child of a node.
+    ///
+    /// If the node already had a right child, it gets detached
+    /// from the node and returned.
+    ///
+    /// *Arguments:*
+    /// * `parent_ref` - [NodeRef] of the parent node.
+    /// * `child_ref` - [NodeRef] of the new right child node.
+    ///
+    /// *Returns:* The previous right child, if any. Returns an error if one
+    ///            of the node references is invalid, if the child already
+    ///            has a parent or if it is an ancestor of the parent.
+    pub fn set_right(
+        &mut self,
+        parent_ref: NodeRef,
+        child_ref: NodeRef,
+    ) -> Result<Option<NodeRef>> {
+        self.set_child(parent_ref, child_ref, RIGHT)
+    }
+
+    /// Get an iterator traversing the node and all child nodes in
+    /// pre-order (node, left subtree, right subtree).
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the starting node.
+    ///
+    /// *Returns:* An iterator returning the node references in pre-order.
+    ///            Returns error if the start node does not exist.
+    pub fn pre_order_of(&self, node_ref: NodeRef) -> Result<PreOrderIterator<'_, T>> {
+        self.check_node(node_ref)?;
+        Ok(PreOrderIterator {
+            tree: self,
+            stack: vec![node_ref],
+        })
+    }
+
+    /// Get an iterator traversing the node and all child nodes in
+    /// in-order (left subtree, node, right subtree).
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the starting node.
+    ///
+    /// *Returns:* An iterator returning the node references in in-order.
+    ///            Returns error if the start node does not exist.
+    pub fn in_order_of(&self, node_ref: NodeRef) -> Result<InOrderIterator<'_, T>> {
+        self.check_node(node_ref)?;
+        Ok(InOrderIterator {
+            tree: self,
+            stack: Vec::new(),
+            current: Some(node_ref),
+        })
+    }
+
+    /// Get an iterator traversing the node and all child nodes in
+    /// post-order (left subtree, right subtree, node).
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the starting node.
+    ///
+    /// *Returns:* An iterator returning the node references in post-order.
+    ///            Returns error if the start node does not exist.
+    pub fn post_order_of(&self, node_ref: NodeRef) -> Result<PostOrderIterator<'_, T>> {
+        self.check_node(node_ref)?;
+        Ok(PostOrderIterator {
+            tree: self,
+            stack: vec![(node_ref, false)],
+        })
+    }
+
+    /// Get an iterator traversing all nodes in the tree in pre-order.
+    ///
+    /// *Returns:* An iterator returning the node references in pre-order.
+    ///            Returns error if no root node exist.
+    pub fn pre_order(&self) -> Result<PreOrderIterator<'_, T>> {
+        self.pre_order_of(self.require_root()?)
+    }
+
+    /// Get an iterator traversing all nodes in the tree in in-order.
+    ///
+    /// *Returns:* An iterator returning the node references in in-order.
+    ///            Returns error if no root node exist.
+    pub fn in_order(&self) -> Result<InOrderIterator<'_, T>> {
+        self.in_order_of(self.require_root()?)
+    }
+
+    /// Get an iterator traversing all nodes in the tree in post-order.
+    ///
+    /// *Returns:* An iterator returning the node references in post-order.
+    ///            Returns error if no root node exist.
+    pub fn post_order(&self) -> Result<PostOrderIterator<'_, T>> {
+        self.post_order_of(self.require_root()?)
+    }
+
+    /// Get the underlying generic tree.
+    pub fn as_tree(&self) -> &Tree<T> {
+        &self.tree
+    }
+
+    /// Convert into the underlying generic tree.
+    pub fn into_tree(self) -> Tree<T> {
+        self.tree
+    }
+
+    fn check_node(&self, node_ref: NodeRef) -> Result<()> {
+        match self.tree.get_node(node_ref) {
+            None => Err(node_not_found(node_ref)),
+            Some(_) => Ok(()),
+        }
+    }
+
+    fn require_root(&self) -> Result<NodeRef> {
+        match self.tree.root {
+            None => Err(not_found("Cannot iterate a tree without a root node.")),
+            Some(root_ref) => Ok(root_ref),
+        }
+    }
+
+    fn get_child(&self, parent_ref: NodeRef, side: usize) -> Result<Option<NodeRef>> {
+        match self.tree.get_node(parent_ref) {
+            None => Err(not_found("Parent node does not exist.")),
+            Some(_) => Ok(self.links[parent_ref.index()][side]),
+        }
+    }
+
+    fn set_child(
+        &mut self,
+        parent_ref: NodeRef,
+        child_ref: NodeRef,
+        side: usize,
+    ) -> Result<Option<NodeRef>> {
+        if self.tree.get_node(parent_ref).is_none() {
+            return Err(not_found("Parent node does not exist."));
+        }
+
+        match self.tree.get_node(child_ref) {
+            None => return Err(not_found("Child node does not exist.")),
+            Some(child_node) => {
+                if child_node.parent.is_some() {
+                    return Err(invalid_input("Child node already has a parent."));
+                }
+            }
+        }
+
+        let mut ancestor = Some(parent_ref);
+        while let Some(ancestor_ref) = ancestor {
+            if ancestor_ref == child_ref {
+                return Err(invalid_input("Child node is an ancestor of the parent."));
+            }
+            ancestor = self.tree.get_node(ancestor_ref).unwrap().parent;
+        }
+
+        let previous = self.links[parent_ref.index()][side].replace(child_ref);
+        if let Some(previous_ref) = previous {
+            self.tree.reattach(previous_ref, None);
+            self.tree.get_node_mut(previous_ref).unwrap().parent = None;
+        }
+        self.tree.reattach(child_ref, Some(parent_ref));
+        self.tree.get_node_mut(child_ref).unwrap().parent = Some(parent_ref);
+
+        let children = self.links[parent_ref.index()]
+            .iter()
+            .flatten()
+            .copied()
+            .collect();
+        self.tree.set_child_list(parent_ref, children);
+
+        Ok(previous)
+    }
+}
+
+impl<T> Default for BinaryTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Iterators
+// ==================================================================
+/// Pre-order traversal of a binary tree, created by
+/// [BinaryTree::pre_order] and [BinaryTree::pre_order_of].
+pub struct PreOrderIterator<'a, T> {
+    tree: &'a BinaryTree<T>,
+    stack: Vec<NodeRef>,
+}
+
+impl<'a, T> Iterator for PreOrderIterator<'a, T> {
+    type Item = NodeRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node_ref = self.stack.pop()?;
+        let [left, right] = self.tree.links[node_ref.index()];
+        self.stack.extend(right);
+        self.stack.extend(left);
+        Some(node_ref)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.stack.len(), None)
+    }
+}
+
+impl<'a, T> FusedIterator for PreOrderIterator<'a, T> {}
+
+impl<'a, T> Clone for PreOrderIterator<'a, T> {
+    fn clone(&self) -> Self {
+        Self {
+            tree: self.tree,
+            stack: self.stack.clone(),
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for PreOrderIterator<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PreOrderIterator")
+            .field("stack", &self.stack)
+            .finish_non_exhaustive()
+    }
+}
+
+/// In-order traversal of a binary tree, created by
+/// [BinaryTree::in_order] and [BinaryTree::in_order_of].
+pub struct InOrderIterator<'a, T> {
+    tree: &'a BinaryTree<T>,
+    stack: Vec<NodeRef>,
+    current: Option<NodeRef>,
+}
+
+impl<'a, T> Iterator for InOrderIterator<'a, T> {
+    type Item = NodeRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node_ref) = self.current {
+            self.stack.push(node_ref);
+            self.current = self.tree.links[node_ref.index()][LEFT];
+        }
+
+        let node_ref = self.stack.pop()?;
+        self.current = self.tree.links[node_ref.index()][RIGHT];
+        Some(node_ref)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.stack.len() + self.current.is_some() as usize, None)
+    }
+}
+
+impl<'a, T> FusedIterator for InOrderIterator<'a, T> {}
+
+impl<'a, T> Clone for InOrderIterator<'a, T> {
+    fn clone(&self) -> Self {
+        Self {
+            tree: self.tree,
+            stack: self.stack.clone(),
+            current: self.current,
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for InOrderIterator<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InOrderIterator")
+            .field("stack", &self.stack)
+            .field("current", &self.current)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Post-order traversal of a binary tree, created by
+/// [BinaryTree::post_order] and [BinaryTree::post_order_of].
+pub struct PostOrderIterator<'a, T> {
+    tree: &'a BinaryTree<T>,
+    stack: Vec<(NodeRef, bool)>,
+}
+
+impl<'a, T> Iterator for PostOrderIterator<'a, T> {
+    type Item = NodeRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node_ref, expanded) = self.stack.pop()?;
+            if expanded {
+                return Some(node_ref);
+            }
+
+            let [left, right] = self.tree.links[node_ref.index()];
+            self.stack.push((node_ref, true));
+            self.stack.extend(right.map(|right_ref| (right_ref, false)));
+            self.stack.extend(left.map(|left_ref| (left_ref, false)));
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.stack.len(), None)
+    }
+}
+
+impl<'a, T> FusedIterator for PostOrderIterator<'a, T> {}
+
+impl<'a, T> Clone for PostOrderIterator<'a, T> {
+    fn clone(&self) -> Self {
+        Self {
+            tree: self.tree,
+            stack: self.stack.clone(),
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for PostOrderIterator<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PostOrderIterator")
+            .field("stack", &self.stack)
+            .finish_non_exhaustive()
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,132 @@
+use super::*;
+use crate::ErrorKind;
+
+fn binary_tree() -> (BinaryTree<i32>, NodeRef, NodeRef) {
+    /*
+     *         4
+     *       /   \
+     *      2     5
+     *     / \     \
+     *    1   3     6
+     */
+
+    let mut tree = BinaryTree::new();
+
+    let node_4 = tree.root(4).unwrap();
+    let node_2 = tree.node(2);
+    let node_5 = tree.node(5);
+    let node_1 = tree.node(1);
+    let node_3 = tree.node(3);
+    let node_6 = tree.node(6);
+
+    tree.set_left(node_4, node_2).unwrap();
+    tree.set_right(node_4, node_5).unwrap();
+    tree.set_left(node_2, node_1).unwrap();
+    tree.set_right(node_2, node_3).unwrap();
+    tree.set_right(node_5, node_6).unwrap();
+
+    (tree, node_2, node_5)
+}
+
+fn values(tree: &BinaryTree<i32>, iterator: impl Iterator<Item = NodeRef>) -> Vec<i32> {
+    iterator
+        .map(|node_ref| *tree.get(node_ref).unwrap())
+        .collect()
+}
+
+#[test]
+fn left_right() {
+    let (tree, node_2, node_5) = binary_tree();
+
+    assert_eq!(
+        tree.left(tree.get_root_ref().unwrap()).unwrap(),
+        Some(node_2)
+    );
+    assert_eq!(
+        tree.right(tree.get_root_ref().unwrap()).unwrap(),
+        Some(node_5)
+    );
+    assert_eq!(tree.left(node_5).unwrap(), None);
+    assert_eq!(tree.get(tree.right(node_5).unwrap().unwrap()), Some(&6));
+    assert_eq!(tree.get_parent(node_5).unwrap(), tree.get_root_ref());
+}
+
+#[test]
+fn set_child_replaces_previous() {
+    let (mut tree, node_2, node_5) = binary_tree();
+    let node_7 = tree.node(7);
+
+    assert_eq!(tree.set_left(node_5, node_7), Ok(None));
+    let node_8 = tree.node(8);
+    assert_eq!(tree.set_left(node_5, node_8), Ok(Some(node_7)));
+    assert_eq!(tree.get_parent(node_7).unwrap(), None);
+    assert_eq!(tree.len(), 7);
+
+    let children: Vec<i32> = values(&tree, tree.as_tree().get_children(node_5).unwrap());
+    assert_eq!(children, vec![8, 6]);
+
+    assert_eq!(
+        tree.set_left(node_2, node_5),
+        Err(TreeError::with_kind(
+            ErrorKind::InvalidInput,
+            "Child node already has a parent."
+        ))
+    );
+}
+
+#[test]
+fn set_child_error_cycle() {
+    let mut tree = BinaryTree::new();
+    let node_a = tree.node(1);
+    let node_b = tree.node(2);
+
+    tree.set_left(node_a, node_b).unwrap();
+
+    assert_eq!(
+        tree.set_right(node_b, node_a),
+        Err(TreeError::with_kind(
+            ErrorKind::InvalidInput,
+            "Child node is an ancestor of the parent."
+        ))
+    );
+}
+
+#[test]
+fn traversal_orders() {
+    let (tree, node_2, _) = binary_tree();
+
+    assert_eq!(
+        values(&tree, tree.pre_order().unwrap()),
+        vec![4, 2, 1, 3, 5, 6]
+    );
+    assert_eq!(
+        values(&tree, tree.in_order().unwrap()),
+        vec![1, 2, 3, 4, 5, 6]
+    );
+    assert_eq!(
+        values(&tree, tree.post_order().unwrap()),
+        vec![1, 3, 2, 6, 5, 4]
+    );
+    assert_eq!(
+        values(&tree, tree.in_order_of(node_2).unwrap()),
+        vec![1, 2, 3]
+    );
+}
+
+#[test]
+fn traversal_iterators_are_cloneable_and_fused() {
+    let (tree, _, _) = binary_tree();
+
+    let mut iterator = tree.in_order().unwrap();
+    iterator.next();
+    assert_eq!(values(&tree, iterator.clone()), vec![2, 3, 4, 5, 6]);
+    assert_eq!(iterator.by_ref().count(), 5);
+    assert_eq!(iterator.next(), None);
+    assert_eq!(iterator.next(), None);
+
+    let pre_order = tree.pre_order().unwrap();
+    assert!(format!("{:?}", pre_order).starts_with("PreOrderIterator"));
+    assert_eq!(pre_order.size_hint(), (1, None));
+    let post_order = tree.post_order().unwrap();
+    assert_eq!(values(&tree, post_order.clone()), vec![1, 3, 2, 6, 5, 4]);
+}
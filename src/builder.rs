@@ -0,0 +1,84 @@
+use super::{invalid_input, NodeRef, Result, Tree};
+
+/// Builder creating a tree from a stream of nodes in depth-first order.
+///
+/// Each node is given with its depth, like the events produced by
+/// streaming parsers or indentation scanners, and its parent is
+/// deduced from the previous nodes: the root has depth 0 and every
+/// other node is a child of the last node with a lower depth.
+///
+/// ```ignore
+/// let mut builder = StreamingBuilder::new();
+/// builder.push(0, "/")?;
+/// builder.push(1, "usr")?;
+/// builder.push(2, "bin")?;
+/// builder.push(1, "etc")?;
+/// let tree = builder.finish();
+/// ```
+#[derive(Debug)]
+pub struct StreamingBuilder<T> {
+    tree: Tree<T>,
+    stack: Vec<NodeRef>,
+}
+
+impl<T> StreamingBuilder<T> {
+    /// Create a new builder for an empty tree.
+    pub fn new() -> Self {
+        Self {
+            tree: Tree::new(),
+            stack: Vec::new(),
+        }
+    }
+
+    /// Add the next node in depth-first order.
+    ///
+    /// *Arguments:*
+    /// * `depth` - Depth of the node, 0 for the root.
+    /// * `content` - The item to be set as content of the node.
+    ///
+    /// *Returns:* A [NodeRef] object referencing the created node.
+    ///            Returns an error if the depth is more than one level
+    ///            deeper than the previous node or if a second root
+    ///            is added.
+    pub fn push(&mut self, depth: usize, content: T) -> Result<NodeRef> {
+        if depth > self.stack.len() {
+            return Err(invalid_input(&format!(
+                "Invalid depth {}, expected at most {}.",
+                depth,
+                self.stack.len()
+            )));
+        }
+
+        let node_ref = match depth {
+            0 => self.tree.root(content)?,
+            _ => self.tree.child_node(self.stack[depth - 1], content)?,
+        };
+        self.stack.truncate(depth);
+        self.stack.push(node_ref);
+
+        Ok(node_ref)
+    }
+
+    /// Get the tree built so far.
+    pub fn tree(&self) -> &Tree<T> {
+        &self.tree
+    }
+
+    /// Finish building.
+    ///
+    /// *Returns:* The built tree.
+    pub fn finish(self) -> Tree<T> {
+        self.tree
+    }
+}
+
+impl<T> Default for StreamingBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
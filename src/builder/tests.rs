@@ -0,0 +1,43 @@
+use super::*;
+use crate::{ErrorKind, TreeError};
+
+#[test]
+fn streaming_builder() {
+    let mut builder = StreamingBuilder::new();
+    for (depth, name) in [(0, "/"), (1, "usr"), (2, "bin"), (2, "lib"), (1, "etc")] {
+        builder.push(depth, name).unwrap();
+    }
+    let tree = builder.finish();
+
+    let nodes: Vec<(usize, &str)> = tree
+        .depth_first(true)
+        .unwrap()
+        .map(|node_ref| {
+            let depth = tree.ancestors(node_ref).unwrap().count();
+            (depth, *tree.get(node_ref).unwrap())
+        })
+        .collect();
+    assert_eq!(
+        nodes,
+        vec![(0, "/"), (1, "usr"), (2, "bin"), (2, "lib"), (1, "etc")]
+    );
+}
+
+#[test]
+fn streaming_builder_errors() {
+    let mut builder = StreamingBuilder::new();
+    assert_eq!(
+        builder.push(1, "a").unwrap_err(),
+        TreeError::with_kind(
+            ErrorKind::InvalidInput,
+            "Invalid depth 1, expected at most 0."
+        )
+    );
+    builder.push(0, "a").unwrap();
+    assert!(builder.push(2, "b").is_err());
+    assert_eq!(
+        builder.push(0, "c").unwrap_err(),
+        TreeError::new("Another root node already exists.")
+    );
+    assert_eq!(builder.tree().len(), 1);
+}
@@ -0,0 +1,114 @@
+use std::fmt;
+use std::iter::FusedIterator;
+
+use super::{NodeRef, Profiled, Tree};
+
+/// Positional information of a node within its tree.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct NodeContext {
+    /// The node itself.
+    pub node_ref: NodeRef,
+    /// The parent node, or `None` for nodes without parent
+    /// or whose parent was removed.
+    pub parent_ref: Option<NodeRef>,
+    /// Position of the node among the live children of its parent,
+    /// 0 for nodes without parent.
+    pub sibling_index: usize,
+    /// Distance from the topmost ancestor, 0 for nodes without parent.
+    pub depth: usize,
+}
+
+/// Extension trait for iterators returning node references.
+pub trait NodeIteratorExt: Iterator<Item = NodeRef> + Sized {
+    /// Turn an iterator of node references into an iterator
+    /// of [NodeContext] items.
+    ///
+    /// The context is resolved lazily from the tree, when each item is
+    /// requested. References to removed nodes are skipped.
+    ///
+    /// *Arguments:*
+    /// * `tree` - The tree the node references belong to.
+    ///
+    /// *Returns:* An iterator returning a [NodeContext] for each node.
+    fn with_context<T>(self, tree: &Tree<T>) -> WithContext<'_, Self, T> {
+        WithContext { iter: self, tree }
+    }
+
+    /// Measure the time spent on each node returned by the iterator.
+    ///
+    /// See [Profiled] for how times are measured and reported.
+    ///
+    /// *Arguments:*
+    /// * `tree` - The tree the node references belong to.
+    ///
+    /// *Returns:* An iterator returning the same nodes.
+    fn profiled<T>(self, tree: &Tree<T>) -> Profiled<'_, Self, T> {
+        Profiled::new(self, tree)
+    }
+}
+
+impl<I: Iterator<Item = NodeRef>> NodeIteratorExt for I {}
+
+/// Iterator returning the [NodeContext] of each node of another
+/// iterator, created by [NodeIteratorExt::with_context].
+pub struct WithContext<'a, I, T> {
+    iter: I,
+    tree: &'a Tree<T>,
+}
+
+impl<'a, I: Iterator<Item = NodeRef>, T> Iterator for WithContext<'a, I, T> {
+    type Item = NodeContext;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let node_ref = self.iter.next()?;
+            if self.tree.get_node(node_ref).is_none() {
+                continue;
+            }
+
+            let parent_ref = self.tree.live_parent(node_ref);
+            let sibling_index = parent_ref
+                .and_then(|parent_ref| {
+                    self.tree
+                        .live_children(parent_ref)
+                        .position(|child_ref| child_ref == node_ref)
+                })
+                .unwrap_or(0);
+
+            return Some(NodeContext {
+                node_ref,
+                parent_ref,
+                sibling_index,
+                depth: self.tree.ancestors(node_ref).unwrap().count(),
+            });
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<'a, I: FusedIterator<Item = NodeRef>, T> FusedIterator for WithContext<'a, I, T> {}
+
+impl<'a, I: Clone, T> Clone for WithContext<'a, I, T> {
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            tree: self.tree,
+        }
+    }
+}
+
+impl<'a, I: fmt::Debug, T> fmt::Debug for WithContext<'a, I, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WithContext")
+            .field("iter", &self.iter)
+            .finish_non_exhaustive()
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
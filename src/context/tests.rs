@@ -0,0 +1,93 @@
+use super::*;
+
+#[test]
+fn with_context() {
+    let mut tree = Tree::new();
+    let root = tree.root("/").unwrap();
+    let etc = tree.child_node(root, "etc").unwrap();
+    let usr = tree.child_node(root, "usr").unwrap();
+    let lib = tree.child_node(usr, "lib").unwrap();
+
+    let iter = tree.depth_first(true).unwrap().with_context(&tree);
+    assert!(format!("{:?}", iter).starts_with("WithContext"));
+    let contexts: Vec<NodeContext> = iter.clone().collect();
+    assert_eq!(iter.count(), 4);
+
+    assert_eq!(
+        contexts,
+        vec![
+            NodeContext {
+                node_ref: root,
+                parent_ref: None,
+                sibling_index: 0,
+                depth: 0
+            },
+            NodeContext {
+                node_ref: etc,
+                parent_ref: Some(root),
+                sibling_index: 0,
+                depth: 1
+            },
+            NodeContext {
+                node_ref: usr,
+                parent_ref: Some(root),
+                sibling_index: 1,
+                depth: 1
+            },
+            NodeContext {
+                node_ref: lib,
+                parent_ref: Some(usr),
+                sibling_index: 0,
+                depth: 2
+            },
+        ]
+    );
+}
+
+#[test]
+fn with_context_skips_removed() {
+    let mut tree = Tree::new();
+    let node_a = tree.node("a");
+    let node_b = tree.node("b");
+    tree.remove(node_a).unwrap();
+
+    let contexts: Vec<NodeRef> = vec![node_a, node_b]
+        .into_iter()
+        .with_context(&tree)
+        .map(|context| context.node_ref)
+        .collect();
+
+    assert_eq!(contexts, vec![node_b]);
+}
+
+#[test]
+fn with_context_after_removal() {
+    let mut tree = Tree::new();
+    let root = tree.root("/").unwrap();
+    let etc = tree.child_node(root, "etc").unwrap();
+    let usr = tree.child_node(root, "usr").unwrap();
+    let lib = tree.child_node(usr, "lib").unwrap();
+    let bin = tree.child_node(lib, "bin").unwrap();
+    tree.remove(etc).unwrap();
+    tree.remove(lib).unwrap();
+
+    let contexts: Vec<NodeContext> = vec![usr, bin].into_iter().with_context(&tree).collect();
+
+    assert_eq!(
+        contexts,
+        vec![
+            NodeContext {
+                node_ref: usr,
+                parent_ref: Some(root),
+                sibling_index: 0,
+                depth: 1
+            },
+            NodeContext {
+                node_ref: bin,
+                parent_ref: None,
+                sibling_index: 0,
+                depth: 0
+            },
+        ]
+    );
+}
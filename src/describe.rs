@@ -0,0 +1,65 @@
+use std::fmt::Debug;
+
+use super::{NodeRef, Tree};
+
+/// Maximum number of characters of the content shown by [Tree::describe].
+const PREVIEW_LEN: usize = 40;
+
+impl<T: Debug> Tree<T> {
+    /// Describe a node for logs and error messages.
+    ///
+    /// The description contains the index of the node, its depth, its
+    /// path as the sequence of sibling positions starting from the root,
+    /// and a preview of its content, for example
+    /// `node 17 at depth 2, path /1/0: "usr"`. Nodes which are not
+    /// reachable from the root are described as detached, with a path
+    /// starting from their topmost ancestor.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node to describe.
+    ///
+    /// *Returns:* The description of the node.
+    pub fn describe(&self, node_ref: NodeRef) -> String {
+        let content = match self.get(node_ref) {
+            None => return format!("node {} (removed)", node_ref.index()),
+            Some(content) => content,
+        };
+
+        let mut positions = Vec::new();
+        let mut top_ref = node_ref;
+        for ancestor_ref in self.ancestors(node_ref).unwrap() {
+            match self.child_position(ancestor_ref, top_ref) {
+                Some(position) if self.get_node(ancestor_ref).is_some() => {
+                    positions.push(position.to_string());
+                    top_ref = ancestor_ref;
+                }
+                _ => break,
+            }
+        }
+        positions.reverse();
+
+        let mut preview = format!("{:?}", content);
+        if preview.chars().count() > PREVIEW_LEN {
+            preview = preview.chars().take(PREVIEW_LEN - 1).collect();
+            preview.push('…');
+        }
+
+        format!(
+            "node {} at depth {}{}, path /{}: {}",
+            node_ref.index(),
+            positions.len(),
+            if Some(top_ref) == self.root {
+                ""
+            } else {
+                " (detached)"
+            },
+            positions.join("/"),
+            preview,
+        )
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
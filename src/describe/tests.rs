@@ -0,0 +1,25 @@
+use super::*;
+
+#[test]
+fn describe() {
+    let mut tree = Tree::new();
+    let root = tree.root("/".to_string()).unwrap();
+    tree.child_node(root, "etc".to_string()).unwrap();
+    let usr = tree.child_node(root, "usr".to_string()).unwrap();
+    let lib = tree.child_node(usr, "x".repeat(50)).unwrap();
+    let detached = tree.node("tmp".to_string());
+    let removed = tree.child_node(root, "var".to_string()).unwrap();
+    tree.remove(removed).unwrap();
+
+    assert_eq!(tree.describe(root), "node 0 at depth 0, path /: \"/\"");
+    assert_eq!(tree.describe(usr), "node 2 at depth 1, path /1: \"usr\"");
+    assert_eq!(
+        tree.describe(lib),
+        format!("node 3 at depth 2, path /1/0: \"{}…", "x".repeat(38))
+    );
+    assert_eq!(
+        tree.describe(detached),
+        "node 4 at depth 0 (detached), path /: \"tmp\""
+    );
+    assert_eq!(tree.describe(removed), "node 5 (removed)");
+}
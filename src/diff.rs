@@ -0,0 +1,130 @@
+use std::fmt::Debug;
+
+use super::{NodeRef, Tree, WalkPair};
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const RESET: &str = "\x1b[0m";
+
+/// Assert that two trees have the same structure and content.
+///
+/// On failure, the panic message contains the output of
+/// [Tree::diff_display], so that only the differing nodes
+/// need to be inspected.
+///
+/// ```ignore
+/// assert_tree_eq!(actual, expected);
+/// ```
+#[macro_export]
+macro_rules! assert_tree_eq {
+    ($left:expr, $right:expr $(,)?) => {{
+        let (left, right) = (&$left, &$right);
+        if !left.structural_eq(right) {
+            panic!(
+                "assertion failed: trees are not equal\n{}",
+                left.diff_display(right)
+            );
+        }
+    }};
+}
+
+impl<T: PartialEq> Tree<T> {
+    /// Compare two trees by structure and content.
+    ///
+    /// Node references, tombstones and slot numbering are ignored:
+    /// two trees are equal if their roots have equal content and the same
+    /// number of children, and so on recursively.
+    ///
+    /// *Arguments:*
+    /// * `other` - The tree to compare with.
+    ///
+    /// *Returns:* Whether the trees are structurally equal.
+    pub fn structural_eq(&self, other: &Tree<T>) -> bool {
+        if self.root.is_none() || other.root.is_none() {
+            return self.root.is_none() && other.root.is_none();
+        }
+
+        let mut equal = true;
+        self.walk_together(other, |pair| {
+            equal &= match pair {
+                WalkPair::Matched(node_ref, other_ref) => {
+                    self.get(node_ref) == other.get(other_ref)
+                }
+                _ => false,
+            };
+        })
+        .unwrap();
+        equal
+    }
+}
+
+impl<T: Debug + PartialEq> Tree<T> {
+    /// Render the differences between two trees.
+    ///
+    /// The output lists the nodes of both trees in depth-first order,
+    /// indented by depth, in a unified format: nodes only present in this
+    /// tree are prefixed with `-` and colored red, nodes only present in
+    /// `other` are prefixed with `+` and colored green. Nodes at the same
+    /// position with different content produce a `-` and a `+` line.
+    ///
+    /// *Arguments:*
+    /// * `other` - The tree to compare with, usually the expected tree.
+    ///
+    /// *Returns:* The rendered diff.
+    pub fn diff_display(&self, other: &Tree<T>) -> String {
+        let mut out = String::new();
+
+        match (self.root, other.root) {
+            (None, None) => {}
+            (Some(root_ref), None) => render_subtree(&mut out, self, root_ref, '-'),
+            (None, Some(root_ref)) => render_subtree(&mut out, other, root_ref, '+'),
+            (Some(_), Some(_)) => self
+                .walk_together(other, |pair| match pair {
+                    WalkPair::Matched(node_ref, other_ref) => {
+                        let (content, other_content) = (self.get(node_ref), other.get(other_ref));
+                        let depth = self.ancestors(node_ref).unwrap().count();
+                        if content == other_content {
+                            render_line(&mut out, ' ', depth, content.unwrap());
+                        } else {
+                            render_line(&mut out, '-', depth, content.unwrap());
+                            render_line(&mut out, '+', depth, other_content.unwrap());
+                        }
+                    }
+                    WalkPair::OnlySelf(node_ref) => render_subtree(&mut out, self, node_ref, '-'),
+                    WalkPair::OnlyOther(node_ref) => render_subtree(&mut out, other, node_ref, '+'),
+                })
+                .unwrap(),
+        }
+
+        out
+    }
+}
+
+fn render_subtree<T: Debug>(out: &mut String, tree: &Tree<T>, node_ref: NodeRef, marker: char) {
+    for descendant in tree.depth_first_of(node_ref, true).unwrap() {
+        let depth = tree.ancestors(descendant).unwrap().count();
+        render_line(out, marker, depth, tree.get(descendant).unwrap());
+    }
+}
+
+fn render_line<T: Debug>(out: &mut String, marker: char, depth: usize, content: &T) {
+    let color = match marker {
+        '-' => RED,
+        '+' => GREEN,
+        _ => "",
+    };
+    let reset = if color.is_empty() { "" } else { RESET };
+    out.push_str(&format!(
+        "{}{} {}{:?}{}\n",
+        color,
+        marker,
+        "  ".repeat(depth),
+        content,
+        reset
+    ));
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,41 @@
+use super::*;
+
+fn sample(leaf: &'static str) -> Tree<&'static str> {
+    let mut tree = Tree::new();
+    let root = tree.root("root").unwrap();
+    let a = tree.child_node(root, "a").unwrap();
+    tree.child_node(a, leaf).unwrap();
+    tree
+}
+
+#[test]
+fn structural_eq() {
+    let mut tree = sample("b");
+    let extra = tree.node("x");
+    tree.remove(extra).unwrap();
+    assert!(tree.structural_eq(&sample("b")));
+    assert!(!tree.structural_eq(&sample("c")));
+    assert!(Tree::<i32>::new().structural_eq(&Tree::new()));
+    assert_tree_eq!(tree, sample("b"));
+}
+
+#[test]
+fn diff_display() {
+    let mut expected = sample("c");
+    let root = expected.get_root_ref().unwrap();
+    expected.child_node(root, "d").unwrap();
+
+    assert_eq!(
+        sample("b").diff_display(&expected),
+        format!(
+            "  \"root\"\n    \"a\"\n{}-     \"b\"{}\n{}+     \"c\"{}\n{}+   \"d\"{}\n",
+            RED, RESET, GREEN, RESET, GREEN, RESET
+        )
+    );
+}
+
+#[test]
+#[should_panic(expected = "trees are not equal")]
+fn assert_tree_eq_fails() {
+    assert_tree_eq!(sample("b"), sample("c"));
+}
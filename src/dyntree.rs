@@ -0,0 +1,157 @@
+use std::fmt::Debug;
+
+use super::{NodeRef, Tree};
+
+/// Object safe, read-only view of a tree.
+///
+/// The trait does not depend on the type of the node values, so code
+/// compiled separately, such as plugins, can traverse any tree through
+/// a `&dyn DynTree` and display its values with [Debug].
+///
+/// Nodes are identified by plain indices, which can be converted from
+/// and to node references with [NodeRef::as_index] and
+/// [NodeRef::from_index]. Methods taking the index of a node that does
+/// not exist return `None` or an empty result. Method names differ from
+/// the ones of [Tree] where the signatures differ, so that both can be
+/// used with the trait in scope.
+///
+/// ```ignore
+/// fn count_leaves(tree: &dyn DynTree) -> usize {
+///     tree.depth_first_indices()
+///         .into_iter()
+///         .filter(|node| tree.child_count(*node) == 0)
+///         .count()
+/// }
+/// ```
+pub trait DynTree {
+    /// Get the index of the root node, if any.
+    fn root_index(&self) -> Option<usize>;
+
+    /// Get the number of nodes in the tree.
+    fn len(&self) -> usize;
+
+    /// Check whether the tree contains no nodes.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Check whether a node exists.
+    ///
+    /// *Arguments:*
+    /// * `node` - Index of the node.
+    fn contains(&self, node: usize) -> bool;
+
+    /// Get the index of the parent of a node.
+    ///
+    /// *Arguments:*
+    /// * `node` - Index of the node.
+    ///
+    /// *Returns:* The parent or `None` if the node does not exist
+    ///            or has no parent.
+    fn parent_index(&self, node: usize) -> Option<usize>;
+
+    /// Get the indices of the children of a node, in order.
+    ///
+    /// The children are returned in a new vector rather than a slice,
+    /// since linked child storage does not keep them contiguous.
+    ///
+    /// *Arguments:*
+    /// * `node` - Index of the node.
+    ///
+    /// *Returns:* The children, empty if the node does not exist.
+    fn children(&self, node: usize) -> Vec<usize>;
+
+    /// Get the number of children of a node.
+    ///
+    /// *Arguments:*
+    /// * `node` - Index of the node.
+    fn child_count(&self, node: usize) -> usize {
+        self.children(node).len()
+    }
+
+    /// Get the value of a node for debug formatting.
+    ///
+    /// *Arguments:*
+    /// * `node` - Index of the node.
+    ///
+    /// *Returns:* The value or `None` if the node does not exist.
+    fn value_dbg(&self, node: usize) -> Option<&dyn Debug>;
+
+    /// Get the indices of the nodes reachable from the root,
+    /// in depth-first order.
+    fn depth_first_indices(&self) -> Vec<usize> {
+        let mut order = Vec::new();
+        let mut stack: Vec<usize> = self.root_index().into_iter().collect();
+        while let Some(node) = stack.pop() {
+            order.push(node);
+            stack.extend(self.children(node).into_iter().rev());
+        }
+        order
+    }
+}
+
+impl<T: Debug> DynTree for Tree<T> {
+    fn root_index(&self) -> Option<usize> {
+        self.root.map(NodeRef::as_index)
+    }
+
+    fn len(&self) -> usize {
+        Tree::len(self)
+    }
+
+    fn contains(&self, node: usize) -> bool {
+        self.get_node(NodeRef::from_index(node)).is_some()
+    }
+
+    fn parent_index(&self, node: usize) -> Option<usize> {
+        self.get_node(NodeRef::from_index(node))?
+            .parent
+            .map(NodeRef::as_index)
+    }
+
+    fn children(&self, node: usize) -> Vec<usize> {
+        let node_ref = NodeRef::from_index(node);
+        if !self.contains(node) {
+            return Vec::new();
+        }
+        self.live_children(node_ref)
+            .map(NodeRef::as_index)
+            .collect()
+    }
+
+    fn child_count(&self, node: usize) -> usize {
+        let node_ref = NodeRef::from_index(node);
+        if !self.contains(node) {
+            return 0;
+        }
+        self.live_children(node_ref).count()
+    }
+
+    fn value_dbg(&self, node: usize) -> Option<&dyn Debug> {
+        self.get(NodeRef::from_index(node))
+            .map(|value| value as &dyn Debug)
+    }
+}
+
+impl NodeRef {
+    /// Get the index identifying the node in a [DynTree].
+    pub fn as_index(self) -> usize {
+        self.index()
+    }
+
+    /// Create a node reference from an index used by a [DynTree].
+    ///
+    /// The reference is not checked against any tree: accessing
+    /// a node that does not exist returns `None` as usual.
+    ///
+    /// *Arguments:*
+    /// * `index` - Index of the node.
+    pub fn from_index(index: usize) -> Self {
+        NodeRef::new(index)
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
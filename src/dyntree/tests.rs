@@ -0,0 +1,39 @@
+use super::*;
+
+fn plugin_render(tree: &dyn DynTree) -> Vec<String> {
+    tree.depth_first_indices()
+        .into_iter()
+        .map(|node| format!("{:?}", tree.value_dbg(node).unwrap()))
+        .collect()
+}
+
+#[test]
+fn test_dyn_tree() {
+    let mut tree = Tree::new();
+    let root = tree.root("a").unwrap();
+    let b = tree.child_node(root, "b").unwrap();
+    tree.child_node(b, "c").unwrap();
+    let d = tree.child_node(root, "d").unwrap();
+
+    let dyn_tree: &dyn DynTree = &tree;
+    assert_eq!(dyn_tree.len(), 4);
+    assert_eq!(dyn_tree.root_index(), Some(root.as_index()));
+    assert_eq!(
+        dyn_tree.children(root.as_index()),
+        vec![b.as_index(), d.as_index()]
+    );
+    assert_eq!(dyn_tree.child_count(b.as_index()), 1);
+    assert_eq!(dyn_tree.parent_index(d.as_index()), Some(root.as_index()));
+    assert_eq!(NodeRef::from_index(d.as_index()), d);
+    assert_eq!(
+        plugin_render(dyn_tree),
+        vec!["\"a\"", "\"b\"", "\"c\"", "\"d\""]
+    );
+
+    tree.remove(d).unwrap();
+    let dyn_tree: &dyn DynTree = &tree;
+    assert!(!dyn_tree.contains(d.as_index()));
+    assert!(dyn_tree.value_dbg(d.as_index()).is_none());
+    assert_eq!(dyn_tree.children(root.as_index()), vec![b.as_index()]);
+    assert!(dyn_tree.children(100).is_empty());
+}
@@ -0,0 +1,276 @@
+use std::cmp::Reverse;
+
+use super::{node_not_found, NodeRef, RemoveBehavior, Result, Tree};
+
+/// Tree dropping the least recently used subtrees above a node budget.
+///
+/// This allows browsing enormous hierarchies which are loaded lazily,
+/// like remote file systems, while keeping only the recently visited
+/// parts in memory. Whenever the number of nodes exceeds the budget,
+/// the descendants of the least recently used nodes are removed and
+/// those nodes are marked as evicted, with the same mark used by
+/// [Tree::mark_children_unknown]. The evicted nodes keep their content
+/// and their place in the tree, so their children can be loaded again
+/// with [EvictingTree::load_children].
+///
+/// Accessing a node through [EvictingTree::touch] or
+/// [EvictingTree::load_children] also counts as an access to its
+/// ancestors. Nodes deeper in the tree are evicted first among nodes
+/// last used at the same time, so eviction starts from the leaves.
+/// Locked subtrees are never evicted, so the budget is approximate.
+///
+/// ```ignore
+/// let mut tree = EvictingTree::new(10_000);
+/// let root = tree.root(bucket)?;
+/// tree.mark_evicted(root)?;
+///
+/// // When a folder is expanded
+/// let entries = tree.load_children(folder, |folder| list_entries(folder))?;
+/// ```
+pub struct EvictingTree<T> {
+    tree: Tree<T>,
+    budget: usize,
+    last_used: Vec<u64>,
+    clock: u64,
+}
+
+impl<T> EvictingTree<T> {
+    /// Create a new empty tree.
+    ///
+    /// *Arguments:*
+    /// * `budget` - Maximum number of nodes kept in the tree.
+    ///
+    /// *Returns:* The new tree.
+    pub fn new(budget: usize) -> Self {
+        Self::from_tree(Tree::new(), budget)
+    }
+
+    /// Wrap an existing tree, evicting nodes if it exceeds the budget.
+    ///
+    /// All the nodes are considered as used at the same time.
+    ///
+    /// *Arguments:*
+    /// * `tree` - The tree to wrap.
+    /// * `budget` - Maximum number of nodes kept in the tree.
+    ///
+    /// *Returns:* The wrapped tree.
+    pub fn from_tree(tree: Tree<T>, budget: usize) -> Self {
+        let mut evicting = Self {
+            last_used: vec![0; tree.nodes.len()],
+            tree,
+            budget,
+            clock: 0,
+        };
+        evicting.evict_to_budget();
+        evicting
+    }
+
+    /// Get the maximum number of nodes kept in the tree.
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// Change the maximum number of nodes kept in the tree.
+    ///
+    /// *Arguments:*
+    /// * `budget` - The new budget.
+    ///
+    /// *Returns:* The number of evicted nodes.
+    pub fn set_budget(&mut self, budget: usize) -> usize {
+        self.budget = budget;
+        self.evict_to_budget()
+    }
+
+    /// Create a root node, see [Tree::root].
+    pub fn root(&mut self, content: T) -> Result<NodeRef> {
+        let root_ref = self.tree.root(content)?;
+        self.touch(root_ref)?;
+        Ok(root_ref)
+    }
+
+    /// Create a node and append it to a parent node, see [Tree::child_node].
+    ///
+    /// This may evict other subtrees to stay within the budget.
+    pub fn child_node(&mut self, parent: NodeRef, content: T) -> Result<NodeRef> {
+        let child_ref = self.tree.child_node(parent, content)?;
+        self.touch(child_ref)?;
+        self.evict_to_budget();
+        Ok(child_ref)
+    }
+
+    /// Get the content of a node.
+    ///
+    /// This does not count as an access, use [EvictingTree::touch]
+    /// to protect the node from eviction.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    ///
+    /// *Returns:* The content or `None` if the node does not exist.
+    pub fn get(&self, node_ref: NodeRef) -> Option<&T> {
+        self.tree.get(node_ref)
+    }
+
+    /// Record an access to a node and its ancestors.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    ///
+    /// *Returns:* Result indicating whether the operations was successful.
+    ///            Returns an error if the node does not exist.
+    pub fn touch(&mut self, node_ref: NodeRef) -> Result<()> {
+        if self.tree.get(node_ref).is_none() {
+            return Err(node_not_found(node_ref));
+        }
+        if self.last_used.len() < self.tree.nodes.len() {
+            self.last_used.resize(self.tree.nodes.len(), 0);
+        }
+
+        self.clock += 1;
+        let mut next = Some(node_ref);
+        while let Some(node_ref) = next {
+            self.last_used[node_ref.index()] = self.clock;
+            next = self.tree.get_node(node_ref).unwrap().parent;
+        }
+        Ok(())
+    }
+
+    /// Mark the children of a node as evicted, so that they are loaded
+    /// on the next call to [EvictingTree::load_children].
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    ///
+    /// *Returns:* Result indicating whether the operations was successful.
+    ///            Returns an error if the node does not exist.
+    pub fn mark_evicted(&mut self, node_ref: NodeRef) -> Result<()> {
+        self.tree.mark_children_unknown(node_ref)
+    }
+
+    /// Check whether the children of a node were evicted or not loaded yet.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    ///
+    /// *Returns:* Whether the children must be loaded again.
+    ///            Returns `false` if the node does not exist.
+    pub fn is_evicted(&self, node_ref: NodeRef) -> bool {
+        self.tree.has_unknown_children(node_ref)
+    }
+
+    /// Get the children of a node, loading them first if they were evicted.
+    ///
+    /// The access is recorded for the node and its children, then other
+    /// subtrees are evicted if needed to stay within the budget.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    /// * `load_fn` - Function returning the contents of the children
+    ///   from the content of the node. It is only called if the node is
+    ///   marked as evicted.
+    ///
+    /// *Returns:* The children of the node. Returns an error if the node
+    ///            does not exist, or if it is locked and its children
+    ///            must be loaded.
+    pub fn load_children<I>(
+        &mut self,
+        node_ref: NodeRef,
+        load_fn: impl FnOnce(&T) -> I,
+    ) -> Result<Vec<NodeRef>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let content = match self.tree.get(node_ref) {
+            None => return Err(node_not_found(node_ref)),
+            Some(content) => content,
+        };
+        if self.tree.has_unknown_children(node_ref) {
+            let contents = load_fn(content);
+            self.tree.populate_children(node_ref, contents)?;
+        }
+
+        let children: Vec<NodeRef> = self.tree.live_children(node_ref).collect();
+        self.touch(node_ref)?;
+        for child_ref in &children {
+            self.last_used[child_ref.index()] = self.clock;
+        }
+        self.evict_to_budget();
+        Ok(children)
+    }
+
+    /// Evict the descendants of a node, regardless of the budget.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    ///
+    /// *Returns:* The number of evicted nodes. Returns an error if
+    ///            the node does not exist or is locked.
+    pub fn evict(&mut self, node_ref: NodeRef) -> Result<usize> {
+        let subtree: Vec<NodeRef> = self.tree.depth_first_of(node_ref, true)?.collect();
+        self.tree.check_subtree_unlocked(&subtree)?;
+
+        let children: Vec<NodeRef> = self.tree.live_children(node_ref).collect();
+        let mut evicted = 0;
+        for child_ref in children {
+            evicted += self
+                .tree
+                .remove_with(child_ref, RemoveBehavior::Recursive)?
+                .removed;
+        }
+        self.tree.mark_children_unknown(node_ref)?;
+        Ok(evicted)
+    }
+
+    /// Get the underlying tree.
+    pub fn tree(&self) -> &Tree<T> {
+        &self.tree
+    }
+
+    /// Drop the access times and get the underlying tree back.
+    pub fn into_tree(self) -> Tree<T> {
+        self.tree
+    }
+
+    /// Evict the least recently used subtrees until the tree fits
+    /// into the budget.
+    ///
+    /// Nodes are considered in a single pass, so nodes whose
+    /// children are evicted do not become candidates themselves.
+    fn evict_to_budget(&mut self) -> usize {
+        if self.tree.len() <= self.budget {
+            return 0;
+        }
+        let root_ref = match self.tree.root {
+            None => return 0,
+            Some(root_ref) => root_ref,
+        };
+
+        let mut candidates: Vec<(u64, Reverse<usize>, NodeRef)> = self
+            .tree
+            .breadth_first_with_level(root_ref, usize::MAX)
+            .unwrap()
+            .filter(|(node_ref, _)| self.tree.live_children(*node_ref).next().is_some())
+            .map(|(node_ref, level)| {
+                let last_used = self.last_used.get(node_ref.index()).copied().unwrap_or(0);
+                (last_used, Reverse(level), node_ref)
+            })
+            .collect();
+        candidates.sort_unstable();
+
+        let mut evicted = 0;
+        for (_, _, node_ref) in candidates {
+            if self.tree.len() <= self.budget {
+                break;
+            }
+            if self.tree.get(node_ref).is_some() {
+                evicted += self.evict(node_ref).unwrap_or(0);
+            }
+        }
+        evicted
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
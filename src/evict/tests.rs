@@ -0,0 +1,45 @@
+use super::*;
+
+#[test]
+fn evict_least_recently_used() {
+    let mut tree = EvictingTree::new(6);
+    let root = tree.root("/").unwrap();
+    let a = tree.child_node(root, "a").unwrap();
+    let b = tree.child_node(root, "b").unwrap();
+    tree.child_node(a, "a1").unwrap();
+    tree.child_node(a, "a2").unwrap();
+    tree.child_node(b, "b1").unwrap();
+    assert_eq!(tree.tree().len(), 6);
+
+    tree.touch(a).unwrap();
+    let b2 = tree.child_node(b, "b2").unwrap();
+    assert_eq!(tree.tree().len(), 5);
+    assert!(tree.is_evicted(a));
+    assert_eq!(tree.get(b2), Some(&"b2"));
+
+    let children = tree.load_children(a, |_| vec!["a1", "a2"]).unwrap();
+    assert_eq!(children.len(), 2);
+    assert!(!tree.is_evicted(a));
+    assert!(tree.is_evicted(b));
+    assert_eq!(tree.tree().len(), 5);
+
+    let loaded = tree.load_children(a, |_| -> Vec<&str> { panic!("already loaded") });
+    assert_eq!(loaded.unwrap(), children);
+}
+
+#[test]
+fn evict_respects_locks() {
+    let mut tree = EvictingTree::new(10);
+    let root = tree.root(0).unwrap();
+    let a = tree.child_node(root, 1).unwrap();
+    tree.child_node(a, 2).unwrap();
+    tree.child_node(root, 3).unwrap();
+
+    let mut inner = tree.into_tree();
+    inner.lock_subtree(a).unwrap();
+    let mut tree = EvictingTree::from_tree(inner, 10);
+    assert!(tree.evict(a).is_err());
+    assert_eq!(tree.set_budget(2), 0);
+    assert_eq!(tree.tree().len(), 4);
+    assert!(!tree.is_evicted(a));
+}
@@ -0,0 +1,285 @@
+use std::iter;
+use std::mem;
+use std::ops::Range;
+
+use super::{not_found, NodeRef, NodeRemap, Result, Tree};
+
+const NO_PARENT: u32 = u32::MAX;
+
+/// Read-only tree with a flat, offset-based layout.
+///
+/// Nodes are stored in depth-first order, so that each subtree occupies
+/// a contiguous range and traversals are linear scans. The structure is
+/// only made of plain integer arrays, which makes it suitable for
+/// zero-copy loading: with the `rkyv` feature, the archived form
+/// (`ArchivedFrozenTree`) offers the same read and traversal API
+/// directly on the serialized bytes.
+///
+/// Create one with [Tree::freeze].
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub struct FrozenTree<T> {
+    contents: Vec<T>,
+    parents: Vec<u32>,
+    ends: Vec<u32>,
+}
+
+impl<T> Tree<T> {
+    /// Convert the tree into a [FrozenTree].
+    ///
+    /// Only the nodes reachable from the root are kept. Node references
+    /// of the frozen tree are different from the ones of this tree: use
+    /// the returned [NodeRemap] to translate them.
+    ///
+    /// *Returns:* The frozen tree and the mapping from the node references
+    ///            of this tree to the ones of the frozen tree.
+    ///
+    /// *Panics:* If the tree contains more than `u32::MAX - 1` nodes.
+    pub fn freeze(mut self) -> (FrozenTree<T>, NodeRemap) {
+        let order: Vec<NodeRef> = match self.depth_first(true) {
+            Ok(nodes) => nodes.collect(),
+            Err(_) => Vec::new(),
+        };
+        assert!(
+            order.len() < NO_PARENT as usize,
+            "Tree is too large to freeze."
+        );
+
+        let mut map = vec![None; self.nodes.len()];
+        for (id, node_ref) in order.iter().enumerate() {
+            map[node_ref.index()] = Some(NodeRef::new(id));
+        }
+
+        let mut nodes = mem::take(&mut self.nodes);
+        self.len = 0;
+        self.unreachable = 0;
+        self.free.clear();
+
+        let mut frozen = FrozenTree {
+            contents: Vec::with_capacity(order.len()),
+            parents: Vec::with_capacity(order.len()),
+            ends: (1..=order.len() as u32).collect(),
+        };
+        for node_ref in order {
+            let node = nodes[node_ref.index()].take().unwrap();
+            let parent = node.parent.and_then(|parent_ref| map[parent_ref.index()]);
+            frozen.contents.push(node.content);
+            frozen
+                .parents
+                .push(parent.map_or(NO_PARENT, |parent_ref| parent_ref.index() as u32));
+        }
+        for id in (1..frozen.parents.len()).rev() {
+            let parent = frozen.parents[id] as usize;
+            frozen.ends[parent] = frozen.ends[parent].max(frozen.ends[id]);
+        }
+
+        (frozen, NodeRemap { map })
+    }
+}
+
+impl<T> FrozenTree<T> {
+    /// Get the number of nodes in the tree.
+    pub fn len(&self) -> usize {
+        self.contents.len()
+    }
+
+    /// Check whether the tree contains no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.contents.is_empty()
+    }
+
+    /// Get immutable reference to the node content.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] object indicating which node
+    ///   content to retrieve.
+    ///
+    /// *Returns:* Reference to the object contained in the node
+    ///            or `None` if the node does not exist.
+    pub fn get(&self, node_ref: NodeRef) -> Option<&T> {
+        self.contents.get(node_ref.index())
+    }
+
+    /// Get the reference of the root node.
+    pub fn get_root_ref(&self) -> Option<NodeRef> {
+        root_ref(self.len())
+    }
+
+    /// Get reference to the parent node.
+    ///
+    /// *Arguments:*
+    /// * `child_ref` - [NodeRef] of the child node.
+    ///
+    /// *Returns:* A reference to the parent node or `None` if no
+    ///            parent exists. Returns error if the node does not exist.
+    pub fn get_parent(&self, child_ref: NodeRef) -> Result<Option<NodeRef>> {
+        parent(self.len(), child_ref, |id| self.parents[id])
+    }
+
+    /// Get an iterator over the children of a node.
+    ///
+    /// *Arguments:*
+    /// * `parent_ref` - [NodeRef] of the parent node.
+    ///
+    /// *Returns:* Iterator returning node references to the children.
+    ///            Returns error if the parent reference is invalid.
+    pub fn get_children(&self, parent_ref: NodeRef) -> Result<impl Iterator<Item = NodeRef> + '_> {
+        children(self.len(), parent_ref, move |id| self.ends[id])
+    }
+
+    /// Get an iterator traversing a node and its descendants
+    /// in depth-first order.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the starting node.
+    /// * `include_start` - If true, iteration starts with the
+    ///   starting node instead of with the first child thereof.
+    ///
+    /// *Returns:* An iterator returning the node references in
+    ///            depth-first order. Returns error if the node does not exist.
+    pub fn depth_first_of(
+        &self,
+        node_ref: NodeRef,
+        include_start: bool,
+    ) -> Result<impl Iterator<Item = NodeRef>> {
+        let ids = depth_first_of(self.len(), node_ref, include_start, |id| self.ends[id])?;
+        Ok(ids.map(NodeRef::new))
+    }
+
+    /// Get an iterator traversing all nodes in the tree in a
+    /// depth-first order.
+    ///
+    /// *Arguments:*
+    /// * `include_root` - If true, iteration starts with the
+    ///   root node instead of with the first child thereof.
+    ///
+    /// *Returns:* An iterator returning the node references in
+    ///            depth-first order. Returns error if no root node exist.
+    pub fn depth_first(&self, include_root: bool) -> Result<impl Iterator<Item = NodeRef>> {
+        depth_first(self.len(), include_root)
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<T: rkyv::Archive> ArchivedFrozenTree<T> {
+    /// Get the number of nodes in the tree.
+    pub fn len(&self) -> usize {
+        self.contents.len()
+    }
+
+    /// Check whether the tree contains no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.contents.is_empty()
+    }
+
+    /// Get reference to the archived node content.
+    pub fn get(&self, node_ref: NodeRef) -> Option<&T::Archived> {
+        self.contents.get(node_ref.index())
+    }
+
+    /// Get the reference of the root node.
+    pub fn get_root_ref(&self) -> Option<NodeRef> {
+        root_ref(self.len())
+    }
+
+    /// See [FrozenTree::get_parent].
+    pub fn get_parent(&self, child_ref: NodeRef) -> Result<Option<NodeRef>> {
+        parent(self.len(), child_ref, |id| self.parents[id].to_native())
+    }
+
+    /// See [FrozenTree::get_children].
+    pub fn get_children(&self, parent_ref: NodeRef) -> Result<impl Iterator<Item = NodeRef> + '_> {
+        children(self.len(), parent_ref, move |id| self.ends[id].to_native())
+    }
+
+    /// See [FrozenTree::depth_first_of].
+    pub fn depth_first_of(
+        &self,
+        node_ref: NodeRef,
+        include_start: bool,
+    ) -> Result<impl Iterator<Item = NodeRef>> {
+        let ids = depth_first_of(self.len(), node_ref, include_start, |id| {
+            self.ends[id].to_native()
+        })?;
+        Ok(ids.map(NodeRef::new))
+    }
+
+    /// See [FrozenTree::depth_first].
+    pub fn depth_first(&self, include_root: bool) -> Result<impl Iterator<Item = NodeRef>> {
+        depth_first(self.len(), include_root)
+    }
+}
+
+// Navigation shared by the frozen tree and its archived form,
+// which only differ in how the offsets are read.
+
+fn root_ref(len: usize) -> Option<NodeRef> {
+    if len == 0 {
+        None
+    } else {
+        Some(NodeRef::new(0))
+    }
+}
+
+fn parent(
+    len: usize,
+    child_ref: NodeRef,
+    parent_of: impl Fn(usize) -> u32,
+) -> Result<Option<NodeRef>> {
+    if child_ref.index() >= len {
+        return Err(not_found("Child node does not exist."));
+    }
+    Ok(match parent_of(child_ref.index()) {
+        NO_PARENT => None,
+        id => Some(NodeRef::new(id as usize)),
+    })
+}
+
+fn children(
+    len: usize,
+    parent_ref: NodeRef,
+    end_of: impl Fn(usize) -> u32,
+) -> Result<impl Iterator<Item = NodeRef>> {
+    if parent_ref.index() >= len {
+        return Err(not_found("Parent node does not exist."));
+    }
+    let end = end_of(parent_ref.index()) as usize;
+    let mut next = parent_ref.index() + 1;
+    Ok(iter::from_fn(move || {
+        if next >= end {
+            return None;
+        }
+        let child_ref = NodeRef::new(next);
+        next = end_of(next) as usize;
+        Some(child_ref)
+    }))
+}
+
+fn depth_first_of(
+    len: usize,
+    node_ref: NodeRef,
+    include_start: bool,
+    end_of: impl Fn(usize) -> u32,
+) -> Result<Range<usize>> {
+    if node_ref.index() >= len {
+        return Err(not_found("Parent node does not exist."));
+    }
+    let start = node_ref.index() + if include_start { 0 } else { 1 };
+    Ok(start..end_of(node_ref.index()) as usize)
+}
+
+fn depth_first(len: usize, include_root: bool) -> Result<impl Iterator<Item = NodeRef>> {
+    if len == 0 {
+        return Err(not_found("Cannot iterate a tree without a root node."));
+    }
+    let start = if include_root { 0 } else { 1 };
+    Ok((start..len).map(NodeRef::new))
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,73 @@
+use super::*;
+
+fn sample() -> (Tree<String>, Vec<NodeRef>) {
+    /*
+     *       a
+     *     / | \
+     *    b  e  f
+     *   / \
+     *  c   d
+     */
+    let mut tree = Tree::new();
+    let a = tree.root("a".to_string()).unwrap();
+    let removed = tree.child_node(a, "removed".to_string()).unwrap();
+    let b = tree.child_node(a, "b".to_string()).unwrap();
+    let c = tree.child_node(b, "c".to_string()).unwrap();
+    let d = tree.child_node(b, "d".to_string()).unwrap();
+    let e = tree.child_node(a, "e".to_string()).unwrap();
+    let f = tree.child_node(a, "f".to_string()).unwrap();
+    tree.node("detached".to_string());
+    tree.remove(removed).unwrap();
+    (tree, vec![a, b, c, d, e, f])
+}
+
+#[test]
+fn freeze() {
+    let (tree, refs) = sample();
+    let (frozen, remap) = tree.freeze();
+    let refs: Vec<NodeRef> = refs.iter().map(|r| remap.get(*r).unwrap()).collect();
+
+    assert_eq!(frozen.len(), 6);
+    assert_eq!(frozen.get_root_ref(), Some(refs[0]));
+    assert_eq!(frozen.get(refs[3]), Some(&"d".to_string()));
+    assert_eq!(frozen.get_parent(refs[3]), Ok(Some(refs[1])));
+    assert_eq!(frozen.get_parent(refs[0]), Ok(None));
+    assert_eq!(
+        frozen.get_children(refs[0]).unwrap().collect::<Vec<_>>(),
+        vec![refs[1], refs[4], refs[5]]
+    );
+    assert_eq!(
+        frozen
+            .depth_first_of(refs[1], false)
+            .unwrap()
+            .collect::<Vec<_>>(),
+        vec![refs[2], refs[3]]
+    );
+    assert_eq!(frozen.depth_first(true).unwrap().collect::<Vec<_>>(), refs);
+    assert!(Tree::<i32>::new().freeze().0.depth_first(true).is_err());
+}
+
+#[test]
+#[cfg(feature = "rkyv")]
+fn archived() {
+    let (tree, _) = sample();
+    let (frozen, _) = tree.freeze();
+    let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&frozen).unwrap();
+    let archived = rkyv::access::<ArchivedFrozenTree<String>, rkyv::rancor::Error>(&bytes).unwrap();
+
+    assert_eq!(
+        archived.depth_first(true).unwrap().collect::<Vec<_>>(),
+        frozen.depth_first(true).unwrap().collect::<Vec<_>>()
+    );
+    for node_ref in frozen.depth_first(true).unwrap() {
+        assert_eq!(
+            archived.get(node_ref).map(|s| s.as_str()),
+            frozen.get(node_ref).map(|s| s.as_str())
+        );
+        assert_eq!(archived.get_parent(node_ref), frozen.get_parent(node_ref));
+        assert_eq!(
+            archived.get_children(node_ref).unwrap().collect::<Vec<_>>(),
+            frozen.get_children(node_ref).unwrap().collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,122 @@
+//! Building trees from the file system.
+//!
+//! This module is available with the `fs` feature.
+
+use super::{NodeRef, Tree};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Options controlling how [Tree::from_dir] walks a directory.
+#[derive(Debug, Clone, Default)]
+pub struct DirOptions {
+    /// Whether to descend into directories reached through symbolic links.
+    /// Links pointing back to one of their ancestors are never followed.
+    pub follow_symlinks: bool,
+    /// Maximum depth of the walk, where the children of the starting
+    /// directory are at depth 1. `None` walks the whole hierarchy.
+    pub max_depth: Option<usize>,
+    /// Whether to include entries whose name starts with a dot.
+    pub include_hidden: bool,
+}
+
+/// Information about a file system entry.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DirEntryInfo {
+    /// Full path of the entry.
+    pub path: PathBuf,
+    /// Whether the entry is a directory, or a link to one.
+    pub is_dir: bool,
+    /// Whether the entry is a symbolic link.
+    pub is_symlink: bool,
+    /// Size of the entry in bytes.
+    pub size: u64,
+}
+
+impl DirEntryInfo {
+    fn new(path: PathBuf) -> io::Result<Self> {
+        let link_metadata = fs::symlink_metadata(&path)?;
+        let is_symlink = link_metadata.file_type().is_symlink();
+        let metadata = if is_symlink {
+            fs::metadata(&path).unwrap_or(link_metadata)
+        } else {
+            link_metadata
+        };
+
+        Ok(Self {
+            path,
+            is_dir: metadata.is_dir(),
+            is_symlink,
+            size: metadata.len(),
+        })
+    }
+}
+
+impl Tree<DirEntryInfo> {
+    /// Create a tree by walking a directory.
+    ///
+    /// The starting directory is the root node. The entries of each
+    /// directory are sorted by file name. Requires the `fs` feature.
+    ///
+    /// *Arguments:*
+    /// * `path` - Path of the directory to walk.
+    /// * `options` - [DirOptions] controlling the walk.
+    ///
+    /// *Returns:* A tree with a node per file system entry.
+    ///            Returns error if any entry cannot be read.
+    pub fn from_dir(path: impl AsRef<Path>, options: &DirOptions) -> io::Result<Self> {
+        let mut tree = Tree::new();
+        let root_info = DirEntryInfo::new(path.as_ref().to_path_buf())?;
+        let root_ref = tree.root(root_info).unwrap();
+
+        let mut visited = Vec::new();
+        walk(&mut tree, root_ref, 1, options, &mut visited)?;
+
+        Ok(tree)
+    }
+}
+
+fn walk(
+    tree: &mut Tree<DirEntryInfo>,
+    dir_ref: NodeRef,
+    depth: usize,
+    options: &DirOptions,
+    visited: &mut Vec<PathBuf>,
+) -> io::Result<()> {
+    let dir_info = tree.get(dir_ref).unwrap();
+    if !dir_info.is_dir || matches!(options.max_depth, Some(max_depth) if depth > max_depth) {
+        return Ok(());
+    }
+    if dir_info.is_symlink && !options.follow_symlinks {
+        return Ok(());
+    }
+
+    let canonical = fs::canonicalize(&dir_info.path)?;
+    if visited.contains(&canonical) {
+        return Ok(());
+    }
+
+    let mut entries = fs::read_dir(&dir_info.path)?.collect::<io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    visited.push(canonical);
+    for entry in entries {
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+        if hidden && !options.include_hidden {
+            continue;
+        }
+
+        let child_ref = tree
+            .child_node(dir_ref, DirEntryInfo::new(entry.path())?)
+            .unwrap();
+        walk(tree, child_ref, depth + 1, options, visited)?;
+    }
+    visited.pop();
+
+    Ok(())
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,78 @@
+use super::*;
+
+fn temp_dir(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("lineartree-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(path.join("usr/lib")).unwrap();
+    fs::create_dir_all(path.join(".git")).unwrap();
+    fs::write(path.join("usr/bin"), "binary").unwrap();
+    fs::write(path.join("usr/lib/libc.so"), "").unwrap();
+    path
+}
+
+fn names(tree: &Tree<DirEntryInfo>, root: &Path) -> Vec<String> {
+    tree.depth_first(false)
+        .unwrap()
+        .map(|node_ref| {
+            let path = &tree.get(node_ref).unwrap().path;
+            path.strip_prefix(root)
+                .unwrap()
+                .to_string_lossy()
+                .into_owned()
+        })
+        .collect()
+}
+
+#[test]
+fn from_dir() {
+    let root = temp_dir("from-dir");
+
+    let tree = Tree::from_dir(&root, &DirOptions::default()).unwrap();
+    assert_eq!(
+        names(&tree, &root),
+        vec!["usr", "usr/bin", "usr/lib", "usr/lib/libc.so"]
+    );
+
+    let bin = tree
+        .nth_in_depth_first(tree.get_root_ref().unwrap(), 2)
+        .unwrap()
+        .unwrap();
+    assert_eq!(tree.get(bin).unwrap().size, 6);
+    assert!(!tree.get(bin).unwrap().is_dir);
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn from_dir_options() {
+    let root = temp_dir("from-dir-options");
+    let options = DirOptions {
+        max_depth: Some(1),
+        include_hidden: true,
+        ..DirOptions::default()
+    };
+
+    let tree = Tree::from_dir(&root, &options).unwrap();
+    assert_eq!(names(&tree, &root), vec![".git", "usr"]);
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn from_dir_symlink_loop() {
+    let root = temp_dir("from-dir-symlink");
+    std::os::unix::fs::symlink(&root, root.join("usr/loop")).unwrap();
+    let options = DirOptions {
+        follow_symlinks: true,
+        ..DirOptions::default()
+    };
+
+    let tree = Tree::from_dir(&root, &options).unwrap();
+    assert_eq!(
+        names(&tree, &root),
+        vec!["usr", "usr/bin", "usr/lib", "usr/lib/libc.so", "usr/loop"]
+    );
+
+    fs::remove_dir_all(&root).unwrap();
+}
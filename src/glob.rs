@@ -0,0 +1,113 @@
+use super::{not_found, NodeRef, Result, Tree};
+
+impl<T: AsRef<str>> Tree<T> {
+    /// Find nodes whose path matches a glob pattern.
+    ///
+    /// The path of a node is made of the values of its ancestors, starting
+    /// below the root node, followed by its own value. Pattern segments are
+    /// separated by `/` and are matched against single values, where `*`
+    /// matches any sequence of characters and `?` matches one character.
+    /// A `**` segment matches any number of values, including none.
+    ///
+    /// ```ignore
+    /// // Matches "usr/lib", "usr/local/lib64", ...
+    /// let libs = tree.match_glob("usr/**/lib*")?;
+    /// ```
+    ///
+    /// Subtrees that cannot match the pattern anymore are not traversed.
+    ///
+    /// *Arguments:*
+    /// * `pattern` - The glob pattern.
+    ///
+    /// *Returns:* The matching node references in depth-first order.
+    ///            Returns error if no root node exist.
+    pub fn match_glob(&self, pattern: &str) -> Result<Vec<NodeRef>> {
+        let root_ref = match self.root {
+            None => return Err(not_found("Cannot search a tree without a root node.")),
+            Some(root_ref) => root_ref,
+        };
+
+        let segments: Vec<&str> = pattern.split('/').filter(|s| !s.is_empty()).collect();
+        let mut matches = Vec::new();
+        let mut stack = vec![(root_ref, closure(&segments, vec![0]))];
+
+        while let Some((node_ref, states)) = stack.pop() {
+            let start = stack.len();
+            for child_ref in self.live_children(node_ref) {
+                let value = self.get(child_ref).unwrap().as_ref();
+                let next_states = advance(&segments, &states, value);
+                if !next_states.is_empty() {
+                    stack.push((child_ref, next_states));
+                }
+            }
+            stack[start..].reverse();
+
+            if node_ref != root_ref && states.contains(&segments.len()) {
+                matches.push(node_ref);
+            }
+        }
+
+        Ok(matches)
+    }
+}
+
+/// Add the states reachable by letting `**` match no value.
+fn closure(segments: &[&str], mut states: Vec<usize>) -> Vec<usize> {
+    let mut i = 0;
+    while i < states.len() {
+        let state = states[i];
+        if segments.get(state) == Some(&"**") && !states.contains(&(state + 1)) {
+            states.push(state + 1);
+        }
+        i += 1;
+    }
+    states
+}
+
+/// Compute the states after matching one more value.
+fn advance(segments: &[&str], states: &[usize], value: &str) -> Vec<usize> {
+    let mut next = Vec::new();
+    for state in states {
+        let next_state = match segments.get(*state) {
+            None => continue,
+            Some(&"**") => *state,
+            Some(segment) if matches_segment(segment, value) => state + 1,
+            Some(_) => continue,
+        };
+        if !next.contains(&next_state) {
+            next.push(next_state);
+        }
+    }
+    closure(segments, next)
+}
+
+/// Match a single value against a segment with `*` and `?` wildcards.
+fn matches_segment(segment: &str, value: &str) -> bool {
+    let pattern: Vec<char> = segment.chars().collect();
+    let text: Vec<char> = value.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = backtrack {
+            p = star_p + 1;
+            t = star_t + 1;
+            backtrack = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,69 @@
+use super::*;
+use crate::{ErrorKind, TreeError};
+
+fn fs_tree() -> Tree<&'static str> {
+    let mut tree = Tree::new();
+    let root = tree.root("/").unwrap();
+    let usr = tree.child_node(root, "usr").unwrap();
+    tree.child_node(root, "etc").unwrap();
+    tree.child_node(usr, "lib").unwrap();
+    let local = tree.child_node(usr, "local").unwrap();
+    tree.child_node(local, "lib64").unwrap();
+    tree.child_node(local, "bin").unwrap();
+    tree
+}
+
+fn glob(tree: &Tree<&'static str>, pattern: &str) -> Vec<&'static str> {
+    tree.match_glob(pattern)
+        .unwrap()
+        .into_iter()
+        .map(|node_ref| *tree.get(node_ref).unwrap())
+        .collect()
+}
+
+#[test]
+fn match_glob() {
+    let tree = fs_tree();
+
+    assert_eq!(glob(&tree, "usr/**/lib*"), vec!["lib", "lib64"]);
+    assert_eq!(glob(&tree, "usr/*"), vec!["lib", "local"]);
+    assert_eq!(glob(&tree, "*/l?b"), vec!["lib"]);
+    assert_eq!(glob(&tree, "**/bin"), vec!["bin"]);
+    assert_eq!(glob(&tree, "etc"), vec!["etc"]);
+    assert_eq!(glob(&tree, "usr/**").len(), 5);
+    assert!(glob(&tree, "var/**").is_empty());
+}
+
+#[test]
+fn match_glob_removed_nodes() {
+    let mut tree = fs_tree();
+    let root = tree.get_root_ref().unwrap();
+    let etc = tree.get_children(root).unwrap().nth(1).unwrap();
+    tree.remove(etc).unwrap();
+
+    assert!(glob(&tree, "etc").is_empty());
+    assert_eq!(glob(&tree, "*"), vec!["usr"]);
+}
+
+#[test]
+fn match_glob_error() {
+    let tree: Tree<String> = Tree::new();
+
+    assert_eq!(
+        tree.match_glob("*"),
+        Err(TreeError::with_kind(
+            ErrorKind::NotFound,
+            "Cannot search a tree without a root node."
+        ))
+    );
+}
+
+#[test]
+fn segment_wildcards() {
+    assert!(matches_segment("lib*", "lib64"));
+    assert!(matches_segment("*.so", "libc.so"));
+    assert!(matches_segment("a*b*c", "aXbYbZc"));
+    assert!(!matches_segment("a*b", "aXbc"));
+    assert!(matches_segment("", ""));
+    assert!(!matches_segment("?", ""));
+}
@@ -0,0 +1,238 @@
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::hash::{BuildHasher, Hash};
+use std::mem;
+
+use super::{node_not_found, Node, NodeRef, Result, Tree};
+
+/// Identifier of a value stored in an [InternedTree].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct InternId(u32);
+
+/// Tree where identical values share storage.
+///
+/// Each distinct value is stored only once and nodes hold an [InternId]
+/// referring to it, which cuts memory usage for trees where values
+/// repeat heavily, such as token trees or markup tag names. Values are
+/// never freed while the tree is alive, even if no node uses them
+/// anymore.
+///
+/// The structure is an ordinary `Tree<InternId>`, available with
+/// [InternedTree::tree] and [InternedTree::tree_mut] for traversals and
+/// structural changes.
+///
+/// ```ignore
+/// let mut tree = InternedTree::new();
+/// let root = tree.root("ul")?;
+/// tree.child_node(root, "li")?;
+/// tree.child_node(root, "li")?;
+///
+/// assert_eq!(tree.distinct_values(), 2);
+/// ```
+#[derive(Debug)]
+pub struct InternedTree<T: Hash + Eq> {
+    pub(crate) tree: Tree<InternId>,
+    values: Vec<T>,
+    index: HashMap<u64, Vec<InternId>>,
+    hasher: RandomState,
+}
+
+impl<T: Hash + Eq> InternedTree<T> {
+    /// Create a new empty interned tree.
+    ///
+    /// *Returns:* The new tree.
+    pub fn new() -> Self {
+        Self {
+            tree: Tree::new(),
+            values: Vec::new(),
+            index: HashMap::new(),
+            hasher: RandomState::new(),
+        }
+    }
+
+    /// Get the shared storage id of a value, storing the value
+    /// if it was not seen before.
+    ///
+    /// *Arguments:*
+    /// * `content` - The value to intern.
+    ///
+    /// *Returns:* The id of the value.
+    ///
+    /// *Panics:* If more than `u32::MAX` distinct values are interned.
+    pub fn intern(&mut self, content: T) -> InternId {
+        let hash = self.hasher.hash_one(&content);
+        let values = &self.values;
+        let bucket = self.index.entry(hash).or_default();
+        if let Some(id) = bucket.iter().find(|id| values[id.0 as usize] == content) {
+            return *id;
+        }
+
+        let id = InternId(u32::try_from(values.len()).expect("Too many interned values."));
+        self.values.push(content);
+        bucket.push(id);
+        id
+    }
+
+    /// Get the id of a value without interning it.
+    ///
+    /// *Arguments:*
+    /// * `content` - The value to look up.
+    ///
+    /// *Returns:* The id of the value or `None` if it was never interned.
+    pub fn lookup<Q>(&self, content: &Q) -> Option<InternId>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.index
+            .get(&self.hasher.hash_one(content))?
+            .iter()
+            .find(|id| self.values[id.0 as usize].borrow() == content)
+            .copied()
+    }
+
+    /// Get the value corresponding to an id.
+    ///
+    /// *Arguments:*
+    /// * `id` - The [InternId] of the value.
+    ///
+    /// *Returns:* The value or `None` if the id does not belong to this tree.
+    pub fn resolve(&self, id: InternId) -> Option<&T> {
+        self.values.get(id.0 as usize)
+    }
+
+    /// Create a root node with the given content.
+    ///
+    /// *Arguments:*
+    /// * `content` - Content of the root node.
+    ///
+    /// *Returns:* A [NodeRef] object referencing the root node.
+    ///            Returns error if another root node already exists.
+    pub fn root(&mut self, content: T) -> Result<NodeRef> {
+        let id = self.intern(content);
+        self.tree.root(id)
+    }
+
+    /// Create a node without parent.
+    ///
+    /// *Arguments:*
+    /// * `content` - Content of the node.
+    ///
+    /// *Returns:* A [NodeRef] object referencing the node.
+    pub fn node(&mut self, content: T) -> NodeRef {
+        let id = self.intern(content);
+        self.tree.node(id)
+    }
+
+    /// Create a node and append it to a parent node.
+    ///
+    /// *Arguments:*
+    /// * `parent` - [NodeRef] of the parent node.
+    /// * `content` - Content of the node.
+    ///
+    /// *Returns:* A [NodeRef] object referencing the new node.
+    ///            Returns error if the parent does not exist.
+    pub fn child_node(&mut self, parent: NodeRef, content: T) -> Result<NodeRef> {
+        let id = self.intern(content);
+        self.tree.child_node(parent, id)
+    }
+
+    /// Get the content of a node.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    ///
+    /// *Returns:* The shared value or `None` if the node does not exist.
+    pub fn get(&self, node_ref: NodeRef) -> Option<&T> {
+        self.tree.get(node_ref).and_then(|id| self.resolve(*id))
+    }
+
+    /// Replace the content of a node.
+    ///
+    /// Only the node is changed: other nodes sharing the previous
+    /// value keep it.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    /// * `content` - The new content.
+    ///
+    /// *Returns:* Result indicating whether the operations was successful.
+    ///            Returns an error if the node does not exist.
+    pub fn set(&mut self, node_ref: NodeRef, content: T) -> Result<()> {
+        if self.tree.get(node_ref).is_none() {
+            return Err(node_not_found(node_ref));
+        }
+        let id = self.intern(content);
+        *self.tree.get_mut(node_ref).unwrap() = id;
+        Ok(())
+    }
+
+    /// Get the number of distinct values stored.
+    pub fn distinct_values(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Get the underlying tree of value ids.
+    pub fn tree(&self) -> &Tree<InternId> {
+        &self.tree
+    }
+
+    /// Get the underlying tree of value ids, to change its structure.
+    pub fn tree_mut(&mut self) -> &mut Tree<InternId> {
+        &mut self.tree
+    }
+}
+
+impl<T: Hash + Eq> Default for InternedTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Hash + Eq> Tree<T> {
+    /// Convert the tree into an [InternedTree], storing
+    /// repeated values only once.
+    ///
+    /// Node references stay valid in the underlying tree.
+    ///
+    /// *Returns:* The interned tree.
+    pub fn into_interned(mut self) -> InternedTree<T> {
+        let mut interned = InternedTree::new();
+        let nodes = mem::take(&mut self.nodes)
+            .into_iter()
+            .map(|node| {
+                node.map(|node| Node {
+                    content: interned.intern(node.content),
+                    parent: node.parent,
+                    children: node.children,
+                    siblings: node.siblings,
+                    locked: node.locked,
+                    children_unknown: node.children_unknown,
+                })
+            })
+            .collect();
+
+        interned.tree = Tree {
+            nodes,
+            root: self.root,
+            len: mem::take(&mut self.len),
+            unreachable: mem::take(&mut self.unreachable),
+            free: mem::take(&mut self.free),
+            storage: self.storage,
+            spare_children: Vec::new(),
+            marks: mem::take(&mut self.marks),
+            #[cfg(feature = "spans")]
+            spans: mem::take(&mut self.spans),
+            stable_ids: mem::take(&mut self.stable_ids),
+            registry: mem::take(&mut self.registry),
+        };
+        interned
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
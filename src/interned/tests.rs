@@ -0,0 +1,39 @@
+use super::*;
+
+#[test]
+fn shares_repeated_values() {
+    let mut tree = InternedTree::new();
+    let root = tree.root(String::from("ul")).unwrap();
+    let first = tree.child_node(root, String::from("li")).unwrap();
+    let second = tree.child_node(root, String::from("li")).unwrap();
+
+    assert_eq!(tree.distinct_values(), 2);
+    assert_eq!(tree.tree().get(first), tree.tree().get(second));
+    assert_eq!(tree.get(second).map(String::as_str), Some("li"));
+
+    tree.set(second, String::from("p")).unwrap();
+    assert_eq!(tree.get(first).map(String::as_str), Some("li"));
+    assert_eq!(tree.get(second).map(String::as_str), Some("p"));
+    assert_eq!(tree.distinct_values(), 3);
+}
+
+#[test]
+fn into_interned() {
+    let mut tree = Tree::new();
+    let root = tree.root("a").unwrap();
+    let child = tree.child_node(root, "b").unwrap();
+    tree.child_node(child, "a").unwrap();
+    tree.child_node(root, "b").unwrap();
+
+    let interned = tree.into_interned();
+    assert_eq!(interned.distinct_values(), 2);
+    assert_eq!(interned.tree().len(), 4);
+    assert_eq!(interned.get(child), Some(&"b"));
+    let values: Vec<&str> = interned
+        .tree()
+        .depth_first(true)
+        .unwrap()
+        .map(|node_ref| *interned.get(node_ref).unwrap())
+        .collect();
+    assert_eq!(values, vec!["a", "b", "a", "b"]);
+}
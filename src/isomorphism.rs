@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+
+use super::{NodeRef, Tree};
+
+impl<T> Tree<T> {
+    /// Check whether two trees have the same shape and equal values,
+    /// taking the order of the children into account.
+    ///
+    /// Node references, tombstones and slot numbering are ignored.
+    /// Two trees without root node are isomorphic.
+    ///
+    /// *Arguments:*
+    /// * `other` - The tree to compare with.
+    /// * `value_eq` - Function deciding whether two values are equal.
+    ///
+    /// *Returns:* Whether the trees are isomorphic.
+    pub fn is_isomorphic<U>(&self, other: &Tree<U>, value_eq: impl Fn(&T, &U) -> bool) -> bool {
+        let (root_ref, other_root_ref) = match (self.root, other.root) {
+            (Some(root_ref), Some(other_root_ref)) => (root_ref, other_root_ref),
+            (root, other_root) => return root.is_none() && other_root.is_none(),
+        };
+
+        let mut stack = vec![(root_ref, other_root_ref)];
+        while let Some((node_ref, other_ref)) = stack.pop() {
+            if !value_eq(self.get(node_ref).unwrap(), other.get(other_ref).unwrap()) {
+                return false;
+            }
+
+            let children: Vec<NodeRef> = self.live_children(node_ref).collect();
+            let other_children: Vec<NodeRef> = other.live_children(other_ref).collect();
+            if children.len() != other_children.len() {
+                return false;
+            }
+            stack.extend(children.into_iter().zip(other_children));
+        }
+        true
+    }
+
+    /// Check whether two trees have the same shape and equal values,
+    /// ignoring the order of the children.
+    ///
+    /// This computes a canonical class for every subtree of both trees,
+    /// bottom-up, where two subtrees get the same class if their roots
+    /// have equal values and their children have the same multiset of
+    /// classes. The trees are isomorphic if their roots share a class.
+    ///
+    /// ```ignore
+    /// // a => [b, c => [d]] and a => [c => [d], b] are isomorphic
+    /// assert!(tree.is_isomorphic_unordered(&other, |a, b| a == b));
+    /// ```
+    ///
+    /// *Arguments:*
+    /// * `other` - The tree to compare with.
+    /// * `value_eq` - Function deciding whether two values are equal.
+    ///
+    /// *Returns:* Whether the trees are isomorphic.
+    pub fn is_isomorphic_unordered(
+        &self,
+        other: &Tree<T>,
+        value_eq: impl Fn(&T, &T) -> bool,
+    ) -> bool {
+        let (root_ref, other_root_ref) = match (self.root, other.root) {
+            (Some(root_ref), Some(other_root_ref)) => (root_ref, other_root_ref),
+            (root, other_root) => return root.is_none() && other_root.is_none(),
+        };
+
+        let mut classes = Classes {
+            buckets: HashMap::new(),
+            count: 0,
+        };
+        let class_ids = self.canonical_classes(root_ref, &mut classes, &value_eq);
+        let other_class_ids = other.canonical_classes(other_root_ref, &mut classes, &value_eq);
+        class_ids[root_ref.index()] == other_class_ids[other_root_ref.index()]
+    }
+
+    /// Assign a class to every node of a subtree, registering
+    /// new classes in `classes`.
+    ///
+    /// *Returns:* The classes, indexed by node index.
+    fn canonical_classes<'a>(
+        &'a self,
+        root_ref: NodeRef,
+        classes: &mut Classes<'a, T>,
+        value_eq: &impl Fn(&T, &T) -> bool,
+    ) -> Vec<usize> {
+        let mut class_ids = vec![0; self.nodes.len()];
+        let order: Vec<NodeRef> = self.depth_first_of(root_ref, true).unwrap().collect();
+
+        // Descendants come before their ancestors in reverse preorder
+        for node_ref in order.into_iter().rev() {
+            let mut children: Vec<usize> = self
+                .live_children(node_ref)
+                .map(|child_ref| class_ids[child_ref.index()])
+                .collect();
+            children.sort_unstable();
+
+            let value = self.get(node_ref).unwrap();
+            let bucket = classes.buckets.entry(children).or_default();
+            class_ids[node_ref.index()] = match bucket
+                .iter()
+                .find(|(representative, _)| value_eq(representative, value))
+            {
+                Some((_, class_id)) => *class_id,
+                None => {
+                    classes.count += 1;
+                    bucket.push((value, classes.count));
+                    classes.count
+                }
+            };
+        }
+        class_ids
+    }
+}
+
+/// Subtree classes shared by the trees being compared, grouped by the
+/// sorted classes of the children, with a representative value each.
+struct Classes<'a, T> {
+    buckets: HashMap<Vec<usize>, Vec<(&'a T, usize)>>,
+    count: usize,
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,31 @@
+use super::*;
+use crate::tree;
+
+#[test]
+fn is_isomorphic() {
+    let tree = tree!('a' => ['b', 'c' => ['d']]);
+    let other = tree!(1 => [2, 3 => [4]]);
+    let to_char = |a: &char, b: &i32| (b'a' + *b as u8 - 1) as char == *a;
+    assert!(tree.is_isomorphic(&other, to_char));
+
+    let reordered = tree!(1 => [3 => [4], 2]);
+    assert!(!tree.is_isomorphic(&reordered, to_char));
+    assert!(!tree.is_isomorphic(&other, |_, _| false));
+    assert!(Tree::<char>::new().is_isomorphic(&Tree::<i32>::new(), to_char));
+    assert!(!tree.is_isomorphic(&Tree::<i32>::new(), to_char));
+}
+
+#[test]
+fn is_isomorphic_unordered() {
+    let tree = tree!('a' => ['b' => ['x', 'y'], 'c' => ['d'], 'b' => ['y']]);
+    let other = tree!('a' => ['b' => ['y'], 'c' => ['d'], 'b' => ['y', 'x']]);
+    assert!(tree.is_isomorphic_unordered(&other, |a, b| a == b));
+    assert!(!tree.is_isomorphic(&other, |a, b| a == b));
+
+    let moved = tree!('a' => ['b' => ['y', 'x'], 'c' => ['d', 'y'], 'b']);
+    assert!(!tree.is_isomorphic_unordered(&moved, |a, b| a == b));
+
+    let case = tree!('A' => ['B' => ['Y'], 'C' => ['D'], 'B' => ['X', 'Y']]);
+    assert!(!tree.is_isomorphic_unordered(&case, |a, b| a == b));
+    assert!(tree.is_isomorphic_unordered(&case, |a, b| a.eq_ignore_ascii_case(b)));
+}
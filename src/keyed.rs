@@ -0,0 +1,265 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::{
+    node_not_found, not_found, ErrorKind, NodeRef, RemovalReport, Result, Tree, TreeError,
+};
+
+/// Tree indexing the children of each node by a key.
+///
+/// The key of a node is extracted from its content by a user provided
+/// function, like the name of an entry of a configuration tree. Each
+/// parent keeps a hash map from the keys to its children, so that
+/// [KeyedTree::child_by_key] does not need to scan the children.
+/// The keys of the children of a node must be unique.
+///
+/// The maps are updated by the methods of this type, so the underlying
+/// tree can only be read with [KeyedTree::tree].
+///
+/// ```ignore
+/// // Look up "server.port"
+/// let mut tree = KeyedTree::new(|entry: &Entry| entry.name.clone());
+/// let root = tree.root(Entry::new("config"))?;
+/// let server = tree.child_node(root, Entry::new("server"))?;
+/// tree.child_node(server, Entry::new("port"))?;
+///
+/// let port = tree
+///     .child_by_key(root, "server")
+///     .and_then(|server| tree.child_by_key(server, "port"));
+/// ```
+pub struct KeyedTree<T, K, F> {
+    tree: Tree<T>,
+    children: Vec<Option<HashMap<K, NodeRef>>>,
+    key_fn: F,
+}
+
+impl<T, K: Hash + Eq, F: Fn(&T) -> K> KeyedTree<T, K, F> {
+    /// Create a new empty tree.
+    ///
+    /// *Arguments:*
+    /// * `key_fn` - Function extracting the key of a node from its content.
+    ///
+    /// *Returns:* The new tree.
+    pub fn new(key_fn: F) -> Self {
+        Self {
+            tree: Tree::new(),
+            children: Vec::new(),
+            key_fn,
+        }
+    }
+
+    /// Index the children of an existing tree.
+    ///
+    /// *Arguments:*
+    /// * `tree` - The tree to index.
+    /// * `key_fn` - Function extracting the key of a node from its content.
+    ///
+    /// *Returns:* The keyed tree. Returns an error if two children
+    ///            of a node have the same key.
+    pub fn from_tree(tree: Tree<T>, key_fn: F) -> Result<Self> {
+        let mut keyed = Self {
+            children: Vec::with_capacity(tree.nodes.len()),
+            tree,
+            key_fn,
+        };
+
+        let nodes: Vec<NodeRef> = keyed.tree.iter_all_nodes().collect();
+        for node_ref in nodes {
+            if let Some(parent_ref) = keyed.live_parent(node_ref) {
+                keyed.check_free(parent_ref, node_ref)?;
+                keyed.insert_key(parent_ref, node_ref);
+            }
+        }
+        Ok(keyed)
+    }
+
+    /// Get the child of a node with the given key.
+    ///
+    /// *Arguments:*
+    /// * `parent_ref` - [NodeRef] of the parent node.
+    /// * `key` - The key of the child.
+    ///
+    /// *Returns:* The [NodeRef] of the child or `None` if the parent
+    ///            does not exist or has no child with this key.
+    pub fn child_by_key<Q>(&self, parent_ref: NodeRef, key: &Q) -> Option<NodeRef>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.tree.get(parent_ref)?;
+        self.children
+            .get(parent_ref.index())?
+            .as_ref()?
+            .get(key)
+            .copied()
+    }
+
+    /// Get the content of a node.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    ///
+    /// *Returns:* The content or `None` if the node does not exist.
+    pub fn get(&self, node_ref: NodeRef) -> Option<&T> {
+        self.tree.get(node_ref)
+    }
+
+    /// Replace the content of a node and update its key.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    /// * `content` - The new content.
+    ///
+    /// *Returns:* Result indicating whether the operations was successful.
+    ///            Returns an error if the node does not exist or if a
+    ///            sibling already has the new key.
+    pub fn set(&mut self, node_ref: NodeRef, content: T) -> Result<()> {
+        if self.tree.get(node_ref).is_none() {
+            return Err(node_not_found(node_ref));
+        }
+
+        let parent = self.live_parent(node_ref);
+        if let Some(parent_ref) = parent {
+            if let Some(sibling_ref) = self.child_by_key(parent_ref, &(self.key_fn)(&content)) {
+                if sibling_ref != node_ref {
+                    return Err(duplicate_key(parent_ref, sibling_ref));
+                }
+            }
+            self.remove_key(parent_ref, node_ref);
+        }
+
+        *self.tree.get_mut(node_ref).unwrap() = content;
+        if let Some(parent_ref) = parent {
+            self.insert_key(parent_ref, node_ref);
+        }
+        Ok(())
+    }
+
+    /// Create a root node, see [Tree::root].
+    pub fn root(&mut self, content: T) -> Result<NodeRef> {
+        self.tree.root(content)
+    }
+
+    /// Create a node without parent, see [Tree::node].
+    pub fn node(&mut self, content: T) -> NodeRef {
+        self.tree.node(content)
+    }
+
+    /// Create a node and append it to a parent node, see [Tree::child_node].
+    ///
+    /// Returns an error if the parent already has a child with the
+    /// same key, in which case no node is created.
+    pub fn child_node(&mut self, parent_ref: NodeRef, content: T) -> Result<NodeRef> {
+        if self.tree.get(parent_ref).is_none() {
+            return Err(not_found("Parent node does not exist."));
+        }
+        if let Some(sibling_ref) = self.child_by_key(parent_ref, &(self.key_fn)(&content)) {
+            return Err(duplicate_key(parent_ref, sibling_ref));
+        }
+
+        let child_ref = self.tree.child_node(parent_ref, content)?;
+        self.insert_key(parent_ref, child_ref);
+        Ok(child_ref)
+    }
+
+    /// Add child node to a node, see [Tree::append_child].
+    ///
+    /// Returns an error if the parent already has another child
+    /// with the same key.
+    pub fn append_child(&mut self, parent_ref: NodeRef, child_ref: NodeRef) -> Result<()> {
+        if self.tree.get(parent_ref).is_some() && self.tree.get(child_ref).is_some() {
+            self.check_free(parent_ref, child_ref)?;
+        }
+
+        let old_parent = self.live_parent(child_ref);
+        self.tree.append_child(parent_ref, child_ref)?;
+        if let Some(old_parent_ref) = old_parent {
+            self.remove_key(old_parent_ref, child_ref);
+        }
+        self.insert_key(parent_ref, child_ref);
+        Ok(())
+    }
+
+    /// Remove a node from the tree, see [Tree::remove].
+    ///
+    /// The children of the removed node are left without parent.
+    pub fn remove(&mut self, node_ref: NodeRef) -> Result<RemovalReport> {
+        let parent = self.live_parent(node_ref);
+        if let Some(parent_ref) = parent {
+            self.remove_key(parent_ref, node_ref);
+        }
+        let report = match self.tree.remove(node_ref) {
+            Ok(report) => report,
+            Err(error) => {
+                if let Some(parent_ref) = parent {
+                    self.insert_key(parent_ref, node_ref);
+                }
+                return Err(error);
+            }
+        };
+        if let Some(slot) = self.children.get_mut(node_ref.index()) {
+            *slot = None;
+        }
+        Ok(report)
+    }
+
+    /// Get the underlying tree.
+    pub fn tree(&self) -> &Tree<T> {
+        &self.tree
+    }
+
+    /// Drop the key maps and get the underlying tree back.
+    pub fn into_tree(self) -> Tree<T> {
+        self.tree
+    }
+
+    fn live_parent(&self, node_ref: NodeRef) -> Option<NodeRef> {
+        self.tree
+            .get_node(node_ref)?
+            .parent
+            .filter(|parent_ref| self.tree.get_node(*parent_ref).is_some())
+    }
+
+    /// Check that no child of a node, other than `child_ref`
+    /// itself, has the key of `child_ref`.
+    fn check_free(&self, parent_ref: NodeRef, child_ref: NodeRef) -> Result<()> {
+        let key = (self.key_fn)(self.tree.get(child_ref).unwrap());
+        match self.child_by_key(parent_ref, &key) {
+            Some(sibling_ref) if sibling_ref != child_ref => {
+                Err(duplicate_key(parent_ref, sibling_ref))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn insert_key(&mut self, parent_ref: NodeRef, child_ref: NodeRef) {
+        let key = (self.key_fn)(self.tree.get(child_ref).unwrap());
+        if self.children.len() <= parent_ref.index() {
+            self.children.resize_with(self.tree.nodes.len(), || None);
+        }
+        self.children[parent_ref.index()]
+            .get_or_insert_with(HashMap::new)
+            .insert(key, child_ref);
+    }
+
+    fn remove_key(&mut self, parent_ref: NodeRef, child_ref: NodeRef) {
+        let key = (self.key_fn)(self.tree.get(child_ref).unwrap());
+        if let Some(Some(keys)) = self.children.get_mut(parent_ref.index()) {
+            keys.remove(&key);
+        }
+    }
+}
+
+fn duplicate_key(parent_ref: NodeRef, sibling_ref: NodeRef) -> TreeError {
+    TreeError::with_kind(
+        ErrorKind::InvalidInput,
+        "A child with the same key already exists.",
+    )
+    .involving(&[parent_ref, sibling_ref])
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,65 @@
+use super::*;
+
+fn name(content: &(&'static str, i32)) -> &'static str {
+    content.0
+}
+
+#[test]
+fn child_by_key() {
+    let mut tree = KeyedTree::new(name);
+    let root = tree.root(("config", 0)).unwrap();
+    let server = tree.child_node(root, ("server", 0)).unwrap();
+    let port = tree.child_node(server, ("port", 80)).unwrap();
+    let client = tree.child_node(root, ("client", 0)).unwrap();
+
+    let found = tree
+        .child_by_key(root, "server")
+        .and_then(|server| tree.child_by_key(server, "port"));
+    assert_eq!(found, Some(port));
+    assert_eq!(tree.child_by_key(root, "port"), None);
+
+    let error = tree.child_node(root, ("server", 1)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    assert_eq!(error.nodes(), &[root, server]);
+    assert_eq!(tree.tree().len(), 4);
+
+    tree.set(client, ("proxy", 0)).unwrap();
+    assert_eq!(tree.child_by_key(root, "client"), None);
+    assert_eq!(tree.child_by_key(root, "proxy"), Some(client));
+    assert!(tree.set(client, ("server", 0)).is_err());
+    tree.set(client, ("proxy", 1)).unwrap();
+
+    tree.append_child(client, port).unwrap();
+    assert_eq!(tree.child_by_key(server, "port"), None);
+    assert_eq!(tree.child_by_key(client, "port"), Some(port));
+
+    tree.remove(client).unwrap();
+    assert_eq!(tree.child_by_key(root, "proxy"), None);
+    assert_eq!(tree.child_by_key(client, "port"), None);
+}
+
+#[test]
+fn from_tree() {
+    let mut tree = Tree::new();
+    let root = tree.root(("a", 0)).unwrap();
+    let b = tree.child_node(root, ("b", 0)).unwrap();
+    let keyed = KeyedTree::from_tree(tree.clone(), name).unwrap();
+    assert_eq!(keyed.child_by_key(root, "b"), Some(b));
+
+    tree.child_node(root, ("b", 1)).unwrap();
+    assert!(KeyedTree::from_tree(tree, name).is_err());
+}
+
+#[test]
+fn remove_leaves() {
+    let mut tree = KeyedTree::new(name);
+    let root = tree.root(("a", 0)).unwrap();
+    tree.remove(root).unwrap();
+    assert_eq!(tree.get(root), None);
+
+    let root = tree.node(("b", 0));
+    let child = tree.child_node(root, ("c", 0)).unwrap();
+    let leaf = tree.child_node(child, ("d", 0)).unwrap();
+    tree.remove(leaf).unwrap();
+    assert_eq!(tree.child_by_key(child, "d"), None);
+}
@@ -0,0 +1,108 @@
+use std::mem;
+
+use super::{NodeRef, Operation, Tree};
+
+/// Mapping from old to new node references, returned by operations
+/// that move nodes inside the underlying vector.
+#[derive(Debug, Clone)]
+pub struct NodeRemap {
+    pub(crate) map: Vec<Option<NodeRef>>,
+}
+
+impl NodeRemap {
+    /// Get the new reference of a node.
+    ///
+    /// *Arguments:*
+    /// * `old_ref` - [NodeRef] of the node before the operation.
+    ///
+    /// *Returns:* The new [NodeRef] of the node or `None` if the
+    ///            reference did not point to a live node.
+    pub fn get(&self, old_ref: NodeRef) -> Option<NodeRef> {
+        self.map.get(old_ref.index()).copied().flatten()
+    }
+}
+
+impl<T> Tree<T> {
+    /// Reorder the underlying vector so that nodes are stored in
+    /// depth-first order.
+    ///
+    /// After this, each subtree occupies a contiguous range of the vector,
+    /// making traversals close to linear scans of memory. Nodes not reachable
+    /// from the root are kept and stored after the ones reachable from
+    /// the root. Tombstones are dropped and the vector is shrunk.
+    ///
+    /// All node references are invalidated: use the returned [NodeRemap]
+    /// to translate them.
+    ///
+    /// *Returns:* The mapping from old to new node references.
+    pub fn optimize_layout(&mut self) -> NodeRemap {
+        let operation = Operation::start("optimize_layout", self.len);
+        let mut order = Vec::with_capacity(self.len);
+        let mut map = vec![None; self.nodes.len()];
+
+        let roots = self
+            .root
+            .into_iter()
+            .chain((0..self.nodes.len()).map(NodeRef::new).filter(|node_ref| {
+                match self.get_node(*node_ref) {
+                    None => false,
+                    Some(node) => node.parent.and_then(|p| self.get_node(p)).is_none(),
+                }
+            }));
+        for subtree_root in roots {
+            if self.get_node(subtree_root).is_none() || map[subtree_root.index()].is_some() {
+                continue;
+            }
+            for node_ref in self.depth_first_of(subtree_root, true).unwrap() {
+                map[node_ref.index()] = Some(NodeRef::new(order.len()));
+                order.push(node_ref.index());
+            }
+        }
+
+        let mut old_nodes = mem::take(&mut self.nodes);
+        self.nodes = order
+            .into_iter()
+            .map(|id| {
+                let mut node = old_nodes[id].take().unwrap();
+                node.parent = node.parent.and_then(|p| map[p.index()]);
+                node.children.remap(|c| map[c.index()]);
+                for sibling in node.siblings.iter_mut() {
+                    *sibling = sibling.and_then(|s| map[s.index()]);
+                }
+                Some(node)
+            })
+            .collect();
+        self.root = self.root.and_then(|root_ref| map[root_ref.index()]);
+        let old_marks = mem::take(&mut self.marks);
+        if !old_marks.is_empty() {
+            self.marks = vec![0; self.nodes.len()];
+            for (old_id, new_ref) in map.iter().enumerate() {
+                if let (Some(new_ref), Some(marks)) = (new_ref, old_marks.get(old_id)) {
+                    self.marks[new_ref.index()] = *marks;
+                }
+            }
+        }
+        #[cfg(feature = "spans")]
+        {
+            let mut old_spans = mem::take(&mut self.spans);
+            if !old_spans.is_empty() {
+                self.spans = vec![None; self.nodes.len()];
+                for (old_id, new_ref) in map.iter().enumerate() {
+                    if let (Some(new_ref), Some(span)) = (new_ref, old_spans.get_mut(old_id)) {
+                        self.spans[new_ref.index()] = span.take();
+                    }
+                }
+            }
+        }
+        self.stable_ids.remap(&map);
+        self.free.clear();
+
+        operation.finish(self.len);
+        NodeRemap { map }
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,32 @@
+use super::*;
+
+#[test]
+fn optimize_layout() {
+    let mut tree = Tree::new();
+    let a = tree.root("a").unwrap();
+    let removed = tree.child_node(a, "removed").unwrap();
+    let b = tree.child_node(a, "b").unwrap();
+    let c = tree.child_node(a, "c").unwrap();
+    let d = tree.child_node(b, "d").unwrap();
+    let detached = tree.node("detached");
+    tree.remove(removed).unwrap();
+
+    let remap = tree.optimize_layout();
+
+    assert_eq!(tree.tombstones(), 0);
+    assert_eq!(remap.get(removed), None);
+    let refs: Vec<NodeRef> = [a, b, d, c, detached]
+        .iter()
+        .map(|node_ref| remap.get(*node_ref).unwrap())
+        .collect();
+    assert_eq!(
+        refs.iter().map(|r| r.index()).collect::<Vec<_>>(),
+        vec![0, 1, 2, 3, 4]
+    );
+    assert_eq!(
+        tree.depth_first(true).unwrap().collect::<Vec<_>>(),
+        refs[..4].to_vec()
+    );
+    assert_eq!(tree.get(refs[4]), Some(&"detached"));
+    assert_eq!(tree.get_parent(refs[2]), Ok(Some(refs[1])));
+}
@@ -0,0 +1,89 @@
+use super::{invalid_input, node_not_found, NodeRef, Result, Tree, TreeError};
+
+impl<T> Tree<T> {
+    /// Mark the children of a node as not loaded yet.
+    ///
+    /// This lets the tree represent remote or virtual hierarchies, like
+    /// the folders of a storage bucket, where the children of a node are
+    /// fetched when it is expanded. Such nodes are reported by
+    /// [Tree::unknown_children_of] and loaded with [Tree::populate_children].
+    /// Children added in other ways do not clear the mark.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    ///
+    /// *Returns:* Result indicating whether the operations was successful.
+    ///            Returns an error if the node does not exist.
+    pub fn mark_children_unknown(&mut self, node_ref: NodeRef) -> Result<()> {
+        match self.get_node_mut(node_ref) {
+            None => Err(node_not_found(node_ref)),
+            Some(node) => {
+                node.children_unknown = true;
+                Ok(())
+            }
+        }
+    }
+
+    /// Check whether the children of a node are not loaded yet.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    ///
+    /// *Returns:* Whether the node was marked with [Tree::mark_children_unknown]
+    ///            and not populated since. Returns `false` if the node
+    ///            does not exist.
+    pub fn has_unknown_children(&self, node_ref: NodeRef) -> bool {
+        self.get_node(node_ref)
+            .is_some_and(|node| node.children_unknown)
+    }
+
+    /// Find the nodes of a subtree whose children are not loaded yet.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the root of the subtree, included
+    ///   in the search.
+    ///
+    /// *Returns:* The nodes with unknown children, in depth-first order.
+    ///            Returns error if the node does not exist.
+    pub fn unknown_children_of(&self, node_ref: NodeRef) -> Result<Vec<NodeRef>> {
+        Ok(self
+            .depth_first_of(node_ref, true)?
+            .filter(|node_ref| self.has_unknown_children(*node_ref))
+            .collect())
+    }
+
+    /// Load the children of a node marked with [Tree::mark_children_unknown].
+    ///
+    /// The new nodes are added after the existing children, if any, and
+    /// the mark is cleared. The new nodes can be marked in turn if their
+    /// own children are not loaded yet.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    /// * `contents` - The items to be set as content of the children.
+    ///
+    /// *Returns:* The [NodeRef] objects referencing the created nodes.
+    ///            Returns an error if the node does not exist, is locked
+    ///            or if its children are not marked as unknown.
+    pub fn populate_children(
+        &mut self,
+        node_ref: NodeRef,
+        contents: impl IntoIterator<Item = T>,
+    ) -> Result<Vec<NodeRef>> {
+        let context = |error: TreeError| error.during("populate_children").involving(&[node_ref]);
+        if self.get_node(node_ref).is_some() && !self.has_unknown_children(node_ref) {
+            return Err(context(invalid_input(
+                "Children of the node are already known.",
+            )));
+        }
+
+        let children = self.child_nodes(node_ref, contents).map_err(context)?;
+        self.get_node_mut(node_ref).unwrap().children_unknown = false;
+        Ok(children)
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
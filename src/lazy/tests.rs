@@ -0,0 +1,42 @@
+use super::*;
+
+#[test]
+fn populate_children() {
+    let mut tree = Tree::new();
+    let root = tree.root("bucket").unwrap();
+    let folders = tree.child_nodes(root, vec!["a", "b"]).unwrap();
+    tree.mark_children_unknown(folders[0]).unwrap();
+    tree.mark_children_unknown(folders[1]).unwrap();
+
+    assert!(tree.has_unknown_children(folders[0]));
+    assert!(!tree.has_unknown_children(root));
+    assert_eq!(tree.unknown_children_of(root).unwrap(), folders);
+
+    let files = tree.populate_children(folders[0], vec!["x", "y"]).unwrap();
+    assert_eq!(files.len(), 2);
+    assert_eq!(tree.get_parent(files[0]).unwrap(), Some(folders[0]));
+    assert_eq!(tree.unknown_children_of(root).unwrap(), vec![folders[1]]);
+
+    let error = tree.populate_children(folders[0], vec!["z"]).unwrap_err();
+    assert_eq!(error.operation(), Some("populate_children"));
+    assert_eq!(tree.len(), 5);
+
+    tree.lock_subtree(folders[1]).unwrap();
+    assert!(tree.populate_children(folders[1], vec!["z"]).is_err());
+    assert!(tree.has_unknown_children(folders[1]));
+}
+
+#[test]
+fn unknown_children_are_kept() {
+    let mut tree = Tree::new();
+    let root = tree.root(0).unwrap();
+    tree.mark_children_unknown(root).unwrap();
+
+    let copy = tree.map(|value| value + 1).unwrap();
+    assert!(copy.has_unknown_children(root));
+
+    let mut other = Tree::new();
+    let other_root = other.root(0).unwrap();
+    let grafted = other.append_tree(other_root, tree).unwrap();
+    assert!(other.has_unknown_children(grafted));
+}
@@ -41,6 +41,12 @@
 //! let etc = tree.child_node(fs_root, "etc")?;
 //! ```
 //!
+//! The [tree!] macro builds the same tree from a literal:
+//!
+//! ```ignore
+//! let tree = lineartree::tree! {"/" => ["etc", "usr" => ["bin", "lib"]]};
+//! ```
+//!
 //! ### Getting, changing and removing nodes
 //!
 //! ```ignore
@@ -77,45 +83,408 @@
 //!     // ...
 //! }
 //! ```
+//!
+//! Iteration order is deterministic: children are always visited in the
+//! order they were added, so traversing the same tree twice yields the
+//! same sequence of nodes.
+//!
+//! ### Mapping
+//!
+//! ```ignore
+//! // Create a tree with the same shape and different content.
+//! // NodeRef objects of the original tree are valid in the new one.
+//! let lengths = tree.map(|name| name.len())?;
+//! assert_eq!(lengths.get(usr), Some(&3));
+//! ```
+//!
+//! ### Child storage
+//!
+//! ```ignore
+//! use lineartree::ChildStorage;
+//!
+//! // Store children as linked lists instead of one vector per node,
+//! // which saves an allocation per node when nodes have few children.
+//! let mut tree = Tree::with_child_storage(ChildStorage::LinkedList);
+//! ```
+//!
+//! ### Comparing trees
+//!
+//! ```ignore
+//! // Compare structure and content, ignoring node references.
+//! assert!(tree.structural_eq(&expected));
+//!
+//! // In tests, panic with a colored diff of the differing nodes.
+//! lineartree::assert_tree_eq!(tree, expected);
+//! ```
+//!
+//! ### Binary trees
+//!
+//! ```ignore
+//! use lineartree::BinaryTree;
+//!
+//! let mut tree = BinaryTree::new();
+//! let plus = tree.root("+")?;
+//! let one = tree.node("1");
+//! let two = tree.node("2");
+//! tree.set_left(plus, one)?;
+//! tree.set_right(plus, two)?;
+//!
+//! // Pre-order, in-order and post-order iteration are available
+//! for node in tree.in_order()? {
+//!     // ...
+//! }
+//! ```
 
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::error::Error;
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::{Enumerate, FusedIterator, Rev, Skip, Take};
+use std::mem;
+use std::num::NonZeroUsize;
+#[cfg(feature = "spans")]
+use std::ops::Range;
 use std::slice::Iter;
 
+extern crate self as lineartree;
+
+mod augment;
+mod binary;
+mod builder;
+mod context;
+mod describe;
+mod diff;
+mod dyntree;
+mod evict;
+mod frozen;
+#[cfg(feature = "fs")]
+pub mod fs;
+mod glob;
+mod interned;
+mod isomorphism;
+mod keyed;
+mod layout;
+mod lazy;
+mod macros;
+#[cfg(feature = "markdown")]
+pub mod markdown;
+mod marks;
+#[cfg(feature = "mcts")]
+pub mod mcts;
+mod nested;
+mod newick;
+mod outline;
+mod parts;
+mod pattern;
+mod preorder;
+mod preview;
+mod profile;
+mod query;
+mod render;
+mod reshape;
+mod scratch;
+mod select;
+mod selection;
+mod snapshot;
+#[cfg(feature = "spans")]
+mod spans;
+mod stable;
+mod storage;
+mod strtree;
+mod template;
+pub mod testing;
+mod trace;
+mod traversal;
+mod validate;
+#[cfg(any(feature = "json", feature = "toml", feature = "yaml"))]
+mod values;
+mod versioned;
+mod weighted;
+#[cfg(feature = "egui")]
+pub mod widget;
+mod wire;
+#[cfg(feature = "xml")]
+pub mod xml;
+mod zip;
+
+pub use augment::AugmentedTree;
+pub use binary::{BinaryTree, InOrderIterator, PostOrderIterator, PreOrderIterator};
+pub use builder::StreamingBuilder;
+pub use context::{NodeContext, NodeIteratorExt, WithContext};
+pub use dyntree::DynTree;
+pub use evict::EvictingTree;
+#[cfg(feature = "rkyv")]
+pub use frozen::ArchivedFrozenTree;
+pub use frozen::FrozenTree;
+pub use interned::{InternId, InternedTree};
+pub use keyed::KeyedTree;
+pub use layout::NodeRemap;
+#[cfg(feature = "derive")]
+pub use lineartree_derive::IntoTree;
+pub use marks::MARK_BITS;
+pub use nested::IntoTree;
+pub use outline::Indent;
+pub use pattern::Captures;
+pub use preorder::PreorderSlicesIterator;
+pub use preview::PreviewStrategy;
+pub use profile::{ProfileEntry, ProfileReport, Profiled};
+pub use query::Predicates;
+pub use render::RenderStyle;
+pub use scratch::{ScratchBreadthFirst, ScratchDepthFirst, TraversalScratch};
+pub use selection::Selection;
+pub use snapshot::TreeSnapshot;
+use stable::StableIds;
+use storage::ChildList;
+use storage::ChildRefs;
+pub use storage::{ChildStorage, Children};
+pub use strtree::StrTree;
+pub use template::Template;
+use trace::Operation;
+pub use traversal::{StackEntry, TraversalStack};
+use validate::{Registry, Stamp};
+pub use versioned::VersionedTree;
+pub use wire::{Migrations, FORMAT_VERSION};
+pub use zip::WalkPair;
+
 // Error
 // ==================================================================
-#[derive(Debug, Eq, PartialEq)]
+/// Category of a [TreeError].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// A structural mutation was attempted inside a locked subtree.
+    Locked,
+    /// Externally supplied data does not describe a valid tree,
+    /// or an argument is invalid.
+    InvalidInput,
+    /// A node, or the root or parent node asked for, does not exist.
+    NotFound,
+    /// Any other error.
+    Other,
+}
+
+/// Error returned by fallible tree operations.
+///
+/// Besides a message and an [ErrorKind], the error records the
+/// operation that failed and the node references that caused the
+/// failure, when known.
+///
+/// Two errors are equal if they have the same kind and message,
+/// regardless of the context.
+#[derive(Debug)]
 pub struct TreeError {
+    kind: ErrorKind,
     message: String,
+    operation: Option<&'static str>,
+    nodes: Vec<NodeRef>,
+    source: Option<Box<dyn Error + Send + Sync + 'static>>,
 }
 
 impl TreeError {
-    fn new(message: &str) -> Self {
+    /// Create an error of kind [ErrorKind::Other].
+    ///
+    /// Useful in code built on top of the tree, for example when
+    /// implementing [IntoTree::from_tree] by hand.
+    ///
+    /// *Arguments:*
+    /// * `message` - Description of the error.
+    ///
+    /// *Returns:* The new error.
+    pub fn new(message: &str) -> Self {
+        Self::with_kind(ErrorKind::Other, message)
+    }
+
+    fn with_kind(kind: ErrorKind, message: &str) -> Self {
         Self {
+            kind,
             message: message.to_string(),
+            operation: None,
+            nodes: Vec::new(),
+            source: None,
+        }
+    }
+
+    /// Attach the underlying cause of the error, returned
+    /// by [Error::source].
+    ///
+    /// *Arguments:*
+    /// * `source` - The error that caused this one.
+    ///
+    /// *Returns:* The error with the source attached.
+    pub fn with_source(
+        mut self,
+        source: impl Into<Box<dyn Error + Send + Sync + 'static>>,
+    ) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Record the name of the failed operation, unless already set.
+    fn during(mut self, operation: &'static str) -> Self {
+        self.operation.get_or_insert(operation);
+        self
+    }
+
+    /// Record the node references that caused the error, unless already set.
+    fn involving(mut self, nodes: &[NodeRef]) -> Self {
+        if self.nodes.is_empty() {
+            self.nodes.extend_from_slice(nodes);
         }
+        self
+    }
+
+    /// Get the category of the error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Get the name of the operation that failed, such as `"append_child"`.
+    pub fn operation(&self) -> Option<&'static str> {
+        self.operation
+    }
+
+    /// Get the node references that caused the error.
+    ///
+    /// *Returns:* The offending references, empty if unknown.
+    pub fn nodes(&self) -> &[NodeRef] {
+        &self.nodes
+    }
+}
+
+impl PartialEq for TreeError {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind && self.message == other.message
     }
 }
 
+impl Eq for TreeError {}
+
 impl fmt::Display for TreeError {
+    /// Write the message. The alternate form `{:#}` also writes
+    /// the operation and the offending node references.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(&self.to_string())
+        f.write_str(&self.message)?;
+        if f.alternate() {
+            if let Some(operation) = self.operation {
+                write!(f, " (in {})", operation)?;
+            }
+            if !self.nodes.is_empty() {
+                write!(f, " [nodes: {:?}]", self.nodes)?;
+            }
+        }
+        Ok(())
     }
 }
 
 impl Error for TreeError {
-    fn cause(&self) -> Option<&dyn Error> {
-        None
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|source| source as &(dyn Error + 'static))
+    }
+}
+
+#[cfg(feature = "io")]
+impl From<TreeError> for std::io::Error {
+    fn from(error: TreeError) -> Self {
+        let kind = match error.kind {
+            ErrorKind::Locked => std::io::ErrorKind::PermissionDenied,
+            ErrorKind::InvalidInput => std::io::ErrorKind::InvalidData,
+            ErrorKind::NotFound => std::io::ErrorKind::NotFound,
+            _ => std::io::ErrorKind::Other,
+        };
+        std::io::Error::new(kind, error)
     }
 }
 
 type Result<T> = std::result::Result<T, TreeError>;
 
+fn node_not_found(node_ref: NodeRef) -> TreeError {
+    not_found("Node does not exist.").involving(&[node_ref])
+}
+
+/// Error of kind [ErrorKind::NotFound], for a missing node, parent or root.
+fn not_found(message: &str) -> TreeError {
+    TreeError::with_kind(ErrorKind::NotFound, message)
+}
+
+/// Error of kind [ErrorKind::InvalidInput], for an invalid argument.
+fn invalid_input(message: &str) -> TreeError {
+    TreeError::with_kind(ErrorKind::InvalidInput, message)
+}
+
 // NodeRef
 // ==================================================================
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Reference to a node in a [Tree].
+///
+/// The ordering of node references is the order in which the nodes
+/// were allocated, which is not meaningful in general. Use
+/// [Tree::cmp_document_order] to order nodes by their position.
+///
+/// The index of the node is stored offset by one in a [NonZeroUsize],
+/// so that `Option<NodeRef>` has the same size as `NodeRef`.
+///
+/// With the `debug-validate` feature, in debug builds, references are
+/// also stamped with the tree they belong to. Using them with another
+/// tree, or after their node was removed and the slot reused, panics.
+/// Stamps are ignored by comparisons and hashing.
+#[derive(Copy, Clone)]
 pub struct NodeRef {
-    id: usize,
+    id: NonZeroUsize,
+    #[cfg_attr(
+        not(all(feature = "debug-validate", debug_assertions)),
+        allow(dead_code)
+    )]
+    stamp: Stamp,
+}
+
+impl NodeRef {
+    fn new(index: usize) -> Self {
+        NodeRef {
+            id: NonZeroUsize::new(index + 1).expect("Node index overflow."),
+            stamp: Stamp::default(),
+        }
+    }
+
+    fn index(self) -> usize {
+        self.id.get() - 1
+    }
+}
+
+impl PartialEq for NodeRef {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for NodeRef {}
+
+impl Hash for NodeRef {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl PartialOrd for NodeRef {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for NodeRef {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+impl fmt::Debug for NodeRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NodeRef")
+            .field("id", &self.index())
+            .finish()
+    }
 }
 
 // Node
@@ -124,16 +493,65 @@ pub struct NodeRef {
 struct Node<T> {
     content: T,
     parent: Option<NodeRef>,
-    children: Vec<NodeRef>,
+    children: ChildList,
+    /// Previous and next sibling, only used by linked child storage.
+    siblings: [Option<NodeRef>; 2],
+    locked: bool,
+    /// Whether the children are not loaded yet, see [Tree::populate_children].
+    children_unknown: bool,
 }
 
 // Tree
 // ==================================================================
+/// What happens to the children of a node removed with [Tree::remove_with].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RemoveBehavior {
+    /// The children are left in the tree, but are not reachable from the
+    /// root anymore, like with [Tree::remove]. They can be appended to
+    /// another node or collected with [Tree::gc].
+    Orphan,
+    /// The children and all their descendants are removed too.
+    Recursive,
+    /// The children take the place of the node in the child list of its
+    /// parent. Promoting the only child of the root makes it the new root.
+    PromoteChildren,
+}
+
+/// Statistics of a removal, returned by [Tree::remove] and related methods.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct RemovalReport {
+    /// Number of removed nodes.
+    pub removed: usize,
+    /// Number of live nodes left unreachable because an ancestor was
+    /// removed, see [Tree::unreachable_nodes].
+    pub orphaned: usize,
+    /// Number of removed slots in the underlying vector after the removal,
+    /// see [Tree::tombstones].
+    pub tombstones: usize,
+}
+
+/// Tree whose nodes are stored in a vector and referenced by [NodeRef].
+///
+/// Cloning a tree keeps every node in the same slot, including removed
+/// and detached ones, so a [NodeRef] of the original tree references the
+/// corresponding node in the clone and vice versa. Use
+/// [Tree::clone_compacted] to get a copy without removed slots instead.
 #[derive(Debug, Clone)]
 pub struct Tree<T> {
     nodes: Vec<Option<Node<T>>>,
     root: Option<NodeRef>,
     len: usize,
+    /// Number of live nodes not reachable from the root, because they
+    /// were created without parent or an ancestor was removed.
+    unreachable: usize,
+    free: Vec<usize>,
+    storage: ChildStorage,
+    spare_children: Vec<Vec<NodeRef>>,
+    marks: Vec<u8>,
+    #[cfg(feature = "spans")]
+    spans: Vec<Option<Range<usize>>>,
+    stable_ids: StableIds,
+    registry: Registry,
 }
 
 /// Represent a tree structure.
@@ -151,6 +569,15 @@ impl<T> Tree<T> {
             nodes: Vec::new(),
             root: None,
             len: 0,
+            unreachable: 0,
+            free: Vec::new(),
+            storage: ChildStorage::Vec,
+            spare_children: Vec::new(),
+            marks: Vec::new(),
+            #[cfg(feature = "spans")]
+            spans: Vec::new(),
+            stable_ids: StableIds::default(),
+            registry: Registry::default(),
         }
     }
 
@@ -171,6 +598,7 @@ impl<T> Tree<T> {
         }
 
         let node_ref = self.node(content);
+        self.unreachable -= 1;
         self.root = Some(node_ref);
 
         Ok(node_ref)
@@ -192,6 +620,9 @@ impl<T> Tree<T> {
             return Err(TreeError::new("Another root node already exists."));
         }
 
+        if self.get_node(root_ref).is_some() && self.root != Some(root_ref) {
+            self.unreachable = self.len - self.subtree_len(root_ref);
+        }
         self.root = Some(root_ref);
         Ok(())
     }
@@ -204,29 +635,146 @@ impl<T> Tree<T> {
     /// *Returns:* A result containing a [NodeRef] object
     ///            referencing the created node.
     pub fn node(&mut self, content: T) -> NodeRef {
-        let id = self.nodes.len();
-
-        self.nodes.push(Some(Node {
+        let node = Some(Node {
             content,
             parent: None,
-            children: Vec::new(),
-        }));
+            children: self.new_child_list(),
+            siblings: [None, None],
+            locked: false,
+            children_unknown: false,
+        });
+
+        let id = match self.free.pop() {
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+            Some(id) => {
+                self.nodes[id] = node;
+                if let Some(marks) = self.marks.get_mut(id) {
+                    *marks = 0;
+                }
+                id
+            }
+        };
+        #[cfg(feature = "spans")]
+        if let Some(span) = self.spans.get_mut(id) {
+            *span = None;
+        }
+        self.stable_ids.clear_slot(id);
         self.len += 1;
+        self.unreachable += 1;
 
-        NodeRef { id }
+        self.registry.stamp(NodeRef::new(id))
     }
 
     /// Create a node child on another.
     ///
+    /// The node is added as last child of the parent node.
+    ///
     /// *Arguments:*
     /// * `parent_ref` - [NodeRef] of the parent node.
     /// * `content` - The item to be set as content of the node.
     ///
     /// *Returns:* A [NodeRef] object referencing the created node.
-    pub fn child_node(&mut self, parent: NodeRef, content: T) -> Result<NodeRef> {
-        let child = self.node(content);
-        self.append_child(parent, child)?;
-        Ok(child)
+    ///            Returns an error if the parent does not exist or is
+    ///            locked, in which case no node is created.
+    pub fn child_node(&mut self, parent_ref: NodeRef, content: T) -> Result<NodeRef> {
+        self.check_parent(parent_ref, "child_node")?;
+        let child_ref = self.node(content);
+        self.append_child(parent_ref, child_ref)?;
+        Ok(child_ref)
+    }
+
+    /// Create several nodes as children of another.
+    ///
+    /// The nodes are added as last children of the parent node,
+    /// in the order of `contents`.
+    ///
+    /// *Arguments:*
+    /// * `parent_ref` - [NodeRef] of the parent node.
+    /// * `contents` - The items to be set as content of the nodes.
+    ///
+    /// *Returns:* The [NodeRef] objects referencing the created nodes.
+    ///            Returns an error if the parent does not exist or is
+    ///            locked, in which case no node is created.
+    pub fn child_nodes(
+        &mut self,
+        parent_ref: NodeRef,
+        contents: impl IntoIterator<Item = T>,
+    ) -> Result<Vec<NodeRef>> {
+        self.check_parent(parent_ref, "child_nodes")?;
+        Ok(contents
+            .into_iter()
+            .map(|content| self.child_node(parent_ref, content).unwrap())
+            .collect())
+    }
+
+    /// Create a node as the next sibling of another.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node to insert after.
+    /// * `content` - The item to be set as content of the new node.
+    ///
+    /// *Returns:* A [NodeRef] object referencing the created node.
+    ///            Returns an error if the node does not exist, has no
+    ///            parent or if the parent is locked, in which case no
+    ///            node is created.
+    pub fn sibling_node_after(&mut self, node_ref: NodeRef, content: T) -> Result<NodeRef> {
+        self.sibling_node(node_ref, content, 1, "sibling_node_after")
+    }
+
+    /// Create a node as the previous sibling of another.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node to insert before.
+    /// * `content` - The item to be set as content of the new node.
+    ///
+    /// *Returns:* A [NodeRef] object referencing the created node.
+    ///            Returns an error if the node does not exist, has no
+    ///            parent or if the parent is locked, in which case no
+    ///            node is created.
+    pub fn sibling_node_before(&mut self, node_ref: NodeRef, content: T) -> Result<NodeRef> {
+        self.sibling_node(node_ref, content, 0, "sibling_node_before")
+    }
+
+    fn sibling_node(
+        &mut self,
+        node_ref: NodeRef,
+        content: T,
+        offset: usize,
+        operation: &'static str,
+    ) -> Result<NodeRef> {
+        let context = |error: TreeError| error.during(operation).involving(&[node_ref]);
+        let parent_ref = match self.get_node(node_ref) {
+            None => return Err(context(node_not_found(node_ref))),
+            Some(node) => match node.parent {
+                Some(parent_ref) if self.get_node(parent_ref).is_some() => parent_ref,
+                _ => return Err(context(not_found("Node has no parent."))),
+            },
+        };
+        self.check_unlocked(parent_ref).map_err(context)?;
+
+        let sibling_ref = self.node(content);
+        let mut children: Vec<NodeRef> = self.live_children(parent_ref).collect();
+        let position = children
+            .iter()
+            .position(|child| *child == node_ref)
+            .unwrap();
+        children.insert(position + offset, sibling_ref);
+        self.set_child_list(parent_ref, children);
+        self.reattach(sibling_ref, Some(parent_ref));
+        self.get_node_mut(sibling_ref).unwrap().parent = Some(parent_ref);
+        Ok(sibling_ref)
+    }
+
+    /// Check that nodes can be added to a parent node.
+    fn check_parent(&self, parent_ref: NodeRef, operation: &'static str) -> Result<()> {
+        let context = |error: TreeError| error.during(operation).involving(&[parent_ref]);
+        if self.get_node(parent_ref).is_none() {
+            return Err(context(not_found("Parent node does not exist.")));
+        }
+        self.check_unlocked(parent_ref).map_err(context)
     }
 
     /// Remove a node from the tree.
@@ -235,127 +783,901 @@ impl<T> Tree<T> {
     /// tree, nor resize the underying vector so that other node
     /// references won't be invalidated.
     ///
+    /// The children of the node are left in the tree, but are not
+    /// reachable from the root anymore. They are counted by
+    /// [RemovalReport::orphaned] together with their descendants. Use
+    /// [Tree::remove_with] to choose what happens to them.
+    ///
     /// *Arguments:*
     /// * `node_ref` - [NodeRef] object indicating which node to remove.
     ///
-    /// *Returns:* A result indicating whether the node was successfully
+    /// *Returns:* A [RemovalReport] if the node was successfully
     ///            removed. Returns an error if `node_ref` is
     ///            invalid or if it was already removed.
-    pub fn remove(&mut self, node_ref: NodeRef) -> Result<()> {
-        match self.nodes.get(node_ref.id) {
-            None => return Err(TreeError::new("Invalid node reference.")),
+    pub fn remove(&mut self, node_ref: NodeRef) -> Result<RemovalReport> {
+        self.registry.check(node_ref);
+        let context = |error: TreeError| error.during("remove").involving(&[node_ref]);
+        match self.nodes.get(node_ref.index()) {
+            None => return Err(context(invalid_input("Invalid node reference."))),
             Some(node) => match node {
-                None => return Err(TreeError::new("Node already removed.")),
-                Some(_) => self.nodes[node_ref.id] = None,
+                None => return Err(context(not_found("Node already removed."))),
+                Some(_) => {
+                    self.check_unlocked(node_ref).map_err(context)?;
+                }
             },
         }
+
+        let orphaned = self.orphan_children(&[node_ref]);
+        self.detach_removed(node_ref);
+        self.nodes[node_ref.index()] = None;
         self.len -= 1;
-        Ok(())
+        Ok(self.removal_report(1, orphaned))
     }
 
-    /// Get the number of nodes in the tree.
+    /// Remove a node from the tree, choosing what happens to its children.
     ///
-    /// This is not the same as the space used by the vector
-    /// implementation if some nodes where removed.
-    pub fn len(&self) -> usize {
-        self.len
-    }
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] object indicating which node to remove.
+    /// * `behavior` - The [RemoveBehavior] for the children of the node.
+    ///
+    /// *Returns:* A [RemovalReport] of the removal.
+    ///            Returns an error if `node_ref` is invalid or was already
+    ///            removed, if a removed node is locked, or if the children
+    ///            of a root node with more than one child are promoted.
+    ///            The tree is left untouched on error.
+    pub fn remove_with(
+        &mut self,
+        node_ref: NodeRef,
+        behavior: RemoveBehavior,
+    ) -> Result<RemovalReport> {
+        let context = |error: TreeError| error.during("remove_with").involving(&[node_ref]);
+        match behavior {
+            RemoveBehavior::Orphan => self.remove(node_ref).map_err(context),
+            RemoveBehavior::Recursive => {
+                let subtree: Vec<NodeRef> = self
+                    .depth_first_of(node_ref, true)
+                    .map_err(context)?
+                    .collect();
+                self.check_subtree_unlocked(&subtree).map_err(context)?;
 
-    fn get_node(&self, node_ref: NodeRef) -> Option<&Node<T>> {
-        match self.nodes.get(node_ref.id) {
-            None => None,
-            Some(node) => node.as_ref(),
-        }
-    }
+                let operation = Operation::start("remove_with", self.len);
+                self.orphan_children(&subtree);
+                self.detach_removed(node_ref);
+                for descendant_ref in &subtree {
+                    self.nodes[descendant_ref.index()] = None;
+                }
+                self.len -= subtree.len();
+                operation.finish(subtree.len());
+                Ok(self.removal_report(subtree.len(), 0))
+            }
+            RemoveBehavior::PromoteChildren => {
+                let node = match self.get_node(node_ref) {
+                    None => return Err(context(node_not_found(node_ref))),
+                    Some(node) => node,
+                };
+                let parent = node.parent.filter(|p| self.get_node(*p).is_some());
+                let children: Vec<NodeRef> = self
+                    .children_of_node(node)
+                    .filter(|child_ref| self.get_node(**child_ref).is_some())
+                    .copied()
+                    .collect();
+                let is_root = self.root == Some(node_ref);
+                if is_root && children.len() > 1 {
+                    return Err(context(invalid_input(
+                        "Cannot promote several children of the root node.",
+                    )));
+                }
+                self.check_unlocked(node_ref).map_err(context)?;
 
-    fn get_node_mut(&mut self, node_ref: NodeRef) -> Option<&mut Node<T>> {
-        match self.nodes.get_mut(node_ref.id) {
-            None => None,
-            Some(node) => node.as_mut(),
+                // The children take the place of the node, reachable or not.
+                if self.is_unreachable(node_ref) {
+                    self.unreachable -= 1;
+                }
+                self.set_child_list(node_ref, Vec::new());
+                if let Some(parent_ref) = parent {
+                    let siblings: Vec<NodeRef> = self
+                        .children_of(parent_ref)
+                        .filter(|child_ref| self.get_node(**child_ref).is_some())
+                        .flat_map(|child_ref| {
+                            if *child_ref == node_ref {
+                                children.clone()
+                            } else {
+                                vec![*child_ref]
+                            }
+                        })
+                        .collect();
+                    self.set_child_list(parent_ref, siblings);
+                }
+                for child_ref in &children {
+                    self.get_node_mut(*child_ref).unwrap().parent = parent;
+                }
+                if is_root {
+                    self.root = children.first().copied();
+                }
+
+                self.nodes[node_ref.index()] = None;
+                self.len -= 1;
+                Ok(self.removal_report(1, 0))
+            }
         }
     }
 
-    /// Get immutable reference to the node content.
+    /// Remove multiple nodes from the tree at once.
+    ///
+    /// All references are validated before any node is removed, so
+    /// either all nodes are removed or, on error, the tree is left
+    /// untouched.
     ///
     /// *Arguments:*
-    /// * `node_ref` - [NodeRef] object indicating which node
-    ///   content to retrieve.
+    /// * `node_refs` - Slice of [NodeRef] objects indicating which nodes to remove.
     ///
-    /// *Returns:* Reference to the object contained in the node
-    ///            or `None` if the `node_ref` is invalid.
-    pub fn get(&self, node_ref: NodeRef) -> Option<&T> {
-        match self.get_node(node_ref) {
-            None => None,
-            Some(node) => Some(&node.content),
+    /// *Returns:* A [RemovalReport] of the removal.
+    ///            Returns an error if any of the references is invalid,
+    ///            was already removed or appears more than once.
+    pub fn remove_many(&mut self, node_refs: &[NodeRef]) -> Result<RemovalReport> {
+        let mut seen = HashSet::with_capacity(node_refs.len());
+        for node_ref in node_refs {
+            self.registry.check(*node_ref);
+            let context = |error: TreeError| error.during("remove_many").involving(&[*node_ref]);
+            match self.nodes.get(node_ref.index()) {
+                None => return Err(context(invalid_input("Invalid node reference."))),
+                Some(None) => return Err(context(not_found("Node already removed."))),
+                Some(Some(_)) => {
+                    if !seen.insert(node_ref.index()) {
+                        return Err(context(invalid_input("Duplicate node reference.")));
+                    }
+                    self.check_unlocked(*node_ref).map_err(context)?;
+                }
+            }
+        }
+
+        let operation = Operation::start("remove_many", self.len);
+        let orphaned = self.orphan_children(node_refs);
+        for node_ref in node_refs {
+            self.detach_removed(*node_ref);
+        }
+        for node_ref in node_refs {
+            self.nodes[node_ref.index()] = None;
         }
+        self.len -= node_refs.len();
+        operation.finish(node_refs.len());
+
+        Ok(self.removal_report(node_refs.len(), orphaned))
     }
 
-    /// Get mutable reference to the node content.
-    ///
-    /// *Arguments:*
-    /// * `node_ref` - [NodeRef] object indicating which node
-    ///   content to retrieve.
+    /// Update the count of unreachable nodes for nodes about to be
+    /// removed, whose kept descendants become unreachable.
     ///
-    /// *Returns:* Mutable reference to the object contained in the node
-    ///            or `None` if the `node_ref` is invalid.
-    pub fn get_mut(&mut self, node_ref: NodeRef) -> Option<&mut T> {
-        match self.get_node_mut(node_ref) {
-            None => None,
-            Some(node) => Some(&mut node.content),
+    /// *Returns:* The number of live nodes orphaned by the removal.
+    fn orphan_children(&mut self, node_refs: &[NodeRef]) -> usize {
+        let removed: HashSet<usize> = node_refs.iter().map(|node_ref| node_ref.index()).collect();
+        let already_orphaned: Vec<bool> = node_refs
+            .iter()
+            .map(|node_ref| self.is_unreachable(*node_ref))
+            .collect();
+
+        // Descendants of unreachable nodes are already counted.
+        let mut visited = removed.clone();
+        let mut stack: Vec<NodeRef> = node_refs
+            .iter()
+            .zip(&already_orphaned)
+            .filter(|(_, already_orphaned)| !**already_orphaned)
+            .map(|(node_ref, _)| *node_ref)
+            .collect();
+        let mut orphaned = 0;
+        while let Some(node_ref) = stack.pop() {
+            for child_ref in self.live_children(node_ref) {
+                if visited.insert(child_ref.index()) {
+                    orphaned += 1;
+                    stack.push(child_ref);
+                }
+            }
         }
+
+        let removed_orphans = already_orphaned
+            .iter()
+            .filter(|orphaned| **orphaned)
+            .count();
+        self.unreachable = self.unreachable + orphaned - removed_orphans;
+        orphaned
     }
-    /// Get reference to root node.
-    ///
-    /// *Returns:* Reference to the root node or `None` if no root node exists.
-    pub fn get_root_ref(&self) -> Option<NodeRef> {
-        self.root
+
+    /// Check whether a node is not reachable from the root, because
+    /// one of its ancestors was removed or has no parent.
+    fn is_unreachable(&self, node_ref: NodeRef) -> bool {
+        if self.unreachable == 0 {
+            return false;
+        }
+        let mut next = Some(node_ref);
+        while let Some(current_ref) = next {
+            if self.root == Some(current_ref) {
+                return false;
+            }
+            match self.get_node(current_ref) {
+                None => return true,
+                Some(node) => next = node.parent,
+            }
+        }
+        true
     }
 
-    /// Add child node to a node.
-    ///
-    /// *Arguments:*
-    /// * `parent_ref` - [NodeRef] of the parent node.
-    /// * `child_ref` - [NodeRef] of the child node.
-    ///
-    /// *Returns:* Result indicating whether the operations was successful.
-    ///            Returns an error if one of the node references is invalid.
-    pub fn append_child(&mut self, parent_ref: NodeRef, child_ref: NodeRef) -> Result<()> {
-        if self.get_node_mut(parent_ref).is_none() {
-            return Err(TreeError::new("Parent node does not exist."));
+    /// Update the count of unreachable nodes for a subtree about to be
+    /// attached to a parent, or detached if `parent_ref` is `None`.
+    fn reattach(&mut self, node_ref: NodeRef, parent_ref: Option<NodeRef>) {
+        let was_unreachable = self.is_unreachable(node_ref);
+        let unreachable = match parent_ref {
+            None => true,
+            Some(parent_ref) => self.is_unreachable(parent_ref),
+        };
+        if was_unreachable != unreachable {
+            let size = self.subtree_len(node_ref);
+            if was_unreachable {
+                self.unreachable -= size;
+            } else {
+                self.unreachable += size;
+            }
         }
+    }
 
-        if self.get_node_mut(child_ref).is_none() {
-            return Err(TreeError::new("Child node does not exist."));
+    fn removal_report(&self, removed: usize, orphaned: usize) -> RemovalReport {
+        RemovalReport {
+            removed,
+            orphaned,
+            tombstones: self.tombstones(),
         }
+    }
 
-        let parent_node = self.get_node_mut(parent_ref).unwrap();
-        parent_node.children.push(child_ref);
+    /// Take a removed node out of the child list of its parent, so that
+    /// the children of a node are the same with all storages.
+    fn detach_removed(&mut self, node_ref: NodeRef) {
+        self.unlink_child(node_ref);
+    }
 
-        let child_node = self.get_node_mut(child_ref).unwrap();
-        child_node.parent = Some(parent_ref);
+    /// Remove all live nodes which are not reachable from the root.
+    ///
+    /// See [Tree::unreachable_nodes] for how such nodes come to be. Nodes
+    /// inside a locked subtree are kept. Like [Tree::remove], this does
+    /// not free memory by itself.
+    ///
+    /// *Returns:* The number of removed nodes.
+    pub fn gc(&mut self) -> usize {
+        let operation = Operation::start("gc", self.len);
+        let unreachable: Vec<NodeRef> = self
+            .unreachable_nodes()
+            .into_iter()
+            .filter(|node_ref| !self.is_locked(*node_ref).unwrap())
+            .collect();
 
-        Ok(())
+        self.orphan_children(&unreachable);
+        for node_ref in &unreachable {
+            self.detach_removed(*node_ref);
+            self.nodes[node_ref.index()] = None;
+        }
+        self.len -= unreachable.len();
+
+        operation.finish(unreachable.len());
+        unreachable.len()
     }
 
-    /// Add children nodes to a node.
+    /// Reclaim the memory used by removed nodes.
     ///
-    /// *Arguments:*
-    /// * `parent_ref` - [NodeRef] of of the parent node.
-    /// * `children_refs` - Slice of [NodeRef] for the child nodes.
+    /// Removed nodes at the end of the underlying vector are dropped and
+    /// the vector is shrunk, while the other removed slots are reused by
+    /// nodes created afterwards. References to live nodes stay valid.
     ///
-    /// *Returns:* Result indicating whether the operations was successful.
-    ///            Returns an error if one of the node references is invalid.
-    pub fn append_children(
-        &mut self,
-        parent_ref: NodeRef,
-        children_refs: &[NodeRef],
-    ) -> Result<()> {
+    /// The references to removed nodes held by the tree itself are
+    /// dropped: the children of removed nodes are left without parent
+    /// and a removed root node is unset.
+    ///
+    /// This is unchecked because references to removed nodes may end up
+    /// pointing to new, unrelated nodes instead of being rejected. Only
+    /// use it if you can guarantee that no such stale references are
+    /// used after the purge.
+    ///
+    /// *Returns:* The number of reclaimed slots.
+    pub fn purge_tombstones_unchecked(&mut self) -> usize {
+        let operation = Operation::start("purge_tombstones_unchecked", self.len);
+        let reclaimed = self.tombstones() - self.free.len();
+        self.drop_dead_refs();
+
+        while let Some(None) = self.nodes.last() {
+            self.nodes.pop();
+        }
+        self.nodes.shrink_to_fit();
+
+        self.free = (0..self.nodes.len())
+            .rev()
+            .filter(|id| self.nodes[*id].is_none())
+            .collect();
+
+        operation.finish(reclaimed);
+        reclaimed
+    }
+
+    /// Remove the references to removed nodes from the root and the
+    /// parents, so that their slots can be reused safely.
+    ///
+    /// Removed and moved children are already taken out of the child
+    /// lists of their parents, with all storages.
+    fn drop_dead_refs(&mut self) {
+        let is_live = |nodes: &[Option<Node<T>>], node_ref: NodeRef| {
+            nodes.get(node_ref.index()).is_some_and(Option::is_some)
+        };
+
+        if self
+            .root
+            .is_some_and(|root_ref| !is_live(&self.nodes, root_ref))
+        {
+            self.root = None;
+        }
+
+        for id in 0..self.nodes.len() {
+            let dead_parent = match &self.nodes[id] {
+                None => continue,
+                Some(node) => node
+                    .parent
+                    .is_some_and(|parent_ref| !is_live(&self.nodes, parent_ref)),
+            };
+            if dead_parent {
+                let node = self.nodes[id].as_mut().unwrap();
+                node.parent = None;
+                node.siblings = [None, None];
+            }
+        }
+    }
+
+    /// Clear the tree and let a closure populate it again, reusing
+    /// the allocated memory.
+    ///
+    /// All nodes are dropped, including locked ones, but the underlying
+    /// vector keeps its capacity and the vectors holding the children of
+    /// the old nodes are given to the new nodes. This avoids allocation
+    /// churn when a tree is regenerated over and over, like the widget
+    /// tree of an immediate-mode user interface.
+    ///
+    /// All node references are invalidated.
+    ///
+    /// *Arguments:*
+    /// * `build` - Function populating the cleared tree.
+    ///
+    /// *Returns:* The value returned by `build`.
+    pub fn rebuild<R>(&mut self, build: impl FnOnce(&mut Tree<T>) -> R) -> R {
+        for node in self.nodes.drain(..).flatten() {
+            if let ChildList::Vec(mut children) = node.children {
+                children.clear();
+                self.spare_children.push(children);
+            }
+        }
+        self.root = None;
+        self.len = 0;
+        self.unreachable = 0;
+        self.free.clear();
+        self.marks.clear();
+        #[cfg(feature = "spans")]
+        self.spans.clear();
+        self.stable_ids.clear();
+
+        build(self)
+    }
+
+    /// Estimate the memory used by a subtree.
+    ///
+    /// For each node, this sums the size of its slot in the underlying
+    /// vector, which includes the content itself, the memory allocated
+    /// to hold its children and the heap memory owned by the content as
+    /// reported by `item_size_fn`. This makes it possible to find the
+    /// heaviest branches of a tree.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the root of the subtree.
+    /// * `item_size_fn` - Function returning the heap memory owned by
+    ///   the content of a node, in bytes.
+    ///
+    /// *Returns:* The estimated size in bytes. Returns error if the
+    ///            node does not exist.
+    pub fn heap_size_estimate(
+        &self,
+        node_ref: NodeRef,
+        item_size_fn: impl Fn(&T) -> usize,
+    ) -> Result<usize> {
+        let size = self
+            .depth_first_of(node_ref, true)?
+            .map(|node_ref| {
+                let node = self.get_node(node_ref).unwrap();
+                let children = match &node.children {
+                    ChildList::Vec(children) => children.capacity() * mem::size_of::<NodeRef>(),
+                    ChildList::Linked { .. } => 0,
+                };
+                mem::size_of::<Option<Node<T>>>() + children + item_size_fn(&node.content)
+            })
+            .sum();
+        Ok(size)
+    }
+
+    /// Get the number of removed nodes still occupying space
+    /// in the underlying vector.
+    pub fn tombstones(&self) -> usize {
+        self.nodes.len() - self.len
+    }
+
+    /// Get the number of slots in the underlying vector, which is
+    /// the sum of the live nodes and of the [Tree::tombstones].
+    ///
+    /// Node references index into these slots, so this is one more
+    /// than the largest index a live node can have.
+    pub fn slot_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Get the number of nodes reachable from the root.
+    ///
+    /// Removed nodes are not counted, see [Tree::slot_count] for
+    /// the space used by the vector implementation. Live nodes which are
+    /// not reachable from the root, like nodes created with [Tree::node]
+    /// and not attached yet or nodes left without parent by the removal
+    /// of an ancestor, see [RemovalReport::orphaned], are not counted
+    /// either until they are attached under the root.
+    pub fn len(&self) -> usize {
+        self.len - self.unreachable
+    }
+
+    /// Check whether the tree contains no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get_node(&self, node_ref: NodeRef) -> Option<&Node<T>> {
+        self.registry.check(node_ref);
+        match self.nodes.get(node_ref.index()) {
+            None => None,
+            Some(node) => node.as_ref(),
+        }
+    }
+
+    fn get_node_mut(&mut self, node_ref: NodeRef) -> Option<&mut Node<T>> {
+        self.registry.check(node_ref);
+        match self.nodes.get_mut(node_ref.index()) {
+            None => None,
+            Some(node) => node.as_mut(),
+        }
+    }
+
+    /// Get immutable reference to the node content.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] object indicating which node
+    ///   content to retrieve.
+    ///
+    /// *Returns:* Reference to the object contained in the node
+    ///            or `None` if the `node_ref` is invalid.
+    pub fn get(&self, node_ref: NodeRef) -> Option<&T> {
+        match self.get_node(node_ref) {
+            None => None,
+            Some(node) => Some(&node.content),
+        }
+    }
+
+    /// Get mutable reference to the node content.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] object indicating which node
+    ///   content to retrieve.
+    ///
+    /// *Returns:* Mutable reference to the object contained in the node
+    ///            or `None` if the `node_ref` is invalid.
+    pub fn get_mut(&mut self, node_ref: NodeRef) -> Option<&mut T> {
+        match self.get_node_mut(node_ref) {
+            None => None,
+            Some(node) => Some(&mut node.content),
+        }
+    }
+
+    /// Get immutable reference to the node content.
+    ///
+    /// Like [Tree::get], but returns an error instead of `None`.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] object indicating which node
+    ///   content to retrieve.
+    ///
+    /// *Returns:* Reference to the object contained in the node.
+    ///            Returns an error of kind [ErrorKind::NotFound]
+    ///            if the `node_ref` is invalid.
+    pub fn try_get(&self, node_ref: NodeRef) -> Result<&T> {
+        self.get(node_ref).ok_or_else(|| node_not_found(node_ref))
+    }
+
+    /// Get mutable reference to the node content.
+    ///
+    /// Like [Tree::get_mut], but returns an error instead of `None`.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] object indicating which node
+    ///   content to retrieve.
+    ///
+    /// *Returns:* Mutable reference to the object contained in the node.
+    ///            Returns an error of kind [ErrorKind::NotFound]
+    ///            if the `node_ref` is invalid.
+    pub fn try_get_mut(&mut self, node_ref: NodeRef) -> Result<&mut T> {
+        self.get_mut(node_ref)
+            .ok_or_else(|| node_not_found(node_ref))
+    }
+
+    /// Change the content of all nodes matching a predicate.
+    ///
+    /// The nodes are visited in a single pass over the storage, so this
+    /// includes nodes which are not reachable from the root node.
+    ///
+    /// *Arguments:*
+    /// * `predicate` - Function deciding whether the content of a node
+    ///   should be changed.
+    /// * `update_fn` - Function changing the content of a matching node.
+    ///
+    /// *Returns:* The number of changed nodes.
+    pub fn update_where(
+        &mut self,
+        predicate: impl Fn(&T) -> bool,
+        mut update_fn: impl FnMut(&mut T),
+    ) -> usize {
+        let mut count = 0;
+        for node in self.nodes.iter_mut().flatten() {
+            if predicate(&node.content) {
+                update_fn(&mut node.content);
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Keep only the nodes whose content matches a predicate.
+    ///
+    /// Nodes not matching the predicate are removed together with all
+    /// their descendants. The predicate is evaluated on all live nodes,
+    /// including the ones not reachable from the root node, so this also
+    /// works on trees without a root that are used as a forest of
+    /// fragments. Removing the root node leaves the tree without root.
+    ///
+    /// *Arguments:*
+    /// * `keep` - Function deciding whether a node is kept.
+    ///
+    /// *Returns:* The number of removed nodes. Returns an error of kind
+    ///            [ErrorKind::Locked] if a removed node is locked, in
+    ///            which case the tree is left untouched.
+    pub fn retain(&mut self, mut keep: impl FnMut(&T) -> bool) -> Result<usize> {
+        let mut removed: Vec<bool> = self
+            .nodes
+            .iter()
+            .map(|node| node.as_ref().is_some_and(|node| !keep(&node.content)))
+            .collect();
+
+        // Propagate the removal from the top of each fragment downwards.
+        let tops: Vec<NodeRef> = self
+            .iter_all_nodes()
+            .filter(|node_ref| {
+                let parent = self.get_node(*node_ref).unwrap().parent;
+                parent
+                    .and_then(|parent_ref| self.get_node(parent_ref))
+                    .is_none()
+            })
+            .collect();
+        let mut removed_tops = Vec::new();
+        let mut stack: Vec<(NodeRef, bool)> = tops.into_iter().map(|top| (top, false)).collect();
+        while let Some((node_ref, parent_removed)) = stack.pop() {
+            if parent_removed {
+                removed[node_ref.index()] = true;
+            } else if removed[node_ref.index()] {
+                removed_tops.push(node_ref);
+            }
+            let node_removed = removed[node_ref.index()];
+            stack.extend(
+                self.live_children(node_ref)
+                    .map(|child_ref| (child_ref, node_removed)),
+            );
+        }
+
+        for top_ref in &removed_tops {
+            self.check_unlocked(*top_ref)
+                .map_err(|error| error.during("retain"))?;
+        }
+        if let Some(locked) = (0..self.nodes.len())
+            .map(NodeRef::new)
+            .find(|node_ref| removed[node_ref.index()] && self.get_node(*node_ref).unwrap().locked)
+        {
+            return Err(self.check_unlocked(locked).unwrap_err().during("retain"));
+        }
+
+        let operation = Operation::start("retain", self.len);
+        let removed_refs: Vec<NodeRef> = (0..self.nodes.len())
+            .filter(|index| removed[*index])
+            .map(NodeRef::new)
+            .collect();
+        self.orphan_children(&removed_refs);
+        for top_ref in removed_tops {
+            self.detach_removed(top_ref);
+        }
+        let mut count = 0;
+        for (index, removed) in removed.into_iter().enumerate() {
+            if removed {
+                self.nodes[index] = None;
+                count += 1;
+            }
+        }
+        if self
+            .root
+            .is_some_and(|root_ref| self.get_node(root_ref).is_none())
+        {
+            self.root = None;
+        }
+        self.len -= count;
+        operation.finish(count);
+        Ok(count)
+    }
+
+    /// Get immutable reference to the node content without any check.
+    ///
+    /// This skips the bounds and removal checks of [Tree::get], for use
+    /// in tight loops over references which were already validated.
+    ///
+    /// # Safety
+    ///
+    /// `node_ref` must point to a node of this tree which was not removed.
+    /// Calling this with any other reference is undefined behavior.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] object indicating which node
+    ///   content to retrieve.
+    ///
+    /// *Returns:* Reference to the object contained in the node.
+    pub unsafe fn get_unchecked(&self, node_ref: NodeRef) -> &T {
+        &self.node_unchecked(node_ref).content
+    }
+
+    /// Get mutable reference to the node content without any check.
+    ///
+    /// # Safety
+    ///
+    /// Same as [Tree::get_unchecked].
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] object indicating which node
+    ///   content to retrieve.
+    ///
+    /// *Returns:* Mutable reference to the object contained in the node.
+    pub unsafe fn get_unchecked_mut(&mut self, node_ref: NodeRef) -> &mut T {
+        match self.nodes.get_unchecked_mut(node_ref.index()) {
+            Some(node) => &mut node.content,
+            None => std::hint::unreachable_unchecked(),
+        }
+    }
+
+    /// Get an iterator over the children of a node without any check.
+    ///
+    /// # Safety
+    ///
+    /// Same as [Tree::get_unchecked].
+    ///
+    /// *Arguments:*
+    /// * `parent_ref` - [NodeRef] of the parent node.
+    ///
+    /// *Returns:* Iterator returning node references to the children.
+    pub unsafe fn children_unchecked(&self, parent_ref: NodeRef) -> Children<'_, T> {
+        Children::new(self.children_of_node(self.node_unchecked(parent_ref)))
+    }
+
+    unsafe fn node_unchecked(&self, node_ref: NodeRef) -> &Node<T> {
+        match self.nodes.get_unchecked(node_ref.index()) {
+            Some(node) => node,
+            None => std::hint::unreachable_unchecked(),
+        }
+    }
+
+    /// Get reference to root node.
+    ///
+    /// *Returns:* Reference to the root node or `None` if no root node exists.
+    pub fn get_root_ref(&self) -> Option<NodeRef> {
+        self.root
+    }
+
+    /// Get reference to root node.
+    ///
+    /// Like [Tree::get_root_ref], but returns an error instead of `None`.
+    ///
+    /// *Returns:* Reference to the root node. Returns an error of kind
+    ///            [ErrorKind::NotFound] if no root node exists.
+    pub fn try_get_root_ref(&self) -> Result<NodeRef> {
+        self.root
+            .ok_or_else(|| TreeError::with_kind(ErrorKind::NotFound, "Root node does not exist."))
+    }
+
+    /// Add child node to a node.
+    ///
+    /// If the child already has a parent, it is moved away from it.
+    ///
+    /// *Arguments:*
+    /// * `parent_ref` - [NodeRef] of the parent node.
+    /// * `child_ref` - [NodeRef] of the child node.
+    ///
+    /// *Returns:* Result indicating whether the operations was successful.
+    ///            Returns an error if one of the node references is invalid.
+    pub fn append_child(&mut self, parent_ref: NodeRef, child_ref: NodeRef) -> Result<()> {
+        let context = |error: TreeError| error.during("append_child");
+        if self.get_node_mut(parent_ref).is_none() {
+            return Err(context(
+                not_found("Parent node does not exist.").involving(&[parent_ref]),
+            ));
+        }
+
+        if self.get_node_mut(child_ref).is_none() {
+            return Err(context(
+                not_found("Child node does not exist.").involving(&[child_ref]),
+            ));
+        }
+
+        self.check_unlocked(parent_ref).map_err(context)?;
+        self.check_unlocked(child_ref).map_err(context)?;
+
+        self.reattach(child_ref, Some(parent_ref));
+        self.unlink_child(child_ref);
+        self.push_child(parent_ref, child_ref);
+
+        let child_node = self.get_node_mut(child_ref).unwrap();
+        child_node.parent = Some(parent_ref);
+
+        Ok(())
+    }
+
+    /// Add children nodes to a node.
+    ///
+    /// *Arguments:*
+    /// * `parent_ref` - [NodeRef] of of the parent node.
+    /// * `children_refs` - Slice of [NodeRef] for the child nodes.
+    ///
+    /// *Returns:* Result indicating whether the operations was successful.
+    ///            Returns an error if one of the node references is invalid.
+    pub fn append_children(
+        &mut self,
+        parent_ref: NodeRef,
+        children_refs: &[NodeRef],
+    ) -> Result<()> {
         for child_ref in children_refs.iter() {
             self.append_child(parent_ref, *child_ref)?;
         }
         Ok(())
     }
 
+    /// Move all nodes of another tree under a node.
+    ///
+    /// The root of the other tree becomes the last child of the parent
+    /// node. Nodes of the other tree not reachable from its root
+    /// are dropped.
+    ///
+    /// *Arguments:*
+    /// * `parent_ref` - [NodeRef] of the parent node.
+    /// * `tree` - The tree to append.
+    ///
+    /// *Returns:* A [NodeRef] object referencing the appended root node.
+    ///            Returns an error if the parent reference is invalid,
+    ///            locked or if the other tree has no root node.
+    pub fn append_tree(&mut self, parent_ref: NodeRef, tree: Tree<T>) -> Result<NodeRef> {
+        let context = |error: TreeError| error.during("append_tree").involving(&[parent_ref]);
+        if self.get_node(parent_ref).is_none() {
+            return Err(context(not_found("Parent node does not exist.")));
+        }
+        if tree.root.is_none() {
+            return Err(context(not_found(
+                "Cannot append a tree without a root node.",
+            )));
+        }
+        self.check_unlocked(parent_ref)?;
+
+        let root_ref = self.graft(tree).unwrap();
+        self.reattach(root_ref, Some(parent_ref));
+        self.push_child(parent_ref, root_ref);
+        self.get_node_mut(root_ref).unwrap().parent = Some(parent_ref);
+
+        Ok(root_ref)
+    }
+
+    /// Create a tree with a new root node and other trees as its children.
+    ///
+    /// The roots of the trees become the children of the new root node,
+    /// in order, and storage for all nodes is allocated upfront. Trees
+    /// without root node are skipped and nodes not reachable from the
+    /// root of their tree are dropped.
+    ///
+    /// ```ignore
+    /// // Aggregate per-file trees into a project tree
+    /// let project = Tree::join(file_trees, Item::Project);
+    /// ```
+    ///
+    /// *Arguments:*
+    /// * `trees` - The trees to join.
+    /// * `root_content` - Content of the new root node.
+    ///
+    /// *Returns:* The new tree.
+    pub fn join(trees: Vec<Tree<T>>, root_content: T) -> Tree<T> {
+        let mut joined = Tree::new();
+        joined
+            .nodes
+            .reserve(1 + trees.iter().map(|tree| tree.len).sum::<usize>());
+        let root_ref = joined.root(root_content).unwrap();
+
+        for tree in trees {
+            if let Some(child_ref) = joined.graft(tree) {
+                joined.reattach(child_ref, Some(root_ref));
+                joined.push_child(root_ref, child_ref);
+                joined.get_node_mut(child_ref).unwrap().parent = Some(root_ref);
+            }
+        }
+        joined
+    }
+
+    /// Move the nodes reachable from the root of another tree into this one,
+    /// without attaching them. Returns the new reference of the other root.
+    pub(crate) fn graft(&mut self, mut tree: Tree<T>) -> Option<NodeRef> {
+        let root_ref = tree.root?;
+        let operation = Operation::start("graft", self.len);
+        let order: Vec<NodeRef> = tree.depth_first(true).unwrap().collect();
+        let mut mapping = vec![None; tree.nodes.len()];
+        let grafted = order.len();
+
+        for old_ref in order {
+            let node = tree.nodes[old_ref.index()].take().unwrap();
+            let new_ref = self.node(node.content);
+            mapping[old_ref.index()] = Some(new_ref);
+            let new_node = self.get_node_mut(new_ref).unwrap();
+            new_node.locked = node.locked;
+            new_node.children_unknown = node.children_unknown;
+
+            if old_ref != root_ref {
+                let parent_ref = mapping[node.parent.unwrap().index()].unwrap();
+                self.push_child(parent_ref, new_ref);
+                self.get_node_mut(new_ref).unwrap().parent = Some(parent_ref);
+            }
+        }
+
+        operation.finish(grafted);
+        mapping[root_ref.index()]
+    }
+
+    /// Replace a node and all its descendants with the nodes of another tree.
+    /// An empty tree removes the subtree.
+    pub(crate) fn replace_subtree(&mut self, node_ref: NodeRef, tree: Tree<T>) -> Result<()> {
+        let removed: Vec<NodeRef> = self.depth_first_of(node_ref, true)?.collect();
+        self.check_subtree_unlocked(&removed)?;
+
+        let node = self.get_node(node_ref).unwrap();
+        let parent_ref = node.parent;
+        let was_unreachable = self.is_unreachable(node_ref);
+        self.orphan_children(&removed);
+        let new_ref = self.graft(tree);
+        if let Some(new_ref) = new_ref.filter(|_| !was_unreachable) {
+            self.unreachable -= self.subtree_len(new_ref);
+        }
+
+        if let Some(parent_ref) =
+            parent_ref.filter(|parent_ref| self.get_node(*parent_ref).is_some())
+        {
+            match new_ref {
+                None => self.unlink_child(node_ref),
+                Some(new_ref) => self.replace_child(parent_ref, node_ref, new_ref),
+            }
+        }
+        if let Some(new_ref) = new_ref {
+            self.get_node_mut(new_ref).unwrap().parent = parent_ref;
+        }
+        if self.root == Some(node_ref) {
+            self.root = new_ref;
+        }
+
+        for removed_ref in &removed {
+            self.nodes[removed_ref.index()] = None;
+        }
+        self.len -= removed.len();
+
+        Ok(())
+    }
+
     /// Get iterator returning references to a node's children.
     ///
     /// *Arguments:*
@@ -363,10 +1685,10 @@ impl<T> Tree<T> {
     ///
     /// *Returns:* Iterator returning node references to the children.
     ///            Returns error if the parent reference is invalid.
-    pub fn get_children(&self, parent_ref: NodeRef) -> Result<Iter<NodeRef>> {
+    pub fn get_children(&self, parent_ref: NodeRef) -> Result<Children<'_, T>> {
         match self.get_node(parent_ref) {
-            None => Err(TreeError::new("Parent node does not exist.")),
-            Some(parent_node) => Ok(parent_node.children.iter()),
+            None => Err(not_found("Parent node does not exist.")),
+            Some(parent_node) => Ok(Children::new(self.children_of_node(parent_node))),
         }
     }
 
@@ -379,121 +1701,1289 @@ impl<T> Tree<T> {
     ///            parent exists. Returns error if the parent does not exist.
     pub fn get_parent(&self, child_ref: NodeRef) -> Result<Option<NodeRef>> {
         match self.get_node(child_ref) {
-            None => Err(TreeError::new("Child node does not exist.")),
+            None => Err(not_found("Child node does not exist.")),
             Some(child_node) => Ok(child_node.parent),
         }
     }
 
-    /// Get an iterator traversing the node and all child nodes in
-    /// depth-first order.
+    /// Get reference to the parent node.
+    ///
+    /// Like [Tree::get_parent], but a missing parent is an error as well.
+    ///
+    /// *Arguments:*
+    /// * `child_ref` - [NodeRef] of the child node.
+    ///
+    /// *Returns:* A reference to the parent node. Returns an error of
+    ///            kind [ErrorKind::NotFound] if the child node does
+    ///            not exist or has no parent.
+    pub fn try_get_parent(&self, child_ref: NodeRef) -> Result<NodeRef> {
+        match self.get_node(child_ref) {
+            None => Err(node_not_found(child_ref)),
+            Some(child_node) => child_node.parent.ok_or_else(|| {
+                TreeError::with_kind(ErrorKind::NotFound, "Node has no parent.")
+                    .involving(&[child_ref])
+            }),
+        }
+    }
+
+    /// Get an iterator over the ancestors of a node.
+    ///
+    /// The iteration starts with the parent of the node and
+    /// goes upwards until a node without live parent is reached,
+    /// so it stops below an ancestor that has been removed.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the starting node.
+    ///
+    /// *Returns:* An iterator returning the node references of the
+    ///            ancestors. Returns error if the node does not exist.
+    pub fn ancestors(&self, node_ref: NodeRef) -> Result<AncestorsIterator<'_, T>> {
+        match self.get_node(node_ref) {
+            None => Err(node_not_found(node_ref)),
+            Some(_) => Ok(AncestorsIterator {
+                tree: self,
+                next: self.live_parent(node_ref),
+            }),
+        }
+    }
+
+    /// Get an iterator over a node and its ancestors.
+    ///
+    /// Like [Tree::ancestors], but the iteration starts with the node itself.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the starting node.
+    ///
+    /// *Returns:* An iterator returning the node references of the node
+    ///            and its ancestors. Returns error if the node does not exist.
+    pub fn ancestors_or_self(&self, node_ref: NodeRef) -> Result<AncestorsIterator<'_, T>> {
+        match self.get_node(node_ref) {
+            None => Err(node_not_found(node_ref)),
+            Some(_) => Ok(AncestorsIterator {
+                tree: self,
+                next: Some(node_ref),
+            }),
+        }
+    }
+
+    /// Find the closest ancestor of a node whose content matches a predicate,
+    /// like the nearest enclosing scope of a syntax node.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the starting node, which is not tested.
+    /// * `predicate` - Function deciding whether the content of an ancestor matches.
+    ///
+    /// *Returns:* The first matching node among the ancestors, starting
+    ///            from the parent, or `None` if no ancestor matches or
+    ///            the node does not exist.
+    pub fn find_ancestor(
+        &self,
+        node_ref: NodeRef,
+        predicate: impl Fn(&T) -> bool,
+    ) -> Option<NodeRef> {
+        self.ancestors(node_ref)
+            .ok()?
+            .find(|ancestor_ref| self.get(*ancestor_ref).is_some_and(&predicate))
+    }
+
+    /// Find the first node in depth-first order whose content matches
+    /// a predicate, together with the path leading to it.
+    ///
+    /// *Arguments:*
+    /// * `predicate` - Function deciding whether the content of a node matches.
+    ///
+    /// *Returns:* The nodes from the root node to the matching node, both
+    ///            included, or `None` if no node matches or no root exists.
+    pub fn find_path(&self, predicate: impl Fn(&T) -> bool) -> Option<Vec<NodeRef>> {
+        let mut path: Vec<NodeRef> = Vec::new();
+        let mut stack = vec![(self.root?, 0)];
+
+        while let Some((node_ref, depth)) = stack.pop() {
+            path.truncate(depth);
+            path.push(node_ref);
+            if predicate(self.get(node_ref).unwrap()) {
+                return Some(path);
+            }
+
+            let children: Vec<NodeRef> = self.live_children(node_ref).collect();
+            stack.extend(children.into_iter().rev().map(|child| (child, depth + 1)));
+        }
+        None
+    }
+
+    /// Get an iterator over the descendants of a node in depth-first order.
+    ///
+    /// This is a shorthand for [Tree::depth_first_of] excluding the node.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the starting node.
+    ///
+    /// *Returns:* An iterator returning the node references of the
+    ///            descendants. Returns error if the node does not exist.
+    pub fn descendants(&self, node_ref: NodeRef) -> Result<DepthFirstIterator<'_, T>> {
+        self.depth_first_of(node_ref, false)
+    }
+
+    /// Get an iterator over a node and its descendants in depth-first order.
+    ///
+    /// This is a shorthand for [Tree::depth_first_of] including the node.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the starting node.
+    ///
+    /// *Returns:* An iterator returning the node references of the node
+    ///            and its descendants. Returns error if the node does not exist.
+    pub fn descendants_or_self(&self, node_ref: NodeRef) -> Result<DepthFirstIterator<'_, T>> {
+        self.depth_first_of(node_ref, true)
+    }
+
+    /// Get an iterator over a node and its descendants in depth-first
+    /// order, together with their content, which can skip subtrees.
+    ///
+    /// Calling [Descendants::skip_subtree] during the iteration prevents
+    /// the descendants of the node just returned from being visited, so
+    /// searches can prune branches based on their content.
+    ///
+    /// ```ignore
+    /// let mut walk = tree.walk(root)?;
+    /// while let Some((node_ref, entry)) = walk.next() {
+    ///     if entry.name == ".git" {
+    ///         walk.skip_subtree();
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the starting node, returned first.
+    ///
+    /// *Returns:* An iterator returning pairs of node references and
+    ///            contents. Returns error if the node does not exist.
+    pub fn walk(&self, node_ref: NodeRef) -> Result<Descendants<'_, T>> {
+        if self.get_node(node_ref).is_none() {
+            return Err(node_not_found(node_ref));
+        }
+        Ok(Descendants {
+            tree: self,
+            stack: vec![node_ref],
+            last: None,
+            remaining: Cell::new(None),
+        })
+    }
+
+    /// Get an iterator over the siblings following a node.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the starting node.
+    ///
+    /// *Returns:* An iterator returning the node references of the
+    ///            following siblings, nearest first. Returns error if the
+    ///            node does not exist.
+    pub fn following_siblings(&self, node_ref: NodeRef) -> Result<Skip<Children<'_, T>>> {
+        let (siblings, index) = self.siblings(node_ref)?;
+        Ok(siblings.skip(index + 1))
+    }
+
+    /// Get an iterator over the siblings preceding a node.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the starting node.
+    ///
+    /// *Returns:* An iterator returning the node references of the
+    ///            preceding siblings, nearest first. Returns error if the
+    ///            node does not exist.
+    pub fn preceding_siblings(&self, node_ref: NodeRef) -> Result<Rev<Take<Children<'_, T>>>> {
+        let (siblings, index) = self.siblings(node_ref)?;
+        Ok(siblings.take(index).rev())
+    }
+
+    /// Get the children of the parent of a node and the position of the node
+    /// among them. A node without parent is its only sibling.
+    fn siblings(&self, node_ref: NodeRef) -> Result<(Children<'_, T>, usize)> {
+        let node = match self.get_node(node_ref) {
+            None => return Err(node_not_found(node_ref)),
+            Some(node) => node,
+        };
+        match node
+            .parent
+            .filter(|parent_ref| self.get_node(*parent_ref).is_some())
+        {
+            None => Ok((Children::new(ChildRefs::empty(self)), 0)),
+            Some(parent_ref) => {
+                let index = self.child_position(parent_ref, node_ref).unwrap();
+                Ok((Children::new(self.children_of(parent_ref)), index))
+            }
+        }
+    }
+
+    /// Compare two nodes by their position in depth-first order.
+    ///
+    /// A node comes before its descendants, and the descendants of a node
+    /// come before its following siblings. This is the order returned
+    /// by [Tree::depth_first_of], which makes it possible to sort a
+    /// selection of nodes into document order. A node below a removed
+    /// ancestor is detached from the nodes above it.
+    ///
+    /// *Arguments:*
+    /// * `a` - [NodeRef] of the first node.
+    /// * `b` - [NodeRef] of the second node.
+    ///
+    /// *Returns:* The ordering of `a` relative to `b`. Returns error if
+    ///            a node does not exist or if the nodes do not share a
+    ///            common ancestor.
+    pub fn cmp_document_order(&self, a: NodeRef, b: NodeRef) -> Result<Ordering> {
+        let path = |node_ref: NodeRef| -> Result<Vec<NodeRef>> {
+            let mut path: Vec<NodeRef> = self.ancestors(node_ref)?.collect();
+            path.reverse();
+            path.push(node_ref);
+            Ok(path)
+        };
+        let (a_path, b_path) = (path(a)?, path(b)?);
+
+        if a_path[0] != b_path[0] {
+            return Err(invalid_input("Nodes do not share a common ancestor."));
+        }
+
+        let common = a_path
+            .iter()
+            .zip(&b_path)
+            .take_while(|(a_ref, b_ref)| a_ref == b_ref)
+            .count();
+        match (a_path.get(common), b_path.get(common)) {
+            (None, None) => Ok(Ordering::Equal),
+            (None, Some(_)) => Ok(Ordering::Less),
+            (Some(_), None) => Ok(Ordering::Greater),
+            (Some(a_child), Some(b_child)) => {
+                let parent_ref = a_path[common - 1];
+                let index = |child| self.child_position(parent_ref, child);
+                Ok(index(*a_child).cmp(&index(*b_child)))
+            }
+        }
+    }
+
+    /// Sort node references into depth-first order.
+    ///
+    /// The position of each node is computed once, from the positions
+    /// of its ancestors among their siblings, so sorting is cheaper than
+    /// calling [Tree::cmp_document_order] for each comparison and does
+    /// not traverse the rest of the tree. Duplicates are kept. A node
+    /// below a removed ancestor is detached from the nodes above it.
+    ///
+    /// *Arguments:*
+    /// * `refs` - The node references to sort in place.
+    ///
+    /// *Returns:* Result indicating whether the operations was successful.
+    ///            Returns an error, leaving `refs` untouched, if a node
+    ///            does not exist or if the nodes do not share a
+    ///            common ancestor.
+    pub fn sort_refs_in_traversal_order(&self, refs: &mut [NodeRef]) -> Result<()> {
+        let mut top = None;
+        let mut keys = HashMap::with_capacity(refs.len());
+        for node_ref in refs.iter() {
+            if keys.contains_key(node_ref) {
+                continue;
+            }
+
+            let mut positions = Vec::new();
+            let mut child_ref = *node_ref;
+            for parent_ref in self.ancestors(*node_ref)? {
+                positions.push(self.child_position(parent_ref, child_ref));
+                child_ref = parent_ref;
+            }
+            if *top.get_or_insert(child_ref) != child_ref {
+                return Err(invalid_input("Nodes do not share a common ancestor."));
+            }
+
+            positions.reverse();
+            keys.insert(*node_ref, positions);
+        }
+
+        refs.sort_by(|a, b| keys[a].cmp(&keys[b]));
+        Ok(())
+    }
+
+    /// Get an iterator over the nodes between two nodes in depth-first order.
+    ///
+    /// This is the structural equivalent of a text selection: it starts
+    /// at the node coming first in depth-first order and ends at the
+    /// other one, both included, regardless of the order of the arguments.
+    ///
+    /// *Arguments:*
+    /// * `a` - [NodeRef] of one end of the range.
+    /// * `b` - [NodeRef] of the other end of the range.
+    ///
+    /// *Returns:* An iterator returning the node references in the range.
+    ///            Returns error if a node does not exist or if the nodes
+    ///            do not share a common ancestor.
+    pub fn range(&self, a: NodeRef, b: NodeRef) -> Result<RangeIterator<'_, T>> {
+        let (first, last) = match self.cmp_document_order(a, b)? {
+            Ordering::Greater => (b, a),
+            _ => (a, b),
+        };
+        Ok(RangeIterator {
+            tree: self,
+            next: Some(first),
+            last,
+        })
+    }
+
+    /// Get the node following a node in depth-first order.
+    ///
+    /// This takes time proportional to the depth of the node, without
+    /// creating an iterator, which is convenient to move a cursor
+    /// through the visual order of a tree node by node.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the starting node.
+    ///
+    /// *Returns:* The next node or `None` if the node is the last one of
+    ///            the subtree of its topmost ancestor. Returns error if
+    ///            the node does not exist.
+    pub fn next_in_preorder(&self, node_ref: NodeRef) -> Result<Option<NodeRef>> {
+        match self.get_node(node_ref) {
+            None => Err(node_not_found(node_ref)),
+            Some(_) => Ok(self.preorder_successor(node_ref)),
+        }
+    }
+
+    /// Get the node preceding a node in depth-first order.
+    ///
+    /// This is the inverse of [Tree::next_in_preorder].
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the starting node.
+    ///
+    /// *Returns:* The previous node or `None` if the node has no parent.
+    ///            Returns error if the node does not exist.
+    pub fn prev_in_preorder(&self, node_ref: NodeRef) -> Result<Option<NodeRef>> {
+        let parent_ref = match self.get_node(node_ref) {
+            None => return Err(node_not_found(node_ref)),
+            Some(node) => node
+                .parent
+                .filter(|parent_ref| self.get_node(*parent_ref).is_some()),
+        };
+        let parent_ref = match parent_ref {
+            None => return Ok(None),
+            Some(parent_ref) => parent_ref,
+        };
+
+        let previous = self
+            .live_children(parent_ref)
+            .take_while(|child_ref| *child_ref != node_ref)
+            .last();
+        let mut node_ref = match previous {
+            None => return Ok(Some(parent_ref)),
+            Some(previous_ref) => previous_ref,
+        };
+        while let Some(last_ref) = self.live_children(node_ref).last() {
+            node_ref = last_ref;
+        }
+        Ok(Some(node_ref))
+    }
+
+    /// Get the node following a node in depth-first order, without
+    /// leaving the subtree of its topmost ancestor.
+    fn preorder_successor(&self, node_ref: NodeRef) -> Option<NodeRef> {
+        if let Some(child_ref) = self.live_children(node_ref).next() {
+            return Some(child_ref);
+        }
+
+        let mut node_ref = node_ref;
+        loop {
+            let parent_ref = self
+                .get_node(node_ref)?
+                .parent
+                .filter(|parent_ref| self.get_node(*parent_ref).is_some())?;
+            let next = self
+                .live_children(parent_ref)
+                .skip_while(|child_ref| *child_ref != node_ref)
+                .nth(1);
+            if next.is_some() {
+                return next;
+            }
+            node_ref = parent_ref;
+        }
+    }
+
+    /// Get an iterator over the children of a node, skipping references
+    /// to removed nodes and to nodes moved to another parent.
+    /// Count the live nodes of a subtree.
+    fn subtree_len(&self, node_ref: NodeRef) -> usize {
+        let mut count = 0;
+        let mut stack = vec![node_ref];
+        while let Some(node_ref) = stack.pop() {
+            count += 1;
+            stack.extend(self.live_children(node_ref));
+        }
+        count
+    }
+
+    fn live_children(&self, node_ref: NodeRef) -> impl Iterator<Item = NodeRef> + '_ {
+        self.children_of(node_ref)
+            .filter(move |child_ref| {
+                self.get_node(**child_ref)
+                    .is_some_and(|child| child.parent == Some(node_ref))
+            })
+            .copied()
+    }
+
+    /// Parent of a node, if it is still live and still lists the node
+    /// among its live children.
+    fn live_parent(&self, node_ref: NodeRef) -> Option<NodeRef> {
+        self.get_node(node_ref)?.parent.filter(|parent_ref| {
+            self.get_node(*parent_ref).is_some()
+                && self
+                    .live_children(*parent_ref)
+                    .any(|child_ref| child_ref == node_ref)
+        })
+    }
+
+    /// Lock a subtree against structural modifications.
+    ///
+    /// While a node is locked, adding children to it or to any of its
+    /// descendants, moving or removing them returns an error of
+    /// kind [ErrorKind::Locked]. The content of the nodes can
+    /// still be changed.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the root of the subtree to lock.
+    ///
+    /// *Returns:* Result indicating whether the operations was successful.
+    ///            Returns an error if the node does not exist.
+    pub fn lock_subtree(&mut self, node_ref: NodeRef) -> Result<()> {
+        self.set_locked(node_ref, true)
+    }
+
+    /// Unlock a subtree previously locked with [Tree::lock_subtree].
+    ///
+    /// The subtree stays locked if one of its ancestors is locked.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the root of the subtree to unlock.
+    ///
+    /// *Returns:* Result indicating whether the operations was successful.
+    ///            Returns an error if the node does not exist.
+    pub fn unlock_subtree(&mut self, node_ref: NodeRef) -> Result<()> {
+        self.set_locked(node_ref, false)
+    }
+
+    /// Check whether a node is inside a locked subtree.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node to check.
+    ///
+    /// *Returns:* Whether the node or one of its ancestors is locked.
+    ///            Returns an error if the node does not exist.
+    pub fn is_locked(&self, node_ref: NodeRef) -> Result<bool> {
+        let node = match self.get_node(node_ref) {
+            None => return Err(node_not_found(node_ref)),
+            Some(node) => node,
+        };
+
+        Ok(node.locked
+            || self
+                .ancestors(node_ref)?
+                .filter_map(|ancestor_ref| self.get_node(ancestor_ref))
+                .any(|ancestor| ancestor.locked))
+    }
+
+    fn set_locked(&mut self, node_ref: NodeRef, locked: bool) -> Result<()> {
+        match self.get_node_mut(node_ref) {
+            None => Err(node_not_found(node_ref)),
+            Some(node) => {
+                node.locked = locked;
+                Ok(())
+            }
+        }
+    }
+
+    /// Check that neither the root of a subtree, given in depth-first
+    /// order, nor any of its descendants is locked.
+    fn check_subtree_unlocked(&self, subtree: &[NodeRef]) -> Result<()> {
+        self.check_unlocked(subtree[0])?;
+        match subtree[1..]
+            .iter()
+            .find(|descendant_ref| self.get_node(**descendant_ref).unwrap().locked)
+        {
+            None => Ok(()),
+            Some(locked_ref) => self.check_unlocked(*locked_ref),
+        }
+    }
+
+    fn check_unlocked(&self, node_ref: NodeRef) -> Result<()> {
+        if self.is_locked(node_ref)? {
+            return Err(TreeError::with_kind(
+                ErrorKind::Locked,
+                "Node is inside a locked subtree.",
+            )
+            .involving(&[node_ref]));
+        }
+        Ok(())
+    }
+
+    /// Get an iterator traversing the node and all child nodes in
+    /// depth-first order.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the starting node.
+    /// * `include_start` - If true, iteration starts with the
+    ///   starting node instead of with the first
+    ///   thereof.
+    ///
+    /// *Returns:* An iterator returning the node references to the
+    ///            child nodes in depth-first order. Returns error
+    ///            if the start node does not exist.
+    pub fn depth_first_of(
+        &self,
+        node_ref: NodeRef,
+        include_start: bool,
+    ) -> Result<DepthFirstIterator<'_, T>> {
+        let mut iterator = DepthFirstIterator::new(self, node_ref, false)?;
+        if !include_start {
+            iterator.next();
+        }
+        Ok(iterator)
+    }
+
+    /// Get an iterator traversing the node and all its descendants
+    /// in breadth-first order, together with their level.
+    ///
+    /// The level of the starting node is 0, the one of its children 1,
+    /// and so on. All nodes of a level are returned before the nodes of
+    /// the next one, so wave-front algorithms can detect the end of a
+    /// level by a change of the level number.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the starting node.
+    /// * `max_level` - Level of the deepest returned nodes, `usize::MAX`
+    ///   for no limit. Deeper nodes are not traversed.
+    ///
+    /// *Returns:* An iterator returning pairs of node references and
+    ///            levels. Returns error if the start node does not exist.
+    pub fn breadth_first_with_level(
+        &self,
+        node_ref: NodeRef,
+        max_level: usize,
+    ) -> Result<BreadthFirstWithLevelIterator<'_, T>> {
+        if self.get_node(node_ref).is_none() {
+            return Err(node_not_found(node_ref));
+        }
+
+        Ok(BreadthFirstWithLevelIterator {
+            tree: self,
+            queue: VecDeque::from(vec![(node_ref, 0)]),
+            max_level,
+        })
+    }
+
+    /// Get an iterator traversing the node and all its descendants
+    /// level by level, starting from the deepest level.
+    ///
+    /// The nodes of each level are returned from left to right, and
+    /// all nodes of a level come before the ones of the level above,
+    /// so children are always visited before their parents. The node
+    /// references are collected once when the iterator is created,
+    /// without allocating a vector per level.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the starting node, returned last.
+    ///
+    /// *Returns:* An iterator returning the node references from the
+    ///            deepest level upward. Returns error if the start node
+    ///            does not exist.
+    pub fn breadth_first_rev_of(&self, node_ref: NodeRef) -> Result<BreadthFirstRevIterator> {
+        if self.get_node(node_ref).is_none() {
+            return Err(node_not_found(node_ref));
+        }
+
+        let mut order = vec![node_ref];
+        let mut starts = vec![0];
+        let mut level_end = 1;
+        let mut index = 0;
+        while index < order.len() {
+            if index == level_end {
+                starts.push(index);
+                level_end = order.len();
+            }
+            let parent_ref = order[index];
+            order.extend(self.live_children(parent_ref));
+            index += 1;
+        }
+
+        Ok(BreadthFirstRevIterator {
+            position: *starts.last().unwrap(),
+            end: order.len(),
+            order,
+            starts,
+        })
+    }
+
+    /// Get an iterator traversing all nodes in the tree level by
+    /// level, starting from the deepest level.
+    ///
+    /// See [Tree::breadth_first_rev_of] for the order of the nodes.
+    ///
+    /// *Returns:* An iterator returning the node references from the
+    ///            deepest level upward, ending with the root node.
+    ///            Returns error if no root node exist.
+    pub fn breadth_first_rev(&self) -> Result<BreadthFirstRevIterator> {
+        match self.root {
+            None => Err(not_found("Cannot iterate a tree without a root node.")),
+            Some(root_ref) => self.breadth_first_rev_of(root_ref),
+        }
+    }
+
+    /// Get an iterator traversing the node and all child nodes in
+    /// depth-first order, visiting children from right to left.
+    ///
+    /// This is not the reverse of [Tree::depth_first_of], as each node
+    /// is still yielded before its children. To iterate in reverse
+    /// document order, call `.rev()` on the iterator returned
+    /// by [Tree::depth_first_of] instead.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the starting node.
+    /// * `include_start` - If true, iteration starts with the
+    ///   starting node instead of with the last child thereof.
+    ///
+    /// *Returns:* An iterator returning the node references to the
+    ///            child nodes in right-to-left depth-first order.
+    ///            Returns error if the start node does not exist.
+    pub fn depth_first_rev_of(
+        &self,
+        node_ref: NodeRef,
+        include_start: bool,
+    ) -> Result<DepthFirstIterator<'_, T>> {
+        let mut iterator = DepthFirstIterator::new(self, node_ref, true)?;
+        if !include_start {
+            iterator.next();
+        }
+        Ok(iterator)
+    }
+
+    /// Get an iterator traversing all nodes in the tree in a
+    /// depth-first order.
+    ///
+    /// *Arguments:*
+    /// * `include_root` - If true, iteration starts with the
+    ///   root node instead of with the first
+    ///   thereof.
+    ///
+    /// *Returns:* An iterator returning the node references to the
+    ///            nodes in depth-first order. Returns error
+    ///            if no root node exist.
+    pub fn depth_first(&self, include_root: bool) -> Result<DepthFirstIterator<'_, T>> {
+        match self.root {
+            None => Err(not_found("Cannot iterate a tree without a root node.")),
+            Some(root_ref) => self.depth_first_of(root_ref, include_root),
+        }
+    }
+
+    /// Get an iterator traversing all nodes in the tree in a
+    /// depth-first order, which is empty if the tree has no root node.
+    ///
+    /// Unlike [Tree::depth_first], a tree without root is not an error,
+    /// which is convenient in generic code handling possibly empty trees.
+    ///
+    /// *Arguments:*
+    /// * `include_root` - If true, iteration starts with the
+    ///   root node instead of with the first
+    ///   thereof.
+    ///
+    /// *Returns:* An iterator returning the node references to the
+    ///            nodes in depth-first order.
+    pub fn depth_first_or_empty(&self, include_root: bool) -> DepthFirstIterator<'_, T> {
+        match self.root {
+            None => DepthFirstIterator::empty(self),
+            Some(root_ref) => self.depth_first_of(root_ref, include_root).unwrap(),
+        }
+    }
+
+    /// Get an iterator traversing all nodes in the tree in a
+    /// depth-first order, visiting children from right to left.
+    ///
+    /// *Arguments:*
+    /// * `include_root` - If true, iteration starts with the
+    ///   root node instead of with the last child thereof.
+    ///
+    /// *Returns:* An iterator returning the node references to the
+    ///            nodes in right-to-left depth-first order. Returns error
+    ///            if no root node exist.
+    pub fn depth_first_rev(&self, include_root: bool) -> Result<DepthFirstIterator<'_, T>> {
+        match self.root {
+            None => Err(not_found("Cannot iterate a tree without a root node.")),
+            Some(root_ref) => self.depth_first_rev_of(root_ref, include_root),
+        }
+    }
+
+    /// Traverse the tree in depth-first order in chunks of nodes.
+    ///
+    /// Each call to [DepthFirstChunks::next_chunk] returns a slice with the
+    /// next `chunk_size` nodes, or less for the last chunk. The slice
+    /// reuses the same buffer, which allows batch processing of huge trees
+    /// with fewer iterator calls and no per-chunk allocation.
+    ///
+    /// *Arguments:*
+    /// * `chunk_size` - Maximum number of nodes in each chunk.
+    ///
+    /// *Returns:* The chunked traversal, including the root node.
+    ///            Returns error if no root node exist or if `chunk_size` is zero.
+    pub fn chunked_depth_first(&self, chunk_size: usize) -> Result<DepthFirstChunks<'_, T>> {
+        if chunk_size == 0 {
+            return Err(invalid_input("Chunk size must be greater than zero."));
+        }
+        Ok(DepthFirstChunks {
+            iterator: self.depth_first(true)?,
+            buffer: Vec::with_capacity(chunk_size),
+            chunk_size,
+        })
+    }
+
+    /// Get the n-th node of a subtree in depth-first order.
+    ///
+    /// The starting node is at position 0. The traversal stops as soon
+    /// as the node is found and does not allocate the full node list.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the starting node.
+    /// * `n` - Position of the node in depth-first order.
+    ///
+    /// *Returns:* A reference to the n-th node or `None` if the subtree
+    ///            has fewer nodes. Returns error if the start node does not exist.
+    pub fn nth_in_depth_first(&self, node_ref: NodeRef, n: usize) -> Result<Option<NodeRef>> {
+        Ok(self.depth_first_of(node_ref, true)?.nth(n))
+    }
+
+    /// Number the nodes in pre-order.
+    ///
+    /// The root is numbered 0 and the other nodes reachable from it follow
+    /// in depth-first order. Unlike node references, these numbers only
+    /// depend on the shape of the tree, which makes them suitable to store
+    /// references between nodes in exported files. The numbering is not
+    /// kept up to date: call this again after modifying the tree.
+    ///
+    /// *Returns:* A map from node references to their pre-order number,
+    ///            which is empty if the tree has no root node.
+    pub fn assign_preorder_ids(&self) -> HashMap<NodeRef, usize> {
+        match self.depth_first(true) {
+            Err(_) => HashMap::new(),
+            Ok(nodes) => nodes
+                .enumerate()
+                .map(|(id, node_ref)| (node_ref, id))
+                .collect(),
+        }
+    }
+
+    /// Pick a node uniformly at random.
+    ///
+    /// Every live node in the tree can be picked, including the ones
+    /// not attached to the root. Requires the `rand` feature.
+    ///
+    /// *Arguments:*
+    /// * `rng` - The random number generator to use.
+    ///
+    /// *Returns:* A reference to the picked node or `None` if
+    ///            the tree is empty.
+    #[cfg(feature = "rand")]
+    pub fn random_node<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Option<NodeRef> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let n = rng.gen_range(0..self.len);
+        if self.len == self.nodes.len() {
+            return Some(NodeRef::new(n));
+        }
+
+        self.iter_all_nodes().nth(n)
+    }
+
+    /// Get an iterator over all live nodes of the tree.
+    ///
+    /// Unlike the traversals, this also returns nodes which are not
+    /// reachable from the root. Nodes are returned in the order of
+    /// the underlying vector.
+    ///
+    /// *Returns:* An iterator returning the references of all live nodes.
+    pub fn iter_all_nodes(&self) -> AllNodesIterator<'_, T> {
+        AllNodesIterator {
+            nodes: self.nodes.iter().enumerate(),
+        }
+    }
+
+    /// Find the live nodes which are not reachable from the root.
+    ///
+    /// These are nodes created but never attached, descendants of removed
+    /// nodes and, if the tree has no root, all nodes. Finding them helps
+    /// spotting leaks, since they are invisible to the traversals.
+    ///
+    /// *Returns:* The references of the unreachable nodes, in the order
+    ///            of the underlying vector.
+    pub fn unreachable_nodes(&self) -> Vec<NodeRef> {
+        let mut reachable = vec![false; self.nodes.len()];
+        if let Some(nodes) = self
+            .root
+            .and_then(|root_ref| self.depth_first_of(root_ref, true).ok())
+        {
+            for node_ref in nodes {
+                reachable[node_ref.index()] = true;
+            }
+        }
+
+        self.iter_all_nodes()
+            .filter(|node_ref| !reachable[node_ref.index()])
+            .collect()
+    }
+
+    /// Get an iterator traversing the node and all child nodes in
+    /// generalized in-order.
+    ///
+    /// Each node is yielded after the subtrees of its first `split_index`
+    /// children and before the remaining ones. With a `split_index` of 1
+    /// this is the classic in-order of binary trees, for example for
+    /// expression trees where the operator sits between the operands.
+    /// Nodes with fewer children than `split_index` are yielded after
+    /// all of their children.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the starting node.
+    /// * `split_index` - Number of children visited before the node itself.
+    ///
+    /// *Returns:* An iterator returning the node references in in-order.
+    ///            Returns error if the start node does not exist.
+    pub fn in_order_of(
+        &self,
+        node_ref: NodeRef,
+        split_index: usize,
+    ) -> Result<SplitInOrderIterator<'_, T>> {
+        if self.get_node(node_ref).is_none() {
+            return Err(node_not_found(node_ref));
+        }
+
+        Ok(SplitInOrderIterator {
+            tree: self,
+            split_index,
+            stack: vec![(node_ref, 0, false)],
+        })
+    }
+
+    /// Create a new tree with the same structure, mapping the
+    /// content of each node.
+    ///
+    /// The new tree keeps the same layout of the original one, including
+    /// removed nodes and nodes not attached to the root, so a [NodeRef]
+    /// obtained on the original tree references the corresponding node
+    /// in the new one.
+    ///
+    /// *Arguments:*
+    /// * `map_fn` - Function mapping the content of each node.
+    ///
+    /// *Returns:* The new tree. Returns error if no root node exist.
+    pub fn map<N>(&self, map_fn: impl Fn(&T) -> N) -> Result<Tree<N>> {
+        if self.root.is_none() {
+            return Err(not_found("Parent node does not exist."));
+        }
+
+        Ok(self.map_all(map_fn))
+    }
+
+    /// Create a new tree with the same structure, mapping the content
+    /// of each node, even if the tree has no root node.
+    ///
+    /// Like [Tree::map], node references of the original tree are valid
+    /// in the new one, which also has no root node if the original has
+    /// none.
+    ///
+    /// *Arguments:*
+    /// * `map_fn` - Function mapping the content of each node.
+    ///
+    /// *Returns:* The new tree.
+    pub fn map_all<N>(&self, map_fn: impl Fn(&T) -> N) -> Tree<N> {
+        let nodes = self
+            .nodes
+            .iter()
+            .map(|node| {
+                node.as_ref().map(|node| Node {
+                    content: map_fn(&node.content),
+                    parent: node.parent,
+                    children: node.children.clone(),
+                    siblings: node.siblings,
+                    locked: node.locked,
+                    children_unknown: node.children_unknown,
+                })
+            })
+            .collect();
+
+        Tree {
+            nodes,
+            root: self.root,
+            len: self.len,
+            unreachable: self.unreachable,
+            free: self.free.clone(),
+            storage: self.storage,
+            spare_children: Vec::new(),
+            marks: self.marks.clone(),
+            #[cfg(feature = "spans")]
+            spans: self.spans.clone(),
+            stable_ids: self.stable_ids.clone(),
+            registry: self.registry.clone(),
+        }
+    }
+
+    /// Convert the tree into a tree with the same structure, converting
+    /// the content of each node by value.
+    ///
+    /// Like [Tree::map], node references of the original tree are valid
+    /// in the new one, but the contents are moved into `map_fn` instead
+    /// of being borrowed, and the child lists are reused.
+    ///
+    /// *Arguments:*
+    /// * `map_fn` - Function converting the content of each node.
+    ///
+    /// *Returns:* The new tree.
+    pub fn into_map<N>(mut self, mut map_fn: impl FnMut(T) -> N) -> Tree<N> {
+        let nodes = mem::take(&mut self.nodes)
+            .into_iter()
+            .map(|node| {
+                node.map(|node| Node {
+                    content: map_fn(node.content),
+                    parent: node.parent,
+                    children: node.children,
+                    siblings: node.siblings,
+                    locked: node.locked,
+                    children_unknown: node.children_unknown,
+                })
+            })
+            .collect();
+
+        Tree {
+            nodes,
+            root: self.root,
+            len: mem::take(&mut self.len),
+            unreachable: mem::take(&mut self.unreachable),
+            free: mem::take(&mut self.free),
+            storage: self.storage,
+            spare_children: mem::take(&mut self.spare_children),
+            marks: mem::take(&mut self.marks),
+            #[cfg(feature = "spans")]
+            spans: mem::take(&mut self.spans),
+            stable_ids: mem::take(&mut self.stable_ids),
+            registry: mem::take(&mut self.registry),
+        }
+    }
+}
+
+impl<T: Clone> Tree<T> {
+    /// Copy the selected nodes and their ancestors into a new tree.
+    ///
+    /// This is the minimal tree containing the selection, useful to show
+    /// search results in context. Descendants of the selected nodes are
+    /// only included if they are selected as well. The order of the
+    /// children is preserved.
+    ///
+    /// *Arguments:*
+    /// * `selection` - Slice of [NodeRef] objects of the selected nodes.
+    ///
+    /// *Returns:* The new tree, which is empty if nothing is selected.
+    ///            Returns an error if a selected node does not exist or
+    ///            is not reachable from the root.
+    pub fn induced_subtree(&self, selection: &[NodeRef]) -> Result<Tree<T>> {
+        let mut keep = vec![false; self.nodes.len()];
+        for node_ref in selection {
+            for ancestor_ref in self.ancestors_or_self(*node_ref)? {
+                if keep[ancestor_ref.index()] {
+                    break;
+                }
+                keep[ancestor_ref.index()] = true;
+                let orphan = match self.get_node(ancestor_ref) {
+                    None => true,
+                    Some(node) => node.parent.is_none() && Some(ancestor_ref) != self.root,
+                };
+                if orphan {
+                    return Err(invalid_input("Node is not reachable from the root."));
+                }
+            }
+        }
+
+        let mut tree = Tree::new();
+        let mut mapping = vec![None; self.nodes.len()];
+        if selection.is_empty() {
+            return Ok(tree);
+        }
+
+        for old_ref in self.depth_first(true)? {
+            if !keep[old_ref.index()] {
+                continue;
+            }
+            let content = self.get(old_ref).unwrap().clone();
+            let new_ref = match self.get_node(old_ref).unwrap().parent {
+                None => tree.root(content).unwrap(),
+                Some(parent_ref) => tree
+                    .child_node(mapping[parent_ref.index()].unwrap(), content)
+                    .unwrap(),
+            };
+            mapping[old_ref.index()] = Some(new_ref);
+        }
+
+        Ok(tree)
+    }
+
+    /// Copy a subtree into a new tree, down to a maximum depth.
+    ///
+    /// Branches below `max_depth` are not traversed, so the cost depends
+    /// only on the size of the copied part. This is useful to send a
+    /// preview of a large tree.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the root of the subtree, at depth 0.
+    /// * `max_depth` - Depth of the deepest copied nodes.
+    /// * `placeholder` - If set, a copy of this value is added as only
+    ///   child of the copied nodes whose children were truncated.
+    ///
+    /// *Returns:* The new tree, with the copy of the node as root.
+    ///            Returns error if the node does not exist.
+    pub fn clone_to_depth(
+        &self,
+        node_ref: NodeRef,
+        max_depth: usize,
+        placeholder: Option<T>,
+    ) -> Result<Tree<T>> {
+        let content = match self.get(node_ref) {
+            None => return Err(node_not_found(node_ref)),
+            Some(content) => content.clone(),
+        };
+
+        let mut tree = Tree::new();
+        let root_ref = tree.root(content).unwrap();
+        let mut stack = vec![(node_ref, root_ref, 0)];
+        while let Some((old_ref, new_ref, depth)) = stack.pop() {
+            let mut children = self
+                .live_children(old_ref)
+                .map(|child_ref| (child_ref, self.get(child_ref).unwrap()))
+                .peekable();
+
+            if depth == max_depth {
+                if let (Some(placeholder), Some(_)) = (&placeholder, children.peek()) {
+                    tree.child_node(new_ref, placeholder.clone()).unwrap();
+                }
+                continue;
+            }
+
+            for (child_ref, content) in children {
+                let new_child_ref = tree.child_node(new_ref, content.clone()).unwrap();
+                stack.push((child_ref, new_child_ref, depth + 1));
+            }
+        }
+
+        Ok(tree)
+    }
+
+    /// Copy the tree without the slots of removed nodes.
+    ///
+    /// The live nodes, including the ones not reachable from the root,
+    /// are stored in depth-first order like with [Tree::optimize_layout],
+    /// so node references differ from the ones of the original tree.
+    ///
+    /// *Returns:* The copy and the mapping from the node references
+    ///            of the original tree to the ones of the copy.
+    pub fn clone_compacted(&self) -> (Tree<T>, NodeRemap) {
+        let mut copy = self.clone();
+        let remap = copy.optimize_layout();
+        (copy, remap)
+    }
+
+    /// Copy a node and all its descendants into a new tree.
+    pub(crate) fn clone_subtree(&self, node_ref: NodeRef) -> Result<Tree<T>> {
+        let mut tree = Tree::new();
+        let mut mapping = vec![None; self.nodes.len()];
+
+        for old_ref in self.depth_first_of(node_ref, true)? {
+            let content = self.get(old_ref).unwrap().clone();
+            let new_ref = if old_ref == node_ref {
+                tree.root(content).unwrap()
+            } else {
+                let parent_ref = self.get_node(old_ref).unwrap().parent.unwrap();
+                tree.child_node(mapping[parent_ref.index()].unwrap(), content)
+                    .unwrap()
+            };
+            mapping[old_ref.index()] = Some(new_ref);
+        }
+
+        Ok(tree)
+    }
+}
+
+impl<T: Copy> Tree<T> {
+    /// Copy the values of a subtree into a vector, in depth-first order.
+    ///
+    /// This is meant to feed numeric trees into array libraries or GPU
+    /// buffers, with the values laid out contiguously.
     ///
     /// *Arguments:*
-    /// * `node_ref` - [NodeRef] of the starting node.
-    /// * `include_start` - If true, iteration starts with the
-    ///                     starting node instead of with the first
-    ///                     thereof.
+    /// * `node_ref` - [NodeRef] of the root of the subtree.
     ///
-    /// *Returns:* An iterator returning the node references to the
-    ///            child nodes in depth-first order. Returns error
-    ///            if the start node does not exist.
-    pub fn depth_first_of(
-        &self,
-        node_ref: NodeRef,
-        include_start: bool,
-    ) -> Result<DepthFirstIterator<T>> {
-        let mut iterator = DepthFirstIterator::new(&self, node_ref)?;
-        if !include_start {
-            iterator.next();
-        }
-        Ok(iterator)
+    /// *Returns:* The values, starting with the one of the node.
+    ///            Returns error if the node does not exist.
+    pub fn values_to_vec_preorder(&self, node_ref: NodeRef) -> Result<Vec<T>> {
+        let values = self
+            .depth_first_of(node_ref, true)?
+            .map(|node_ref| self.nodes[node_ref.index()].as_ref().unwrap().content)
+            .collect();
+        Ok(values)
     }
 
-    /// Get an iterator traversing all nodes in the tree in a
-    /// depth-first order.
+    /// Copy the values of the tree into a buffer, in depth-first order.
+    ///
+    /// Only nodes reachable from the root are copied, so that the buffer
+    /// can be sized with [Tree::len] when there are no detached nodes.
     ///
     /// *Arguments:*
-    /// * `include_root` - If true, iteration starts with the
-    ///                    root node instead of with the first
-    ///                    thereof.
+    /// * `buf` - The buffer, whose start is overwritten.
     ///
-    /// *Returns:* An iterator returning the node references to the
-    ///            nodes in depth-first order. Returns error
-    ///            if no root node exist.
-    pub fn depth_first(&self, include_root: bool) -> Result<DepthFirstIterator<T>> {
-        match self.root {
-            None => Err(TreeError::new("Cannot iterate a tree without a root node.")),
-            Some(root_ref) => self.depth_first_of(root_ref, include_root),
-        }
-    }
+    /// *Returns:* The number of copied values. Returns an error if the
+    ///            buffer is too small, in which case its content is
+    ///            unspecified.
+    pub fn write_values_into(&self, buf: &mut [T]) -> Result<usize> {
+        let root_ref = match self.root {
+            None => return Ok(0),
+            Some(root_ref) => root_ref,
+        };
 
-    /// TODO: complete documentation
-    pub fn map<N>(&self, map_fn: impl Fn(&T) -> N) -> Result<Tree<N>> {
-        match self.root {
-            None => Err(TreeError::new("Parent node does not exist.")),
-            Some(root) => {
-                let mut tree = Tree::new();
-                self.map_intern(root, &mut tree, None, &map_fn);
-                Ok(tree)
+        let mut count = 0;
+        for node_ref in self.depth_first_of(root_ref, true)? {
+            match buf.get_mut(count) {
+                None => {
+                    return Err(TreeError::with_kind(
+                        ErrorKind::InvalidInput,
+                        "Buffer is too small for the values of the tree.",
+                    ))
+                }
+                Some(slot) => *slot = self.nodes[node_ref.index()].as_ref().unwrap().content,
             }
+            count += 1;
         }
+        Ok(count)
+    }
+}
+
+impl<T> Default for Tree<T> {
+    fn default() -> Self {
+        Self::new()
     }
+}
 
-    fn map_intern<N>(
-        &self,
-        orig_node: NodeRef,
-        tree: &mut Tree<N>,
-        new_parent: Option<NodeRef>,
-        map_fn: &impl Fn(&T) -> N,
-    ) {
-        let orig_content = self.get(orig_node).unwrap();
-        let new_content = map_fn(orig_content);
-
-        let new_node = match new_parent {
-            None => tree.root(new_content).unwrap(),
-            Some(parent) => tree.child_node(parent, new_content).unwrap(),
+/// Structural hash, consistent with [Tree::structural_eq].
+///
+/// Only the content and shape of the nodes reachable from the root
+/// are hashed: tombstones, detached nodes and slot numbering are ignored.
+impl<T: Hash> Hash for Tree<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let nodes = match self.depth_first(true) {
+            Ok(nodes) => nodes,
+            Err(_) => return state.write_usize(0),
         };
 
-        for orig_child in self.get_children(orig_node).unwrap() {
-            self.map_intern(*orig_child, tree, Some(new_node), map_fn);
+        for node_ref in nodes {
+            self.get(node_ref).unwrap().hash(state);
+            self.live_children(node_ref).count().hash(state);
+        }
+    }
+}
+
+#[cfg(feature = "debug")]
+impl<T> Drop for Tree<T> {
+    fn drop(&mut self) {
+        const MIN_SLOTS: usize = 64;
+
+        let tombstones = self.tombstones() - self.free.len();
+        if self.nodes.len() >= MIN_SLOTS && tombstones * 2 > self.nodes.len() {
+            eprintln!(
+                "lineartree: dropping tree with {} tombstones out of {} slots, \
+                 consider calling purge_tombstones_unchecked() after removals",
+                tombstones,
+                self.nodes.len(),
+            );
         }
     }
 }
 
 // Iterators
 // ==================================================================
-#[doc(hidden)]
+/// Depth-first traversal, created by [Tree::depth_first],
+/// [Tree::depth_first_of] and related methods.
+///
+/// The iterator is double ended: with [Iterator::rev] the nodes are
+/// returned in reverse depth-first order, each node after its
+/// descendants and the last child first. This is not post-order,
+/// which starts with the descendants of the first child.
 pub struct DepthFirstIterator<'a, T> {
     tree: &'a Tree<T>,
-    current: NodeRef,
-    child_iterator: Box<dyn Iterator<Item = &'a NodeRef> + 'a>,
-    current_iterator: Option<Box<dyn Iterator<Item = NodeRef> + 'a>>,
+    rev: bool,
+    front: Vec<NodeRef>,
+    back: Vec<(NodeRef, bool)>,
+    last_front: Option<NodeRef>,
+    last_back: Option<NodeRef>,
     finished: bool,
+    start: Option<NodeRef>,
+    returned: usize,
+    /// Size of the traversed subtree, computed on the first size hint.
+    subtree_len: Cell<Option<usize>>,
 }
 
 impl<'a, T> DepthFirstIterator<'a, T> {
-    fn new(tree: &'a Tree<T>, current: NodeRef) -> Result<Self> {
+    fn new(tree: &'a Tree<T>, start: NodeRef, rev: bool) -> Result<Self> {
+        if tree.get_node(start).is_none() {
+            return Err(not_found("Parent node does not exist."));
+        }
+
         Ok(Self {
             tree,
-            current,
-            child_iterator: Box::new(tree.get_children(current)?),
-            current_iterator: None,
+            rev,
+            front: vec![start],
+            back: vec![(start, false)],
+            last_front: None,
+            last_back: None,
             finished: false,
+            start: Some(start),
+            returned: 0,
+            subtree_len: Cell::new(None),
         })
     }
 
-    fn next_child(&mut self) -> bool {
-        match self.child_iterator.next() {
-            None => {
-                self.finished = true;
-                false
-            }
-            Some(next_child) => {
-                let child_iterator = self.tree.depth_first_of(*next_child, true).unwrap();
-                self.current_iterator = Some(Box::new(child_iterator));
-                true
-            }
+    fn empty(tree: &'a Tree<T>) -> Self {
+        Self {
+            tree,
+            rev: false,
+            front: Vec::new(),
+            back: Vec::new(),
+            last_front: None,
+            last_back: None,
+            finished: true,
+            start: None,
+            returned: 0,
+            subtree_len: Cell::new(Some(0)),
         }
     }
+
+    /// Live children of a node, in the order they are traversed.
+    fn children(&self, node_ref: NodeRef) -> impl DoubleEndedIterator<Item = NodeRef> + 'a {
+        let tree = self.tree;
+        let children = tree.children_of(node_ref);
+        let (forward, backward) = if self.rev {
+            (None, Some(children.rev()))
+        } else {
+            (Some(children), None)
+        };
+
+        forward
+            .into_iter()
+            .flatten()
+            .chain(backward.into_iter().flatten())
+            .filter(move |child_ref| {
+                tree.get_node(**child_ref)
+                    .is_some_and(|child| child.parent == Some(node_ref))
+            })
+            .copied()
+    }
 }
 
 impl<'a, T> Iterator for DepthFirstIterator<'a, T> {
@@ -504,25 +2994,489 @@ impl<'a, T> Iterator for DepthFirstIterator<'a, T> {
             return None;
         }
 
-        match &mut self.current_iterator {
+        let node_ref = self.front.pop()?;
+        if Some(node_ref) == self.last_back {
+            self.finished = true;
+            return None;
+        }
+
+        let children = self.children(node_ref);
+        self.front.extend(children.rev());
+        self.last_front = Some(node_ref);
+        self.returned += 1;
+        Some(node_ref)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.finished {
+            return (0, Some(0));
+        }
+        let subtree_len = match self.subtree_len.get() {
+            Some(subtree_len) => subtree_len,
+            None => {
+                let subtree_len = self.start.map_or(0, |start| self.tree.subtree_len(start));
+                self.subtree_len.set(Some(subtree_len));
+                subtree_len
+            }
+        };
+        let remaining = subtree_len - self.returned;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for DepthFirstIterator<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        loop {
+            let (node_ref, expanded) = self.back.pop()?;
+            if expanded {
+                if Some(node_ref) == self.last_front {
+                    self.finished = true;
+                    return None;
+                }
+                self.last_back = Some(node_ref);
+                self.returned += 1;
+                return Some(node_ref);
+            }
+
+            self.back.push((node_ref, true));
+            let children = self.children(node_ref);
+            self.back
+                .extend(children.into_iter().map(|child_ref| (child_ref, false)));
+        }
+    }
+}
+
+impl<'a, T> FusedIterator for DepthFirstIterator<'a, T> {}
+
+impl<'a, T> Clone for DepthFirstIterator<'a, T> {
+    fn clone(&self) -> Self {
+        Self {
+            tree: self.tree,
+            rev: self.rev,
+            front: self.front.clone(),
+            back: self.back.clone(),
+            last_front: self.last_front,
+            last_back: self.last_back,
+            finished: self.finished,
+            start: self.start,
+            returned: self.returned,
+            subtree_len: self.subtree_len.clone(),
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for DepthFirstIterator<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DepthFirstIterator")
+            .field("rev", &self.rev)
+            .field("front", &self.front)
+            .field("finished", &self.finished)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Depth-first traversal in chunks, created by [Tree::chunked_depth_first].
+pub struct DepthFirstChunks<'a, T> {
+    iterator: DepthFirstIterator<'a, T>,
+    buffer: Vec<NodeRef>,
+    chunk_size: usize,
+}
+
+impl<'a, T> DepthFirstChunks<'a, T> {
+    /// Get the next chunk of nodes in depth-first order.
+    ///
+    /// *Returns:* A slice with at most `chunk_size` node references,
+    ///            or `None` once all nodes were returned.
+    pub fn next_chunk(&mut self) -> Option<&[NodeRef]> {
+        self.buffer.clear();
+        self.buffer
+            .extend(self.iterator.by_ref().take(self.chunk_size));
+        if self.buffer.is_empty() {
+            None
+        } else {
+            Some(&self.buffer)
+        }
+    }
+}
+
+/// Depth-first traversal returning node contents, which can skip
+/// subtrees, created by [Tree::walk].
+pub struct Descendants<'a, T> {
+    tree: &'a Tree<T>,
+    stack: Vec<NodeRef>,
+    /// Last returned node, whose children are not on the stack yet.
+    last: Option<NodeRef>,
+    /// Number of nodes left, computed on the first size hint.
+    remaining: Cell<Option<usize>>,
+}
+
+impl<'a, T> Descendants<'a, T> {
+    /// Do not visit the descendants of the node returned last.
+    ///
+    /// Has no effect before the first node is returned
+    /// or if called more than once.
+    pub fn skip_subtree(&mut self) {
+        if self.last.take().is_some() {
+            self.remaining.set(None);
+        }
+    }
+}
+
+impl<'a, T> Iterator for Descendants<'a, T> {
+    type Item = (NodeRef, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let tree = self.tree;
+        if let Some(last_ref) = self.last.take() {
+            let start = self.stack.len();
+            self.stack.extend(tree.live_children(last_ref));
+            self.stack[start..].reverse();
+        }
+
+        let node_ref = self.stack.pop()?;
+        self.last = Some(node_ref);
+        if let Some(remaining) = self.remaining.get() {
+            self.remaining.set(Some(remaining - 1));
+        }
+        Some((node_ref, &tree.get_node(node_ref).unwrap().content))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = match self.remaining.get() {
+            Some(remaining) => remaining,
             None => {
-                self.next_child();
-                Some(self.current)
+                let tree = self.tree;
+                let pending = self
+                    .last
+                    .map_or(0, |last_ref| tree.subtree_len(last_ref) - 1);
+                let remaining = pending
+                    + self
+                        .stack
+                        .iter()
+                        .map(|node_ref| tree.subtree_len(*node_ref))
+                        .sum::<usize>();
+                self.remaining.set(Some(remaining));
+                remaining
+            }
+        };
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T> FusedIterator for Descendants<'a, T> {}
+
+impl<'a, T> Clone for Descendants<'a, T> {
+    fn clone(&self) -> Self {
+        Self {
+            tree: self.tree,
+            stack: self.stack.clone(),
+            last: self.last,
+            remaining: self.remaining.clone(),
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for Descendants<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Descendants")
+            .field("stack", &self.stack)
+            .field("last", &self.last)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Iterator over all the nodes in the tree, in allocation order,
+/// created by [Tree::iter_all_nodes].
+pub struct AllNodesIterator<'a, T> {
+    nodes: Enumerate<Iter<'a, Option<Node<T>>>>,
+}
+
+impl<'a, T> Iterator for AllNodesIterator<'a, T> {
+    type Item = NodeRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.nodes
+            .find(|(_, node)| node.is_some())
+            .map(|(id, _)| NodeRef::new(id))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.nodes.size_hint().1)
+    }
+}
+
+impl<'a, T> FusedIterator for AllNodesIterator<'a, T> {}
+
+impl<'a, T> Clone for AllNodesIterator<'a, T> {
+    fn clone(&self) -> Self {
+        Self {
+            nodes: self.nodes.clone(),
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for AllNodesIterator<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AllNodesIterator")
+            .field("remaining_slots", &self.nodes.len())
+            .finish()
+    }
+}
+
+/// Iterator over the ancestors of a node, from the parent up to the
+/// root, created by [Tree::ancestors] and [Tree::ancestors_or_self].
+pub struct AncestorsIterator<'a, T> {
+    tree: &'a Tree<T>,
+    next: Option<NodeRef>,
+}
+
+impl<'a, T> Iterator for AncestorsIterator<'a, T> {
+    type Item = NodeRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node_ref = self.next?;
+        self.next = self.tree.live_parent(node_ref);
+        Some(node_ref)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.next {
+            None => (0, Some(0)),
+            Some(_) => (1, Some(self.tree.len.max(1))),
+        }
+    }
+}
+
+impl<'a, T> FusedIterator for AncestorsIterator<'a, T> {}
+
+impl<'a, T> Clone for AncestorsIterator<'a, T> {
+    fn clone(&self) -> Self {
+        Self {
+            tree: self.tree,
+            next: self.next,
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for AncestorsIterator<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AncestorsIterator")
+            .field("next", &self.next)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Breadth-first traversal returning the level of each node,
+/// created by [Tree::breadth_first_with_level].
+pub struct BreadthFirstWithLevelIterator<'a, T> {
+    tree: &'a Tree<T>,
+    queue: VecDeque<(NodeRef, usize)>,
+    max_level: usize,
+}
+
+impl<'a, T> Iterator for BreadthFirstWithLevelIterator<'a, T> {
+    type Item = (NodeRef, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node_ref, level) = self.queue.pop_front()?;
+        if level < self.max_level {
+            self.queue.extend(
+                self.tree
+                    .live_children(node_ref)
+                    .map(|child_ref| (child_ref, level + 1)),
+            );
+        }
+        Some((node_ref, level))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let lower = self.queue.len();
+        if lower == 0 {
+            return (0, Some(0));
+        }
+        (lower, Some(self.tree.len.max(lower)))
+    }
+}
+
+impl<'a, T> FusedIterator for BreadthFirstWithLevelIterator<'a, T> {}
+
+impl<'a, T> Clone for BreadthFirstWithLevelIterator<'a, T> {
+    fn clone(&self) -> Self {
+        Self {
+            tree: self.tree,
+            queue: self.queue.clone(),
+            max_level: self.max_level,
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for BreadthFirstWithLevelIterator<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BreadthFirstWithLevelIterator")
+            .field("queue", &self.queue)
+            .field("max_level", &self.max_level)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Level by level traversal starting from the deepest level,
+/// created by [Tree::breadth_first_rev_of] and [Tree::breadth_first_rev].
+///
+/// The node references are collected when the iterator is created,
+/// so it does not borrow the tree and its length is known.
+#[derive(Debug, Clone)]
+pub struct BreadthFirstRevIterator {
+    order: Vec<NodeRef>,
+    /// Start of each level in `order`, down to the level being returned.
+    starts: Vec<usize>,
+    position: usize,
+    end: usize,
+}
+
+impl Iterator for BreadthFirstRevIterator {
+    type Item = NodeRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.position == self.end {
+            self.end = self.starts.pop()?;
+            self.position = self.starts.last().copied().unwrap_or(self.end);
+        }
+        self.position += 1;
+        Some(self.order[self.position - 1])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // All the levels above the current one are still to be returned.
+        let remaining = self.end - self.position + self.starts.last().copied().unwrap_or(0);
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for BreadthFirstRevIterator {}
+
+impl FusedIterator for BreadthFirstRevIterator {}
+
+/// Iterator over the nodes between two nodes in depth-first order,
+/// created by [Tree::range].
+pub struct RangeIterator<'a, T> {
+    tree: &'a Tree<T>,
+    next: Option<NodeRef>,
+    last: NodeRef,
+}
+
+impl<'a, T> Iterator for RangeIterator<'a, T> {
+    type Item = NodeRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node_ref = self.next?;
+        self.next = if node_ref == self.last {
+            None
+        } else {
+            self.tree.preorder_successor(node_ref)
+        };
+        Some(node_ref)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.next {
+            None => (0, Some(0)),
+            Some(_) => (1, Some(self.tree.len.max(1))),
+        }
+    }
+}
+
+impl<'a, T> FusedIterator for RangeIterator<'a, T> {}
+
+impl<'a, T> Clone for RangeIterator<'a, T> {
+    fn clone(&self) -> Self {
+        Self {
+            tree: self.tree,
+            next: self.next,
+            last: self.last,
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for RangeIterator<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RangeIterator")
+            .field("next", &self.next)
+            .field("last", &self.last)
+            .finish_non_exhaustive()
+    }
+}
+
+/// In-order traversal of a tree with any number of children,
+/// created by [Tree::in_order_of].
+pub struct SplitInOrderIterator<'a, T> {
+    tree: &'a Tree<T>,
+    split_index: usize,
+    stack: Vec<(NodeRef, usize, bool)>,
+}
+
+impl<'a, T> Iterator for SplitInOrderIterator<'a, T> {
+    type Item = NodeRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node_ref, next_child, yielded) = self.stack.last_mut()?;
+
+            if !*yielded
+                && *next_child
+                    >= self
+                        .split_index
+                        .min(self.tree.live_children(*node_ref).count())
+            {
+                *yielded = true;
+                return Some(*node_ref);
             }
-            Some(iterator) => match iterator.next() {
+
+            match self.tree.live_children(*node_ref).nth(*next_child) {
                 None => {
-                    if self.next_child() {
-                        self.next()
-                    } else {
-                        None
-                    }
+                    self.stack.pop();
                 }
-                Some(value) => Some(value),
-            },
+                Some(child_ref) => {
+                    *next_child += 1;
+                    self.stack.push((child_ref, 0, false));
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let lower = self.stack.iter().filter(|(_, _, yielded)| !yielded).count();
+        if self.stack.is_empty() {
+            return (0, Some(0));
+        }
+        (lower, Some(self.tree.len.max(lower)))
+    }
+}
+
+impl<'a, T> FusedIterator for SplitInOrderIterator<'a, T> {}
+
+impl<'a, T> Clone for SplitInOrderIterator<'a, T> {
+    fn clone(&self) -> Self {
+        Self {
+            tree: self.tree,
+            split_index: self.split_index,
+            stack: self.stack.clone(),
         }
     }
 }
 
+impl<'a, T> fmt::Debug for SplitInOrderIterator<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SplitInOrderIterator")
+            .field("split_index", &self.split_index)
+            .field("stack", &self.stack)
+            .finish_non_exhaustive()
+    }
+}
+
 // Tests
 // ==================================================================
 #[cfg(test)]
@@ -0,0 +1,40 @@
+/// Create a tree from a literal.
+///
+/// Each node is written as its content, optionally followed by `=>` and
+/// the list of its children in brackets. The content can be any
+/// expression, and all contents must have the same type.
+///
+/// ```ignore
+/// let tree = tree! {
+///     "/" => [
+///         "etc",
+///         "usr" => ["bin", "lib"],
+///     ]
+/// };
+/// ```
+#[macro_export]
+macro_rules! tree {
+    ($root:expr $(=> [$($children:tt)*])? $(,)?) => {{
+        let mut tree = $crate::Tree::new();
+        #[allow(unused_variables)]
+        let root = tree.root($root).unwrap();
+        $($crate::tree!(@children tree, root, $($children)*);)?
+        tree
+    }};
+    (@children $tree:ident, $parent:ident, ) => {};
+    (@children $tree:ident, $parent:ident,
+        $content:expr => [$($children:tt)*] $(, $($rest:tt)*)?) => {
+        let node = $tree.child_node($parent, $content).unwrap();
+        $crate::tree!(@children $tree, node, $($children)*);
+        $($crate::tree!(@children $tree, $parent, $($rest)*);)?
+    };
+    (@children $tree:ident, $parent:ident, $content:expr $(, $($rest:tt)*)?) => {
+        $tree.child_node($parent, $content).unwrap();
+        $($crate::tree!(@children $tree, $parent, $($rest)*);)?
+    };
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
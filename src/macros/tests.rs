@@ -0,0 +1,29 @@
+use crate::{assert_tree_eq, Tree};
+
+#[test]
+fn tree_macro() {
+    let tree = tree! {"/" => ["etc", "usr" => ["bin", "lib"],]};
+
+    let mut expected = Tree::new();
+    let root = expected.root("/").unwrap();
+    expected.child_node(root, "etc").unwrap();
+    let usr = expected.child_node(root, "usr").unwrap();
+    expected.child_node(usr, "bin").unwrap();
+    expected.child_node(usr, "lib").unwrap();
+
+    assert_tree_eq!(tree, expected);
+}
+
+#[test]
+fn tree_macro_expressions() {
+    let base = 10;
+    let tree = tree!(base => [base + 1 => [base + 2], base * 3]);
+    let values: Vec<i32> = tree
+        .depth_first(true)
+        .unwrap()
+        .map(|node_ref| *tree.get(node_ref).unwrap())
+        .collect();
+    assert_eq!(values, vec![10, 11, 12, 30]);
+
+    assert_eq!(tree!("leaf").len(), 1);
+}
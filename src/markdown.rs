@@ -0,0 +1,81 @@
+//! Building heading outlines from Markdown documents.
+//!
+//! This module is available with the `markdown` feature.
+
+use super::{NodeRef, Tree};
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+
+/// A Markdown heading.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Heading {
+    /// Level of the heading, from 1 for `#` to 6 for `######`.
+    /// The root node representing the whole document has level 0.
+    pub level: u8,
+    /// Plain text of the heading, without inline markup.
+    pub text: String,
+}
+
+impl Tree<Heading> {
+    /// Create a tree from the headings of a Markdown document.
+    ///
+    /// The root node represents the document and has level 0. Each heading
+    /// is a child of the closest preceding heading with a lower level,
+    /// so that the tree follows the section nesting even when levels are
+    /// skipped. Requires the `markdown` feature.
+    ///
+    /// *Arguments:*
+    /// * `text` - The Markdown document.
+    ///
+    /// *Returns:* A tree with one node per heading.
+    pub fn from_markdown(text: &str) -> Self {
+        let mut tree = Tree::new();
+        let root = tree
+            .root(Heading {
+                level: 0,
+                text: String::new(),
+            })
+            .unwrap();
+
+        let mut stack: Vec<(u8, NodeRef)> = vec![(0, root)];
+        let mut current: Option<Heading> = None;
+
+        for event in Parser::new(text) {
+            match event {
+                Event::Start(Tag::Heading { level, .. }) => {
+                    current = Some(Heading {
+                        level: level as u8,
+                        text: String::new(),
+                    });
+                }
+                Event::Text(text) | Event::Code(text) => {
+                    if let Some(heading) = current.as_mut() {
+                        heading.text.push_str(&text);
+                    }
+                }
+                Event::SoftBreak | Event::HardBreak => {
+                    if let Some(heading) = current.as_mut() {
+                        heading.text.push(' ');
+                    }
+                }
+                Event::End(TagEnd::Heading(_)) => {
+                    let heading = current.take().unwrap();
+                    while stack.last().unwrap().0 >= heading.level {
+                        stack.pop();
+                    }
+                    let level = heading.level;
+                    let parent_ref = stack.last().unwrap().1;
+                    let node_ref = tree.child_node(parent_ref, heading).unwrap();
+                    stack.push((level, node_ref));
+                }
+                _ => {}
+            }
+        }
+
+        tree
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
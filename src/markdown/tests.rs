@@ -0,0 +1,43 @@
+use super::*;
+
+#[test]
+fn from_markdown() {
+    let text = "\
+# Guide
+
+Intro text.
+
+## Install `lineartree`
+
+### From source
+
+#### Build
+
+## Usage
+Setext heading
+==============
+";
+    let tree = Tree::from_markdown(text);
+
+    let outline: Vec<(usize, u8, &str)> = tree
+        .depth_first(false)
+        .unwrap()
+        .map(|node_ref| {
+            let heading = tree.get(node_ref).unwrap();
+            let depth = tree.ancestors(node_ref).unwrap().count();
+            (depth, heading.level, heading.text.as_str())
+        })
+        .collect();
+
+    assert_eq!(
+        outline,
+        vec![
+            (1, 1, "Guide"),
+            (2, 2, "Install lineartree"),
+            (3, 3, "From source"),
+            (4, 4, "Build"),
+            (2, 2, "Usage"),
+            (1, 1, "Setext heading"),
+        ]
+    );
+}
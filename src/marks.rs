@@ -0,0 +1,83 @@
+use super::{invalid_input, node_not_found, NodeRef, Result, Tree};
+
+/// Number of mark bits available for each node.
+pub const MARK_BITS: u8 = 8;
+
+impl<T> Tree<T> {
+    /// Set a mark bit on a node.
+    ///
+    /// Marks are flags stored in a compact bitset next to the nodes,
+    /// for algorithms which need to flag nodes as visited or dirty
+    /// without changing the content type or allocating a set of
+    /// references. Marks of removed nodes are reset when their slot is
+    /// reused.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node to mark.
+    /// * `bit` - Index of the mark, lower than [MARK_BITS].
+    ///
+    /// *Returns:* Result indicating whether the operations was successful.
+    ///            Returns an error if the node does not exist or if the
+    ///            bit is out of range.
+    pub fn mark(&mut self, node_ref: NodeRef, bit: u8) -> Result<()> {
+        let mask = self.mark_mask(node_ref, bit)?;
+        if self.marks.len() <= node_ref.index() {
+            self.marks.resize(self.nodes.len(), 0);
+        }
+        self.marks[node_ref.index()] |= mask;
+        Ok(())
+    }
+
+    /// Clear a mark bit on a node.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node to unmark.
+    /// * `bit` - Index of the mark, lower than [MARK_BITS].
+    ///
+    /// *Returns:* Result indicating whether the operations was successful.
+    ///            Returns an error if the node does not exist or if the
+    ///            bit is out of range.
+    pub fn unmark(&mut self, node_ref: NodeRef, bit: u8) -> Result<()> {
+        let mask = self.mark_mask(node_ref, bit)?;
+        if let Some(marks) = self.marks.get_mut(node_ref.index()) {
+            *marks &= !mask;
+        }
+        Ok(())
+    }
+
+    /// Check whether a mark bit is set on a node.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node to check.
+    /// * `bit` - Index of the mark, lower than [MARK_BITS].
+    ///
+    /// *Returns:* Whether the mark is set. Returns an error if the node
+    ///            does not exist or if the bit is out of range.
+    pub fn is_marked(&self, node_ref: NodeRef, bit: u8) -> Result<bool> {
+        let mask = self.mark_mask(node_ref, bit)?;
+        Ok(self
+            .marks
+            .get(node_ref.index())
+            .is_some_and(|marks| marks & mask != 0))
+    }
+
+    /// Clear all marks of all nodes.
+    pub fn clear_marks(&mut self) {
+        self.marks.iter_mut().for_each(|marks| *marks = 0);
+    }
+
+    fn mark_mask(&self, node_ref: NodeRef, bit: u8) -> Result<u8> {
+        if self.get_node(node_ref).is_none() {
+            return Err(node_not_found(node_ref));
+        }
+        if bit >= MARK_BITS {
+            return Err(invalid_input("Mark bit out of range."));
+        }
+        Ok(1 << bit)
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,48 @@
+use super::*;
+use crate::{ErrorKind, TreeError};
+
+#[test]
+fn marks() {
+    let mut tree = Tree::new();
+    let root = tree.root("root").unwrap();
+    let child = tree.child_node(root, "child").unwrap();
+
+    tree.mark(child, 0).unwrap();
+    tree.mark(child, 7).unwrap();
+    assert_eq!(tree.is_marked(child, 0), Ok(true));
+    assert_eq!(tree.is_marked(child, 1), Ok(false));
+    assert_eq!(tree.is_marked(root, 0), Ok(false));
+
+    tree.unmark(child, 0).unwrap();
+    assert_eq!(tree.is_marked(child, 0), Ok(false));
+    assert_eq!(tree.is_marked(child, 7), Ok(true));
+
+    tree.clear_marks();
+    assert_eq!(tree.is_marked(child, 7), Ok(false));
+
+    assert_eq!(
+        tree.mark(child, MARK_BITS),
+        Err(TreeError::with_kind(
+            ErrorKind::InvalidInput,
+            "Mark bit out of range."
+        ))
+    );
+}
+
+#[test]
+fn marks_follow_nodes() {
+    let mut tree = Tree::new();
+    let root = tree.root("root").unwrap();
+    let removed = tree.child_node(root, "removed").unwrap();
+    let child = tree.child_node(root, "child").unwrap();
+    tree.mark(removed, 1).unwrap();
+    tree.mark(child, 2).unwrap();
+
+    tree.remove(removed).unwrap();
+    let remap = tree.optimize_layout();
+    assert_eq!(tree.is_marked(remap.get(child).unwrap(), 2), Ok(true));
+
+    tree.purge_tombstones_unchecked();
+    let reused = tree.node("new");
+    assert_eq!(tree.is_marked(reused, 1), Ok(false));
+}
@@ -0,0 +1,216 @@
+//! Primitives for Monte-Carlo Tree Search.
+//!
+//! This module is available with the `mcts` feature. The node content
+//! carries its statistics by implementing [MctsNode], which allows
+//! the tree to select children and backpropagate rewards to the root.
+//!
+//! ```ignore
+//! use lineartree::mcts::{MctsNode, NodeStats};
+//!
+//! let leaf = tree.select_leaf_ucb1(root, 2f64.sqrt())?;
+//! let reward = simulate(tree.get(leaf).unwrap());
+//! tree.backpropagate(leaf, reward)?;
+//! ```
+
+use super::{not_found, NodeRef, Result, Tree};
+
+/// Visit statistics of a node in the search tree.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct NodeStats {
+    /// Number of times the node was visited.
+    pub visits: u64,
+    /// Sum of all rewards propagated through the node.
+    pub total_reward: f64,
+}
+
+impl NodeStats {
+    /// Record a visit with the given reward.
+    pub fn record(&mut self, reward: f64) {
+        self.visits += 1;
+        self.total_reward += reward;
+    }
+
+    /// Get the average reward, or `None` if the node was never visited.
+    pub fn mean_reward(&self) -> Option<f64> {
+        if self.visits == 0 {
+            None
+        } else {
+            Some(self.total_reward / self.visits as f64)
+        }
+    }
+}
+
+/// Node content which carries search statistics.
+pub trait MctsNode {
+    /// Get the statistics of the node.
+    fn stats(&self) -> &NodeStats;
+
+    /// Get mutable statistics of the node.
+    fn stats_mut(&mut self) -> &mut NodeStats;
+}
+
+impl MctsNode for NodeStats {
+    fn stats(&self) -> &NodeStats {
+        self
+    }
+
+    fn stats_mut(&mut self) -> &mut NodeStats {
+        self
+    }
+}
+
+impl<T> Tree<T> {
+    /// Select the child with the highest score.
+    ///
+    /// Children with a score that cannot be compared, like `NaN`, are
+    /// never selected. On ties the first child wins.
+    ///
+    /// *Arguments:*
+    /// * `parent_ref` - [NodeRef] of the parent node.
+    /// * `score_fn` - Function computing the score of a child from its content.
+    ///
+    /// *Returns:* A reference to the best child or `None` if the node has
+    ///            no children. Returns error if the parent does not exist.
+    pub fn select_child_by<S: PartialOrd>(
+        &self,
+        parent_ref: NodeRef,
+        mut score_fn: impl FnMut(&T) -> S,
+    ) -> Result<Option<NodeRef>> {
+        let mut best: Option<(NodeRef, S)> = None;
+
+        for child_ref in self.get_children(parent_ref)? {
+            let child = match self.get(child_ref) {
+                None => continue,
+                Some(child) => child,
+            };
+
+            let score = score_fn(child);
+            let is_better = match &best {
+                None => score.partial_cmp(&score).is_some(),
+                Some((_, best_score)) => score > *best_score,
+            };
+            if is_better {
+                best = Some((child_ref, score));
+            }
+        }
+
+        Ok(best.map(|(child_ref, _)| child_ref))
+    }
+}
+
+impl<T: MctsNode> Tree<T> {
+    /// Get the number of visits of a node.
+    ///
+    /// *Returns:* The visit count or `None` if the `node_ref` is invalid.
+    pub fn visits(&self, node_ref: NodeRef) -> Option<u64> {
+        self.get(node_ref).map(|content| content.stats().visits)
+    }
+
+    /// Select the child maximizing the UCB1 score.
+    ///
+    /// Unvisited children are always selected first.
+    ///
+    /// *Arguments:*
+    /// * `parent_ref` - [NodeRef] of the parent node.
+    /// * `exploration` - Exploration constant, `sqrt(2)` is a common choice.
+    ///
+    /// *Returns:* A reference to the selected child or `None` if the node has
+    ///            no children. Returns error if the parent does not exist.
+    pub fn select_child_ucb1(
+        &self,
+        parent_ref: NodeRef,
+        exploration: f64,
+    ) -> Result<Option<NodeRef>> {
+        let parent_visits = match self.get(parent_ref) {
+            None => return Err(not_found("Parent node does not exist.").involving(&[parent_ref])),
+            Some(parent) => parent.stats().visits.max(1) as f64,
+        };
+
+        self.select_child_by(parent_ref, |child| match child.stats().mean_reward() {
+            None => f64::INFINITY,
+            Some(mean) => {
+                let visits = child.stats().visits as f64;
+                mean + exploration * (parent_visits.ln() / visits).sqrt()
+            }
+        })
+    }
+
+    /// Descend from a node selecting children by UCB1 until a leaf is reached.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the starting node, usually the root.
+    /// * `exploration` - Exploration constant passed to [Tree::select_child_ucb1].
+    ///
+    /// *Returns:* A reference to the selected leaf. Returns error
+    ///            if the start node does not exist.
+    pub fn select_leaf_ucb1(&self, node_ref: NodeRef, exploration: f64) -> Result<NodeRef> {
+        let mut current = node_ref;
+        while let Some(child_ref) = self.select_child_ucb1(current, exploration)? {
+            current = child_ref;
+        }
+        Ok(current)
+    }
+
+    /// Get the child with the most visits, the usual final move choice.
+    ///
+    /// *Arguments:*
+    /// * `parent_ref` - [NodeRef] of the parent node.
+    ///
+    /// *Returns:* A reference to the most visited child or `None` if the node
+    ///            has no children. Returns error if the parent does not exist.
+    pub fn most_visited_child(&self, parent_ref: NodeRef) -> Result<Option<NodeRef>> {
+        self.select_child_by(parent_ref, |child| child.stats().visits)
+    }
+
+    /// Record a reward on a node and all of its ancestors.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node where the simulation started.
+    /// * `reward` - The reward to record.
+    ///
+    /// *Returns:* Result indicating whether the operations was successful.
+    ///            Returns an error if the node does not exist.
+    pub fn backpropagate(&mut self, node_ref: NodeRef, reward: f64) -> Result<()> {
+        self.backpropagate_with(node_ref, |_| reward)
+    }
+
+    /// Record a reward computed per node on a node and all of its ancestors.
+    ///
+    /// This is useful for two-player games, where the reward has to be
+    /// seen from the perspective of the player moving at each node.
+    /// If an ancestor was removed, the propagation stops below it.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node where the simulation started.
+    /// * `reward_fn` - Function computing the reward from the distance
+    ///   to the starting node, which is 0 for the starting node itself.
+    ///
+    /// *Returns:* Result indicating whether the operations was successful.
+    ///            Returns an error if the node does not exist.
+    pub fn backpropagate_with(
+        &mut self,
+        node_ref: NodeRef,
+        mut reward_fn: impl FnMut(usize) -> f64,
+    ) -> Result<()> {
+        let path: Vec<NodeRef> = self.ancestors(node_ref)?.collect();
+
+        self.get_mut(node_ref)
+            .unwrap()
+            .stats_mut()
+            .record(reward_fn(0));
+        for (distance, ancestor_ref) in path.into_iter().enumerate() {
+            let reward = reward_fn(distance + 1);
+            self.get_mut(ancestor_ref)
+                .unwrap()
+                .stats_mut()
+                .record(reward);
+        }
+
+        Ok(())
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,59 @@
+use super::*;
+
+fn search_tree() -> (Tree<NodeStats>, NodeRef, NodeRef, NodeRef) {
+    let mut tree = Tree::new();
+    let root = tree.root(NodeStats::default()).unwrap();
+    let move_a = tree.child_node(root, NodeStats::default()).unwrap();
+    let move_b = tree.child_node(root, NodeStats::default()).unwrap();
+    (tree, root, move_a, move_b)
+}
+
+#[test]
+fn backpropagate() {
+    let (mut tree, root, move_a, move_b) = search_tree();
+    let leaf = tree.child_node(move_a, NodeStats::default()).unwrap();
+
+    tree.backpropagate(leaf, 1.0).unwrap();
+    tree.backpropagate(move_b, 0.0).unwrap();
+
+    assert_eq!(tree.visits(root), Some(2));
+    assert_eq!(tree.visits(move_a), Some(1));
+    assert_eq!(tree.get(root).unwrap().mean_reward(), Some(0.5));
+    assert_eq!(tree.get(leaf).unwrap().total_reward, 1.0);
+
+    tree.backpropagate_with(leaf, |distance| if distance % 2 == 0 { 1.0 } else { -1.0 })
+        .unwrap();
+    assert_eq!(tree.get(move_a).unwrap().total_reward, 0.0);
+    assert_eq!(tree.get(root).unwrap().total_reward, 2.0);
+}
+
+#[test]
+fn backpropagate_removed_ancestor() {
+    let (mut tree, root, move_a, _) = search_tree();
+    let leaf = tree.child_node(move_a, NodeStats::default()).unwrap();
+    tree.remove(move_a).unwrap();
+
+    tree.backpropagate(leaf, 1.0).unwrap();
+    assert_eq!(tree.visits(leaf), Some(1));
+    assert_eq!(tree.visits(root), Some(0));
+}
+
+#[test]
+fn select_child() {
+    let (mut tree, root, move_a, move_b) = search_tree();
+
+    assert_eq!(tree.select_child_ucb1(root, 1.0), Ok(Some(move_a)));
+    tree.backpropagate(move_a, 0.0).unwrap();
+    assert_eq!(tree.select_child_ucb1(root, 1.0), Ok(Some(move_b)));
+    tree.backpropagate(move_b, 1.0).unwrap();
+    tree.backpropagate(move_b, 1.0).unwrap();
+
+    assert_eq!(tree.select_child_ucb1(root, 0.0), Ok(Some(move_b)));
+    assert_eq!(tree.most_visited_child(root), Ok(Some(move_b)));
+    assert_eq!(tree.select_leaf_ucb1(root, 0.0), Ok(move_b));
+    assert_eq!(
+        tree.select_child_by(root, |stats| -stats.total_reward),
+        Ok(Some(move_a))
+    );
+    assert_eq!(tree.select_child_by(move_a, |stats| stats.visits), Ok(None));
+}
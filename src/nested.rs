@@ -0,0 +1,93 @@
+use super::{not_found, NodeRef, Result, Tree};
+
+/// Conversion between recursive structs and trees.
+///
+/// Implement this trait, or derive it with the `derive` feature, for
+/// naturally nested data like `struct Dir { name: String, children: Vec<Dir> }`.
+/// Each node of the resulting tree contains the struct itself, with the
+/// children moved out of it and into the tree structure.
+///
+/// ```ignore
+/// use lineartree::IntoTree;
+///
+/// #[derive(IntoTree)]
+/// struct Dir {
+///     name: String,
+///     children: Vec<Dir>,
+/// }
+///
+/// let tree = dir.into_tree();
+/// let dir = Dir::from_tree(tree)?;
+/// ```
+pub trait IntoTree: Sized {
+    /// Move the children out of the struct, leaving it without children.
+    fn take_children(&mut self) -> Vec<Self>;
+
+    /// Replace the children of the struct.
+    fn set_children(&mut self, children: Vec<Self>);
+
+    /// Convert the struct into a tree, with the struct itself as root node.
+    ///
+    /// *Returns:* The new tree.
+    fn into_tree(self) -> Tree<Self> {
+        let mut tree = Tree::new();
+        let mut stack: Vec<(Self, Option<NodeRef>)> = vec![(self, None)];
+
+        while let Some((mut item, parent_ref)) = stack.pop() {
+            let children = item.take_children();
+            let node_ref = match parent_ref {
+                None => tree.root(item).unwrap(),
+                Some(parent_ref) => tree.child_node(parent_ref, item).unwrap(),
+            };
+            stack.extend(
+                children
+                    .into_iter()
+                    .rev()
+                    .map(|child| (child, Some(node_ref))),
+            );
+        }
+
+        tree
+    }
+
+    /// Convert a tree back into a nested struct.
+    ///
+    /// Nodes not reachable from the root are dropped.
+    ///
+    /// *Arguments:*
+    /// * `tree` - The tree to convert.
+    ///
+    /// *Returns:* The struct corresponding to the root node.
+    ///            Returns error if the tree has no root node.
+    fn from_tree(mut tree: Tree<Self>) -> Result<Self> {
+        let root_ref = match tree.root {
+            None => return Err(not_found("Cannot convert a tree without a root node.")),
+            Some(root_ref) => root_ref,
+        };
+
+        let order: Vec<NodeRef> = tree.depth_first(true)?.collect();
+        let mut built: Vec<Option<Self>> = (0..tree.nodes.len()).map(|_| None).collect();
+        let child_lists: Vec<Vec<NodeRef>> = order
+            .iter()
+            .map(|node_ref| tree.children_of(*node_ref).copied().collect())
+            .collect();
+
+        for (node_ref, child_list) in order.into_iter().zip(child_lists).rev() {
+            let node = tree.nodes[node_ref.index()].take().unwrap();
+            let mut item = node.content;
+            let children = child_list
+                .iter()
+                .filter_map(|child_ref| built[child_ref.index()].take())
+                .collect();
+            item.set_children(children);
+            built[node_ref.index()] = Some(item);
+        }
+
+        Ok(built[root_ref.index()].take().unwrap())
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
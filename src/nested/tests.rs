@@ -0,0 +1,85 @@
+use super::*;
+use crate::{ErrorKind, TreeError};
+
+#[derive(Debug, Clone, PartialEq)]
+struct Dir {
+    name: &'static str,
+    children: Vec<Dir>,
+}
+
+impl IntoTree for Dir {
+    fn take_children(&mut self) -> Vec<Self> {
+        std::mem::take(&mut self.children)
+    }
+
+    fn set_children(&mut self, children: Vec<Self>) {
+        self.children = children;
+    }
+}
+
+fn dir(name: &'static str, children: Vec<Dir>) -> Dir {
+    Dir { name, children }
+}
+
+fn fs() -> Dir {
+    dir(
+        "/",
+        vec![
+            dir("etc", vec![]),
+            dir("usr", vec![dir("bin", vec![]), dir("lib", vec![])]),
+        ],
+    )
+}
+
+#[test]
+fn into_tree() {
+    let tree = fs().into_tree();
+
+    let names: Vec<&str> = tree
+        .depth_first(true)
+        .unwrap()
+        .map(|node_ref| tree.get(node_ref).unwrap().name)
+        .collect();
+
+    assert_eq!(names, vec!["/", "etc", "usr", "bin", "lib"]);
+    assert!(tree.depth_first(true).unwrap().all(|node_ref| tree
+        .get(node_ref)
+        .unwrap()
+        .children
+        .is_empty()));
+}
+
+#[test]
+fn from_tree() {
+    assert_eq!(Dir::from_tree(fs().into_tree()), Ok(fs()));
+    assert_eq!(
+        Dir::from_tree(Tree::new()),
+        Err(TreeError::with_kind(
+            ErrorKind::NotFound,
+            "Cannot convert a tree without a root node."
+        ))
+    );
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn derive() {
+    #[derive(Debug, PartialEq, crate::IntoTree)]
+    struct Section<T> {
+        title: T,
+        #[tree(children)]
+        subsections: Vec<Section<T>>,
+    }
+
+    let section = Section {
+        title: 1,
+        subsections: vec![Section {
+            title: 2,
+            subsections: vec![],
+        }],
+    };
+
+    let tree = section.into_tree();
+    assert_eq!(tree.len(), 2);
+    assert_eq!(Section::from_tree(tree).unwrap().subsections[0].title, 2);
+}
@@ -0,0 +1,177 @@
+//! Conversion between trees and Newick strings.
+//!
+//! The Newick format writes a tree as nested parentheses, where the
+//! children of a node are listed between parentheses before its label,
+//! like `(A,B,(C,D)E)F;`. It is the usual exchange format of
+//! phylogenetic trees. Branch lengths, like in `A:0.1`, are not
+//! interpreted and are kept as part of the labels.
+
+use super::{not_found, ErrorKind, NodeRef, Result, Tree, TreeError};
+
+/// Characters which cannot appear in an unquoted label.
+const SPECIAL: &[char] = &['(', ')', ',', ';', '\''];
+
+impl Tree<String> {
+    /// Create a tree from a Newick string.
+    ///
+    /// Labels can be quoted with single quotes, in which case two single
+    /// quotes stand for one. Whitespace around unquoted labels is removed.
+    ///
+    /// *Arguments:*
+    /// * `text` - The Newick string, terminated by `;`.
+    ///
+    /// *Returns:* The tree, with the outermost node as root node.
+    ///            Returns an error if the string is not valid Newick.
+    pub fn from_newick(text: &str) -> Result<Self> {
+        let mut tree = Tree::new();
+        let mut parser = Parser { text, position: 0 };
+        let mut groups: Vec<Vec<NodeRef>> = Vec::new();
+
+        loop {
+            while parser.skip_whitespace() == Some('(') {
+                parser.position += 1;
+                groups.push(Vec::new());
+            }
+            let mut node_ref = tree.node(parser.label()?);
+
+            loop {
+                match (parser.skip_whitespace(), groups.last_mut()) {
+                    (Some(','), Some(group)) => {
+                        parser.position += 1;
+                        group.push(node_ref);
+                        break;
+                    }
+                    (Some(')'), Some(_)) => {
+                        parser.position += 1;
+                        let mut children = groups.pop().unwrap();
+                        children.push(node_ref);
+                        node_ref = tree.node(parser.label()?);
+                        tree.append_children(node_ref, &children)?;
+                    }
+                    (Some(';'), None) => {
+                        parser.position += 1;
+                        if parser.skip_whitespace().is_some() {
+                            return Err(parser.error());
+                        }
+                        tree.set_root(node_ref, false)?;
+                        return Ok(tree);
+                    }
+                    _ => return Err(parser.error()),
+                }
+            }
+        }
+    }
+}
+
+impl<T: AsRef<str>> Tree<T> {
+    /// Render the tree as a Newick string.
+    ///
+    /// Labels containing characters with a special meaning in Newick
+    /// are quoted. This is the inverse of [Tree::from_newick].
+    ///
+    /// *Returns:* The Newick string, terminated by `;`.
+    ///            Returns error if no root node exist.
+    pub fn to_newick(&self) -> Result<String> {
+        let root_ref = match self.root {
+            None => return Err(not_found("Cannot convert a tree without a root node.")),
+            Some(root_ref) => root_ref,
+        };
+
+        let mut out = String::new();
+        let mut stack = vec![Step::Enter(root_ref)];
+        while let Some(step) = stack.pop() {
+            match step {
+                Step::Enter(node_ref) => {
+                    let children: Vec<NodeRef> = self.live_children(node_ref).collect();
+                    stack.push(Step::Label(node_ref));
+                    if children.is_empty() {
+                        continue;
+                    }
+
+                    out.push('(');
+                    stack.push(Step::Text(")"));
+                    for (index, child_ref) in children.into_iter().enumerate().rev() {
+                        stack.push(Step::Enter(child_ref));
+                        if index > 0 {
+                            stack.push(Step::Text(","));
+                        }
+                    }
+                }
+                Step::Label(node_ref) => push_label(&mut out, self.get(node_ref).unwrap().as_ref()),
+                Step::Text(text) => out.push_str(text),
+            }
+        }
+
+        out.push(';');
+        Ok(out)
+    }
+}
+
+enum Step {
+    Enter(NodeRef),
+    Label(NodeRef),
+    Text(&'static str),
+}
+
+fn push_label(out: &mut String, label: &str) {
+    if !label.contains(SPECIAL) && label.trim() == label {
+        out.push_str(label);
+        return;
+    }
+
+    out.push('\'');
+    out.push_str(&label.replace('\'', "''"));
+    out.push('\'');
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    /// Skip whitespace and peek at the next character.
+    fn skip_whitespace(&mut self) -> Option<char> {
+        let rest = &self.text[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+        self.text[self.position..].chars().next()
+    }
+
+    fn label(&mut self) -> Result<String> {
+        if self.skip_whitespace() != Some('\'') {
+            let rest = &self.text[self.position..];
+            let end = rest.find(SPECIAL).unwrap_or(rest.len());
+            self.position += end;
+            return Ok(rest[..end].trim().to_string());
+        }
+
+        let mut label = String::new();
+        self.position += 1;
+        loop {
+            let rest = &self.text[self.position..];
+            let end = match rest.find('\'') {
+                None => return Err(self.error()),
+                Some(end) => end,
+            };
+            label.push_str(&rest[..end]);
+            self.position += end + 1;
+            if !self.text[self.position..].starts_with('\'') {
+                return Ok(label);
+            }
+            label.push('\'');
+            self.position += 1;
+        }
+    }
+
+    fn error(&self) -> TreeError {
+        TreeError::with_kind(
+            ErrorKind::InvalidInput,
+            &format!("Invalid Newick string at byte {}.", self.position),
+        )
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,35 @@
+use super::*;
+
+fn labels(tree: &Tree<String>) -> Vec<&str> {
+    tree.depth_first(true)
+        .unwrap()
+        .map(|node_ref| tree.get(node_ref).unwrap().as_str())
+        .collect()
+}
+
+#[test]
+fn from_newick() {
+    let tree = Tree::from_newick("(A, B:0.2, (C,D)E) F;").unwrap();
+    assert_eq!(labels(&tree), vec!["F", "A", "B:0.2", "E", "C", "D"]);
+
+    let tree = Tree::from_newick("('it''s',(,));").unwrap();
+    assert_eq!(labels(&tree), vec!["", "it's", "", "", ""]);
+
+    for invalid in &["(A,B)", "(A,B));", "A,B;", "('A);", "A; B", ""] {
+        let error = Tree::from_newick(invalid).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    }
+}
+
+#[test]
+fn to_newick() {
+    let text = "(A,B:0.2,(C,'D, d')E)F;";
+    let tree = Tree::from_newick(text).unwrap();
+    assert_eq!(tree.to_newick().unwrap(), text);
+
+    let mut tree = Tree::new();
+    let root = tree.root("it's").unwrap();
+    tree.child_node(root, " x").unwrap();
+    assert_eq!(tree.to_newick().unwrap(), "(' x')'it''s';");
+    assert!(Tree::<&str>::new().to_newick().is_err());
+}
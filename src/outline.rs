@@ -0,0 +1,110 @@
+//! Conversion between trees and indented outline text.
+//!
+//! Each non-blank line of the text is a node, and its indentation level
+//! determines its parent: a line indented one level deeper than the
+//! previous one is a child of it. Since outlines may have more than one
+//! top-level line, the root node of the tree has an empty content and
+//! top-level lines become its children.
+
+use super::{ErrorKind, NodeRef, Result, Tree, TreeError};
+
+/// Indentation unit of outline text.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Indent {
+    /// One tab per level.
+    Tab,
+    /// The given number of spaces per level.
+    Spaces(usize),
+    /// Use the indentation of the first indented line.
+    Detect,
+}
+
+impl Tree<String> {
+    /// Create a tree from indented outline text.
+    ///
+    /// Blank lines are ignored and trailing whitespace is removed
+    /// from the content of the nodes.
+    ///
+    /// *Arguments:*
+    /// * `text` - The outline text.
+    /// * `indent` - The indentation unit used by the text.
+    ///
+    /// *Returns:* A tree with an empty root node and one node per line.
+    ///            Returns an error of kind [ErrorKind::InvalidInput] if
+    ///            a line is not indented by a whole number of units, or
+    ///            by more than one level deeper than the previous line.
+    pub fn from_indented_text(text: &str, mut indent: Indent) -> Result<Self> {
+        let mut tree = Tree::new();
+        let mut stack: Vec<NodeRef> = vec![tree.root(String::new()).unwrap()];
+
+        for (index, line) in text.lines().enumerate() {
+            let content = line.trim();
+            if content.is_empty() {
+                continue;
+            }
+
+            let leading = &line[..line.len() - line.trim_start().len()];
+            if indent == Indent::Detect && !leading.is_empty() {
+                indent = match leading.strip_prefix('\t') {
+                    Some(_) => Indent::Tab,
+                    None => Indent::Spaces(leading.len() - leading.trim_start_matches(' ').len()),
+                };
+            }
+
+            let invalid = || {
+                TreeError::with_kind(
+                    ErrorKind::InvalidInput,
+                    &format!("Invalid indentation on line {}.", index + 1),
+                )
+            };
+            let level = match indent {
+                _ if leading.is_empty() => 0,
+                Indent::Tab if leading.chars().all(|c| c == '\t') => leading.len(),
+                Indent::Spaces(n) if n > 0 && leading.chars().all(|c| c == ' ') => {
+                    if leading.len() % n != 0 {
+                        return Err(invalid());
+                    }
+                    leading.len() / n
+                }
+                _ => return Err(invalid()),
+            };
+            if level >= stack.len() {
+                return Err(invalid());
+            }
+
+            stack.truncate(level + 1);
+            let node_ref = tree.child_node(stack[level], content.to_string()).unwrap();
+            stack.push(node_ref);
+        }
+
+        Ok(tree)
+    }
+
+    /// Render the tree as tab-indented outline text.
+    ///
+    /// The root node is not rendered, its children are the top-level lines.
+    /// This is the inverse of [Tree::from_indented_text].
+    ///
+    /// *Returns:* The outline text, with one line per node.
+    pub fn to_indented_text(&self) -> String {
+        let mut out = String::new();
+        let nodes = match self.depth_first(false) {
+            Ok(nodes) => nodes,
+            Err(_) => return out,
+        };
+
+        for node_ref in nodes {
+            let level = self.ancestors(node_ref).unwrap().count() - 1;
+            out.push_str(&"\t".repeat(level));
+            out.push_str(self.get(node_ref).unwrap());
+            out.push('\n');
+        }
+
+        out
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,30 @@
+use super::*;
+
+#[test]
+fn from_indented_text() {
+    let text = "groceries\n  fruit\n    apples\n\n    pears\n  bread\nchores\n";
+    let tree = Tree::from_indented_text(text, Indent::Detect).unwrap();
+
+    let root = tree.get_root_ref().unwrap();
+    assert_eq!(tree.get(root), Some(&String::new()));
+    assert_eq!(tree.get_children(root).unwrap().len(), 2);
+    assert_eq!(
+        tree.to_indented_text(),
+        "groceries\n\tfruit\n\t\tapples\n\t\tpears\n\tbread\nchores\n"
+    );
+
+    let tabs = Tree::from_indented_text(&tree.to_indented_text(), Indent::Tab).unwrap();
+    assert!(tabs.structural_eq(&tree));
+}
+
+#[test]
+fn invalid_indentation() {
+    let error = Tree::from_indented_text("a\n     b\n", Indent::Spaces(2)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    assert_eq!(error.to_string(), "Invalid indentation on line 2.");
+    assert_eq!(
+        Tree::from_indented_text("a\n\t\tb\n", Indent::Tab).unwrap_err(),
+        TreeError::with_kind(ErrorKind::InvalidInput, "Invalid indentation on line 2.")
+    );
+    assert!(Tree::from_indented_text("a\n\tb\n  c\n", Indent::Detect).is_err());
+}
@@ -0,0 +1,118 @@
+use super::{ErrorKind, NodeRef, Result, Tree, TreeError};
+
+impl<T> Tree<T> {
+    /// Build a tree from separate arrays of contents and links,
+    /// validating them first.
+    ///
+    /// Node `i` has content `values[i]`, parent `parents[i]` and children
+    /// `children[i]`, all given as indices. The arrays typically come
+    /// from a file or another untrusted source, so they are checked to
+    /// have the same length, to contain only indices in range, to agree
+    /// with each other, to have a single root and to have no cycles.
+    ///
+    /// *Arguments:*
+    /// * `values` - Content of each node.
+    /// * `parents` - Index of the parent of each node, `None` for the root.
+    /// * `children` - Indices of the children of each node, in order.
+    ///
+    /// *Returns:* The tree, where node `i` has index `i`. Returns an error
+    ///            of kind [ErrorKind::InvalidInput] describing the first
+    ///            violation found, with the offending nodes available
+    ///            through [TreeError::nodes] when they are in range.
+    pub fn try_from_parts(
+        values: Vec<T>,
+        parents: Vec<Option<usize>>,
+        children: Vec<Vec<usize>>,
+    ) -> Result<Self> {
+        let n = values.len();
+        let invalid = |message: &str, nodes: &[usize]| {
+            let nodes: Vec<NodeRef> = nodes
+                .iter()
+                .filter(|id| **id < n)
+                .map(|id| NodeRef::new(*id))
+                .collect();
+            Err(TreeError::with_kind(ErrorKind::InvalidInput, message)
+                .during("try_from_parts")
+                .involving(&nodes))
+        };
+
+        if parents.len() != n || children.len() != n {
+            return invalid(
+                "Arrays of values, parents and children differ in length.",
+                &[],
+            );
+        }
+
+        let mut root = None;
+        for (id, parent) in parents.iter().enumerate() {
+            match parent {
+                None if root.is_some() => {
+                    return invalid("More than one node without parent.", &[root.unwrap(), id]);
+                }
+                None => root = Some(id),
+                Some(parent) if *parent >= n => {
+                    return invalid(&format!("Parent index {} out of range.", parent), &[id]);
+                }
+                Some(parent) if !children[*parent].contains(&id) => {
+                    return invalid(
+                        "Node is missing from the children of its parent.",
+                        &[id, *parent],
+                    );
+                }
+                Some(_) => {}
+            }
+        }
+
+        for (id, child_ids) in children.iter().enumerate() {
+            for child in child_ids {
+                if *child >= n {
+                    return invalid(&format!("Child index {} out of range.", child), &[id]);
+                }
+                if parents[*child] != Some(id) {
+                    return invalid("Child does not have the node as parent.", &[id, *child]);
+                }
+            }
+        }
+
+        let root = match root {
+            None if n == 0 => return Ok(Tree::new()),
+            None => return invalid("No node without parent.", &[]),
+            Some(root) => root,
+        };
+
+        // With consistent links, each node is listed at most once, unless
+        // it is listed twice by its own parent.
+        let mut visited = vec![false; n];
+        let mut stack = vec![root];
+        while let Some(id) = stack.pop() {
+            if visited[id] {
+                return invalid("Node listed more than once as child.", &[id]);
+            }
+            visited[id] = true;
+            stack.extend(&children[id]);
+        }
+        if let Some(id) = visited.iter().position(|visited| !visited) {
+            return invalid("Node is part of a cycle.", &[id]);
+        }
+
+        let mut tree = Tree::new();
+        for content in values {
+            tree.node(content);
+        }
+        tree.root = Some(NodeRef::new(root));
+        for (id, child_ids) in children.into_iter().enumerate() {
+            for child in child_ids {
+                tree.push_child(NodeRef::new(id), NodeRef::new(child));
+                tree.get_node_mut(NodeRef::new(child)).unwrap().parent = Some(NodeRef::new(id));
+            }
+        }
+        // All nodes were checked to be reachable from the root.
+        tree.unreachable = 0;
+        Ok(tree)
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,58 @@
+use super::*;
+
+#[test]
+fn try_from_parts() {
+    let tree = Tree::try_from_parts(
+        vec!["a", "b", "c", "d"],
+        vec![None, Some(0), Some(0), Some(2)],
+        vec![vec![2, 1], vec![], vec![3], vec![]],
+    )
+    .unwrap();
+
+    let values: Vec<&str> = tree
+        .depth_first(true)
+        .unwrap()
+        .map(|node_ref| *tree.get(node_ref).unwrap())
+        .collect();
+    assert_eq!(values, vec!["a", "c", "d", "b"]);
+    assert_eq!(tree.get_parent(NodeRef::new(3)), Ok(Some(NodeRef::new(2))));
+    assert_eq!(
+        Tree::<i32>::try_from_parts(vec![], vec![], vec![])
+            .unwrap()
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn try_from_parts_violations() {
+    let error = |parents: Vec<Option<usize>>, children: Vec<Vec<usize>>| {
+        Tree::try_from_parts(vec![0; parents.len()], parents, children).unwrap_err()
+    };
+
+    let cycle = error(vec![None, Some(2), Some(1)], vec![vec![], vec![2], vec![1]]);
+    assert_eq!(cycle.kind(), ErrorKind::InvalidInput);
+    assert_eq!(cycle.to_string(), "Node is part of a cycle.");
+    assert_eq!(cycle.nodes(), &[NodeRef::new(1)]);
+
+    let two_roots = error(vec![None, None], vec![vec![], vec![]]);
+    assert_eq!(two_roots.nodes(), &[NodeRef::new(0), NodeRef::new(1)]);
+
+    let out_of_range = error(vec![None, Some(7)], vec![vec![1], vec![]]);
+    assert_eq!(out_of_range.to_string(), "Parent index 7 out of range.");
+
+    let inconsistent = error(vec![None, Some(0)], vec![vec![], vec![]]);
+    assert_eq!(
+        inconsistent.to_string(),
+        "Node is missing from the children of its parent."
+    );
+
+    let duplicate = error(vec![None, Some(0)], vec![vec![1, 1], vec![]]);
+    assert_eq!(
+        duplicate.to_string(),
+        "Node listed more than once as child."
+    );
+
+    let lengths = Tree::try_from_parts(vec![1], vec![], vec![]).unwrap_err();
+    assert!(lengths.nodes().is_empty());
+}
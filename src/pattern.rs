@@ -0,0 +1,193 @@
+use super::{NodeRef, Result, Tree, TreeError};
+
+/// The result of matching a pattern, passed to the replacement
+/// function of [Tree::rewrite].
+pub struct Captures<'a, T> {
+    tree: &'a Tree<T>,
+    matched: NodeRef,
+    captures: Vec<(NodeRef, NodeRef)>,
+}
+
+impl<'a, T> Captures<'a, T> {
+    /// Get the tree being rewritten.
+    pub fn tree(&self) -> &'a Tree<T> {
+        self.tree
+    }
+
+    /// Get the root of the matched subtree.
+    pub fn matched(&self) -> NodeRef {
+        self.matched
+    }
+
+    /// Get the node matched by a leaf of the pattern.
+    ///
+    /// *Arguments:*
+    /// * `pattern_ref` - [NodeRef] of a leaf of the pattern tree.
+    ///
+    /// *Returns:* The matched node or `None` if `pattern_ref`
+    ///            is not a leaf of the pattern.
+    pub fn get(&self, pattern_ref: NodeRef) -> Option<NodeRef> {
+        self.captures
+            .iter()
+            .find(|(leaf_ref, _)| *leaf_ref == pattern_ref)
+            .map(|(_, node_ref)| *node_ref)
+    }
+}
+
+impl<'a, T: Clone> Captures<'a, T> {
+    /// Copy the subtree matched by a leaf of the pattern,
+    /// to be used in the replacement.
+    ///
+    /// *Arguments:*
+    /// * `pattern_ref` - [NodeRef] of a leaf of the pattern tree.
+    ///
+    /// *Returns:* A copy of the matched subtree or `None` if `pattern_ref`
+    ///            is not a leaf of the pattern.
+    pub fn subtree(&self, pattern_ref: NodeRef) -> Option<Tree<T>> {
+        self.get(pattern_ref)
+            .map(|node_ref| self.tree.clone_subtree(node_ref).unwrap())
+    }
+}
+
+impl<T> Tree<T> {
+    /// Find all subtrees matching a pattern tree.
+    ///
+    /// A node matches the root of the pattern if `matcher` accepts it and
+    /// its children match the children of the pattern root, in order and
+    /// in the same number. Leaves of the pattern match nodes regardless of
+    /// their children, so they act as wildcards for whole subtrees.
+    ///
+    /// *Arguments:*
+    /// * `pattern` - The pattern tree. A pattern without root matches nothing.
+    /// * `matcher` - Function deciding whether a pattern node matches a node.
+    ///
+    /// *Returns:* The roots of the matching subtrees in depth-first order.
+    pub fn match_subtree<P>(
+        &self,
+        pattern: &Tree<P>,
+        matcher: impl Fn(&P, &T) -> bool,
+    ) -> Vec<NodeRef> {
+        let (root_ref, pattern_root_ref) = match (self.root, pattern.root) {
+            (Some(root_ref), Some(pattern_root_ref)) => (root_ref, pattern_root_ref),
+            _ => return Vec::new(),
+        };
+
+        let mut captures = Vec::new();
+        self.depth_first_of(root_ref, true)
+            .unwrap()
+            .filter(|node_ref| {
+                captures.clear();
+                self.match_at(
+                    *node_ref,
+                    pattern,
+                    pattern_root_ref,
+                    &matcher,
+                    &mut captures,
+                )
+            })
+            .collect()
+    }
+
+    /// Replace subtrees matching a pattern until none is left.
+    ///
+    /// At each step, the first subtree in depth-first order matching the
+    /// pattern, as described in [Tree::match_subtree], is replaced by the
+    /// tree returned by `replace`. An empty tree removes the subtree. Steps
+    /// are repeated until no subtree matches or the step limit is reached.
+    ///
+    /// ```ignore
+    /// // Rewrite "x * 1" into "x"
+    /// let mut pattern = Tree::new();
+    /// let times = pattern.root("*")?;
+    /// let x = pattern.child_node(times, "_")?;
+    /// pattern.child_node(times, "1")?;
+    ///
+    /// tree.rewrite(&pattern, matcher, |captures| captures.subtree(x).unwrap(), 100)?;
+    /// ```
+    ///
+    /// *Arguments:*
+    /// * `pattern` - The pattern tree.
+    /// * `matcher` - Function deciding whether a pattern node matches a node.
+    /// * `replace` - Function building the replacement from the [Captures].
+    /// * `max_steps` - Maximum number of replacements.
+    ///
+    /// *Returns:* The number of replacements performed. Returns an error if
+    ///            subtrees still match after `max_steps` replacements, in which
+    ///            case the performed replacements are kept, or if a matched
+    ///            subtree is locked.
+    pub fn rewrite<P>(
+        &mut self,
+        pattern: &Tree<P>,
+        matcher: impl Fn(&P, &T) -> bool,
+        mut replace: impl FnMut(&Captures<T>) -> Tree<T>,
+        max_steps: usize,
+    ) -> Result<usize> {
+        for step in 0..=max_steps {
+            let matched = match self.match_subtree(pattern, &matcher).first() {
+                None => return Ok(step),
+                Some(matched) => *matched,
+            };
+            if step == max_steps {
+                break;
+            }
+
+            let mut captures = Vec::new();
+            self.match_at(
+                matched,
+                pattern,
+                pattern.root.unwrap(),
+                &matcher,
+                &mut captures,
+            );
+            let replacement = replace(&Captures {
+                tree: self,
+                matched,
+                captures,
+            });
+            self.replace_subtree(matched, replacement)?;
+        }
+
+        Err(TreeError::new(
+            "Rewrite did not reach a fixpoint within the step limit.",
+        ))
+    }
+
+    /// Match a pattern node against a node, collecting the pairs of
+    /// pattern leaves and matched nodes into `captures`.
+    pub(crate) fn match_at<P>(
+        &self,
+        node_ref: NodeRef,
+        pattern: &Tree<P>,
+        pattern_ref: NodeRef,
+        matcher: &impl Fn(&P, &T) -> bool,
+        captures: &mut Vec<(NodeRef, NodeRef)>,
+    ) -> bool {
+        let (node, pattern_node) = match (self.get_node(node_ref), pattern.get_node(pattern_ref)) {
+            (Some(node), Some(pattern_node)) => (node, pattern_node),
+            _ => return false,
+        };
+
+        if !matcher(&pattern_node.content, &node.content) {
+            return false;
+        }
+
+        let pattern_children = pattern.live_children(pattern_ref).count();
+        if pattern_children == 0 {
+            captures.push((pattern_ref, node_ref));
+            return true;
+        }
+
+        pattern_children == self.live_children(node_ref).count()
+            && pattern
+                .live_children(pattern_ref)
+                .zip(self.live_children(node_ref))
+                .all(|(pattern_child, child)| {
+                    self.match_at(child, pattern, pattern_child, matcher, captures)
+                })
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,147 @@
+use super::*;
+use crate::ErrorKind;
+
+fn expression() -> (Tree<&'static str>, NodeRef, NodeRef) {
+    /*
+     *            +
+     *            |
+     *     * -----+----- *
+     *     |             |
+     *  x -+- 0      y --+-- 2
+     */
+    let mut tree = Tree::new();
+    let plus = tree.root("+").unwrap();
+    let times_x = tree.child_node(plus, "*").unwrap();
+    tree.child_node(times_x, "x").unwrap();
+    tree.child_node(times_x, "0").unwrap();
+    let times_y = tree.child_node(plus, "*").unwrap();
+    tree.child_node(times_y, "y").unwrap();
+    tree.child_node(times_y, "2").unwrap();
+    (tree, times_x, times_y)
+}
+
+fn pattern(root: &'static str, children: &[&'static str]) -> Tree<&'static str> {
+    let mut pattern = Tree::new();
+    let root_ref = pattern.root(root).unwrap();
+    for child in children {
+        pattern.child_node(root_ref, *child).unwrap();
+    }
+    pattern
+}
+
+fn matcher(pattern: &&str, value: &&str) -> bool {
+    *pattern == "_" || pattern == value
+}
+
+#[test]
+fn match_subtree() {
+    let (tree, times_x, times_y) = expression();
+
+    assert_eq!(
+        tree.match_subtree(&pattern("*", &["_", "0"]), matcher),
+        vec![times_x]
+    );
+    assert_eq!(
+        tree.match_subtree(&pattern("*", &["_", "_"]), matcher),
+        vec![times_x, times_y]
+    );
+    assert_eq!(
+        tree.match_subtree(&pattern("+", &["_", "_"]), matcher),
+        vec![tree.get_root_ref().unwrap()]
+    );
+    assert_eq!(tree.match_subtree(&pattern("_", &[]), matcher).len(), 7);
+    assert!(tree
+        .match_subtree(&pattern("*", &["_"]), matcher)
+        .is_empty());
+    assert!(tree.match_subtree(&Tree::new(), matcher).is_empty());
+}
+
+#[test]
+fn rewrite() {
+    let (mut tree, _, _) = expression();
+
+    // x * 0 => 0
+    let mut times_zero = Tree::new();
+    let times = times_zero.root("*").unwrap();
+    times_zero.child_node(times, "_").unwrap();
+    let zero = times_zero.child_node(times, "0").unwrap();
+
+    let steps = tree.rewrite(
+        &times_zero,
+        matcher,
+        |captures| captures.subtree(zero).unwrap(),
+        10,
+    );
+    assert_eq!(steps, Ok(1));
+    assert_eq!(
+        tree.depth_first(true)
+            .unwrap()
+            .map(|node_ref| *tree.get(node_ref).unwrap())
+            .collect::<Vec<&str>>(),
+        vec!["+", "0", "*", "y", "2"]
+    );
+    assert_eq!(tree.len(), 5);
+
+    // a + b => a, repeatedly collapsing the root
+    let steps = tree.rewrite(
+        &pattern("+", &["_", "_"]),
+        matcher,
+        |captures| {
+            let first = captures
+                .tree()
+                .get_children(captures.matched())
+                .unwrap()
+                .next();
+            captures.tree().clone_subtree(first.unwrap()).unwrap()
+        },
+        10,
+    );
+    assert_eq!(steps, Ok(1));
+    assert_eq!(tree.get(tree.get_root_ref().unwrap()), Some(&"0"));
+    assert_eq!(tree.len(), 1);
+}
+
+#[test]
+fn rewrite_step_limit() {
+    let mut tree = Tree::new();
+    tree.root("a").unwrap();
+
+    // a => a(a), never terminates
+    let steps = tree.rewrite(&pattern("a", &[]), matcher, |_| pattern("a", &["a"]), 3);
+
+    assert_eq!(
+        steps,
+        Err(TreeError::new(
+            "Rewrite did not reach a fixpoint within the step limit."
+        ))
+    );
+    assert_eq!(tree.len(), 2);
+}
+
+#[test]
+fn rewrite_locked_descendant() {
+    let mut tree = pattern("a", &["b"]);
+    let root = tree.get_root_ref().unwrap();
+    let child = tree.get_children(root).unwrap().next().unwrap();
+    tree.lock_subtree(child).unwrap();
+
+    let error = tree
+        .rewrite(&pattern("a", &["_"]), matcher, |_| pattern("c", &[]), 1)
+        .unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Locked);
+    assert_eq!(tree.get(child), Some(&"b"));
+    assert_eq!(tree.len(), 2);
+}
+
+#[test]
+fn match_subtree_ignores_removed_children() {
+    let mut tree = pattern("+", &["x", "y", "z"]);
+    let root = tree.get_root_ref().unwrap();
+    let last = tree.get_children(root).unwrap().next_back().unwrap();
+    tree.remove(last).unwrap();
+
+    assert_eq!(
+        tree.match_subtree(&pattern("+", &["_", "_"]), matcher),
+        vec![root]
+    );
+}
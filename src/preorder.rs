@@ -0,0 +1,109 @@
+use std::fmt;
+use std::iter::FusedIterator;
+
+use super::{not_found, ChildRefs, NodeRef, Result, Tree};
+
+impl<T> Tree<T> {
+    /// Get an iterator traversing all nodes reachable from the root in
+    /// depth-first order, which never allocates once created.
+    ///
+    /// The iterator walks the child lists of the nodes in place, keeping
+    /// one position per level of the tree. The stack holding them is
+    /// sized to the height of the tree when the iterator is created, so
+    /// iterating does not allocate, box or copy any child list. This is
+    /// useful in real-time or allocation-sensitive code, at the price of
+    /// an additional pass over the tree at creation.
+    ///
+    /// *Returns:* An iterator returning the node references in depth-first
+    ///            order, starting with the root. Returns error if no root
+    ///            node exist.
+    pub fn depth_first_preorder_slices(&self) -> Result<PreorderSlicesIterator<'_, T>> {
+        let root_ref = match self.root {
+            None => return Err(not_found("Root node does not exist.")),
+            Some(root_ref) => root_ref,
+        };
+
+        let mut height = 0;
+        let mut levels = vec![(root_ref, 1)];
+        while let Some((node_ref, level)) = levels.pop() {
+            height = height.max(level);
+            levels.extend(
+                self.children_of(node_ref)
+                    .filter(|child_ref| self.get_node(**child_ref).is_some())
+                    .map(|child_ref| (*child_ref, level + 1)),
+            );
+        }
+
+        Ok(PreorderSlicesIterator {
+            tree: self,
+            start: Some(root_ref),
+            stack: Vec::with_capacity(height),
+        })
+    }
+}
+
+/// Depth-first traversal walking the child lists in place, created by
+/// [Tree::depth_first_preorder_slices].
+pub struct PreorderSlicesIterator<'a, T> {
+    tree: &'a Tree<T>,
+    start: Option<NodeRef>,
+    stack: Vec<ChildRefs<'a, T>>,
+}
+
+impl<'a, T> Iterator for PreorderSlicesIterator<'a, T> {
+    type Item = NodeRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(start) = self.start.take() {
+            self.stack.push(self.tree.children_of(start));
+            return Some(start);
+        }
+
+        loop {
+            let child_ref = match self.stack.last_mut()?.next() {
+                None => {
+                    self.stack.pop();
+                    continue;
+                }
+                Some(child_ref) => *child_ref,
+            };
+            if self.tree.get_node(child_ref).is_some() {
+                self.stack.push(self.tree.children_of(child_ref));
+                return Some(child_ref);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.start.is_some() as usize, None)
+    }
+}
+
+impl<'a, T> FusedIterator for PreorderSlicesIterator<'a, T> {}
+
+impl<'a, T> Clone for PreorderSlicesIterator<'a, T> {
+    fn clone(&self) -> Self {
+        // Keep the capacity, so that the clone does not allocate either.
+        let mut stack = Vec::with_capacity(self.stack.capacity());
+        stack.extend(self.stack.iter().cloned());
+        Self {
+            tree: self.tree,
+            start: self.start,
+            stack,
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for PreorderSlicesIterator<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PreorderSlicesIterator")
+            .field("start", &self.start)
+            .field("depth", &self.stack.len())
+            .finish_non_exhaustive()
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,84 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use super::*;
+use crate::ChildStorage;
+
+/// Allocator counting the allocations of the current thread,
+/// so that tests running in parallel do not interfere.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+fn deep_tree(storage: ChildStorage) -> Tree<usize> {
+    let mut tree = Tree::with_child_storage(storage);
+    let mut parent = tree.root(0).unwrap();
+    for i in 1..50 {
+        let child = tree.child_node(parent, i).unwrap();
+        tree.child_node(parent, i * 100).unwrap();
+        parent = child;
+    }
+    tree
+}
+
+#[test]
+fn iteration_does_not_allocate() {
+    for storage in [ChildStorage::Vec, ChildStorage::LinkedList] {
+        let tree = deep_tree(storage);
+        let expected: Vec<NodeRef> = tree.depth_first(true).unwrap().collect();
+
+        let mut nodes = Vec::with_capacity(expected.len());
+        let iter = tree.depth_first_preorder_slices().unwrap();
+        let before = allocations();
+        nodes.extend(iter);
+        assert_eq!(allocations(), before);
+        assert_eq!(nodes, expected);
+    }
+}
+
+#[test]
+fn skips_removed_nodes() {
+    let mut tree = deep_tree(ChildStorage::Vec);
+    let root = tree.get_root_ref().unwrap();
+    let child = tree.get_children(root).unwrap().next().unwrap();
+    tree.remove(child).unwrap();
+
+    assert_eq!(tree.depth_first_preorder_slices().unwrap().count(), 2);
+    assert!(Tree::<i32>::new().depth_first_preorder_slices().is_err());
+}
+
+#[test]
+fn cloned_iteration_does_not_allocate() {
+    let tree = deep_tree(ChildStorage::Vec);
+    let mut iter = tree.depth_first_preorder_slices().unwrap();
+    iter.next();
+
+    let copy = iter.clone();
+    let mut nodes = Vec::with_capacity(tree.len());
+    let before = allocations();
+    nodes.extend(copy);
+    assert_eq!(allocations(), before);
+    assert_eq!(nodes, iter.collect::<Vec<_>>());
+    assert!(format!("{:?}", tree.depth_first_preorder_slices().unwrap())
+        .starts_with("PreorderSlicesIterator"));
+}
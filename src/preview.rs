@@ -0,0 +1,90 @@
+use std::collections::VecDeque;
+
+use super::{not_found, NodeRef, Result, Tree};
+
+/// How [Tree::preview] chooses the nodes to keep.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PreviewStrategy {
+    /// Keep nodes in breadth-first order until the budget is spent,
+    /// so that the upper levels are shown completely first.
+    BreadthFirst,
+    /// Like [PreviewStrategy::BreadthFirst], but keep at most the
+    /// given number of nodes on each level, so that some of the
+    /// deeper levels are shown even below wide levels.
+    PerLevel(usize),
+}
+
+impl<T: Clone> Tree<T> {
+    /// Copy a bounded part of the tree into a new tree, to show a
+    /// preview of a large tree.
+    ///
+    /// The root node is always kept. Other nodes are considered in
+    /// breadth-first order and kept if they fit the budget chosen by
+    /// `strategy`. The descendants of a node which is not kept are
+    /// not traversed.
+    ///
+    /// ```ignore
+    /// let preview = tree.preview(100, PreviewStrategy::PerLevel(20), Some("…"))?;
+    /// ```
+    ///
+    /// *Arguments:*
+    /// * `max_nodes` - Maximum number of copied nodes, not counting placeholders.
+    /// * `strategy` - The [PreviewStrategy] choosing the nodes to keep.
+    /// * `placeholder` - If set, a copy of this value is added as last
+    ///   child of the copied nodes with elided children.
+    ///
+    /// *Returns:* The new tree. Returns error if no root node exist.
+    pub fn preview(
+        &self,
+        max_nodes: usize,
+        strategy: PreviewStrategy,
+        placeholder: Option<T>,
+    ) -> Result<Tree<T>> {
+        let root_ref = match self.root {
+            None => return Err(not_found("Cannot preview a tree without a root node.")),
+            Some(root_ref) => root_ref,
+        };
+        let level_cap = match strategy {
+            PreviewStrategy::BreadthFirst => usize::MAX,
+            PreviewStrategy::PerLevel(level_cap) => level_cap,
+        };
+
+        let mut tree = Tree::new();
+        let new_root_ref = tree.root(self.get(root_ref).unwrap().clone()).unwrap();
+        let mut kept = 1;
+        let mut level_counts: Vec<usize> = vec![1];
+        let mut queue: VecDeque<(NodeRef, NodeRef, usize)> = VecDeque::new();
+        queue.push_back((root_ref, new_root_ref, 0));
+
+        while let Some((old_ref, new_ref, depth)) = queue.pop_front() {
+            if level_counts.len() == depth + 1 {
+                level_counts.push(0);
+            }
+
+            let mut elided = false;
+            for child_ref in self.live_children(old_ref) {
+                if kept >= max_nodes || level_counts[depth + 1] >= level_cap {
+                    elided = true;
+                    continue;
+                }
+
+                let content = self.get(child_ref).unwrap().clone();
+                let new_child_ref = tree.child_node(new_ref, content).unwrap();
+                queue.push_back((child_ref, new_child_ref, depth + 1));
+                kept += 1;
+                level_counts[depth + 1] += 1;
+            }
+
+            if let (Some(placeholder), true) = (&placeholder, elided) {
+                tree.child_node(new_ref, placeholder.clone()).unwrap();
+            }
+        }
+
+        Ok(tree)
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
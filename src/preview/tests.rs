@@ -0,0 +1,41 @@
+use super::*;
+use crate::tree;
+
+fn values(tree: &Tree<i32>) -> Vec<i32> {
+    tree.depth_first(true)
+        .unwrap()
+        .map(|node_ref| *tree.get(node_ref).unwrap())
+        .collect()
+}
+
+#[test]
+fn preview_breadth_first() {
+    let tree = tree!(1 => [2 => [5, 6], 3 => [7], 4]);
+    let preview = tree
+        .preview(5, PreviewStrategy::BreadthFirst, Some(0))
+        .unwrap();
+    assert_eq!(values(&preview), vec![1, 2, 5, 0, 3, 0, 4]);
+
+    let preview = tree
+        .preview(0, PreviewStrategy::BreadthFirst, None)
+        .unwrap();
+    assert_eq!(values(&preview), vec![1]);
+
+    let preview = tree
+        .preview(100, PreviewStrategy::BreadthFirst, Some(0))
+        .unwrap();
+    assert!(preview.structural_eq(&tree));
+
+    assert!(Tree::<i32>::new()
+        .preview(10, PreviewStrategy::BreadthFirst, None)
+        .is_err());
+}
+
+#[test]
+fn preview_per_level() {
+    let tree = tree!(1 => [2 => [5 => [8]], 3 => [6, 7], 4]);
+    let preview = tree
+        .preview(100, PreviewStrategy::PerLevel(2), Some(0))
+        .unwrap();
+    assert_eq!(values(&preview), vec![1, 2, 5, 8, 3, 6, 0, 0]);
+}
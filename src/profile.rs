@@ -0,0 +1,172 @@
+use std::cmp::Reverse;
+use std::time::{Duration, Instant};
+
+use super::{NodeRef, Tree};
+
+/// Time spent on the nodes returned by a [Profiled] traversal.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct ProfileEntry {
+    /// Number of times the node was returned.
+    pub visits: usize,
+    /// Time spent on the node itself, from when it was returned
+    /// until the next node was requested.
+    pub self_time: Duration,
+    /// Number of visits of the node and its descendants.
+    pub subtree_visits: usize,
+    /// Time spent on the node and its descendants.
+    pub subtree_time: Duration,
+}
+
+/// Visit counts and durations collected by a [Profiled] traversal,
+/// created by [Profiled::into_report].
+#[derive(Debug, Clone, Default)]
+pub struct ProfileReport {
+    entries: Vec<Option<ProfileEntry>>,
+}
+
+impl ProfileReport {
+    /// Get the profile of a node and its subtree.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    ///
+    /// *Returns:* The profile or `None` if neither the node nor
+    ///            any of its descendants was visited.
+    pub fn get(&self, node_ref: NodeRef) -> Option<&ProfileEntry> {
+        self.entries.get(node_ref.index())?.as_ref()
+    }
+
+    /// Get the total time spent on the visited nodes.
+    pub fn total_time(&self) -> Duration {
+        self.entries
+            .iter()
+            .flatten()
+            .map(|entry| entry.self_time)
+            .sum()
+    }
+
+    /// Get the subtrees on which the most time was spent.
+    ///
+    /// Subtrees contain each other, so the root of a traversal is
+    /// usually first. The path from it to a hot branch can be followed
+    /// by comparing the subtree times of the children of each node.
+    ///
+    /// *Arguments:*
+    /// * `count` - Maximum number of returned subtrees.
+    ///
+    /// *Returns:* The roots of the subtrees with their profile,
+    ///            by decreasing subtree time.
+    pub fn hottest(&self, count: usize) -> Vec<(NodeRef, ProfileEntry)> {
+        let mut entries: Vec<(NodeRef, ProfileEntry)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| Some((NodeRef::new(index), (*entry)?)))
+            .collect();
+        entries.sort_by_key(|(_, entry)| Reverse(entry.subtree_time));
+        entries.truncate(count);
+        entries
+    }
+}
+
+/// Iterator adapter measuring the time spent on each node of a traversal,
+/// created by [NodeIteratorExt::profiled](crate::NodeIteratorExt::profiled).
+///
+/// The time spent on a node goes from when it is returned until the next
+/// node is requested, which is the time the consumer spends processing it.
+/// Call [Profiled::into_report] once the traversal is done to get the
+/// times of the nodes and of their subtrees.
+///
+/// ```ignore
+/// let mut nodes = tree.depth_first(true)?.profiled(&tree);
+/// for node_ref in nodes.by_ref() {
+///     process(&tree, node_ref);
+/// }
+/// for (node_ref, entry) in nodes.into_report().hottest(10) {
+///     println!("{:?}: {:?}", tree.get(node_ref), entry.subtree_time);
+/// }
+/// ```
+pub struct Profiled<'a, I, T> {
+    iter: I,
+    tree: &'a Tree<T>,
+    current: Option<(NodeRef, Instant)>,
+    entries: Vec<Option<ProfileEntry>>,
+}
+
+impl<'a, I: Iterator<Item = NodeRef>, T> Profiled<'a, I, T> {
+    pub(crate) fn new(iter: I, tree: &'a Tree<T>) -> Self {
+        Profiled {
+            iter,
+            tree,
+            current: None,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Stop measuring and compute the times of the subtrees.
+    ///
+    /// *Returns:* The [ProfileReport] of the nodes returned so far.
+    pub fn into_report(mut self) -> ProfileReport {
+        self.stop();
+
+        let mut entries = vec![None; self.entries.len()];
+        for (index, entry) in self.entries.iter().enumerate() {
+            let entry = match entry {
+                None => continue,
+                Some(entry) => entry,
+            };
+            let ancestors = match self.tree.ancestors_or_self(NodeRef::new(index)) {
+                Err(_) => continue,
+                Ok(ancestors) => ancestors,
+            };
+            for ancestor_ref in ancestors {
+                if entries.len() <= ancestor_ref.index() {
+                    entries.resize(ancestor_ref.index() + 1, None);
+                }
+                let subtree: &mut ProfileEntry =
+                    entries[ancestor_ref.index()].get_or_insert_with(Default::default);
+                subtree.subtree_visits += entry.visits;
+                subtree.subtree_time += entry.self_time;
+            }
+            let own = entries[index].as_mut().unwrap();
+            own.visits = entry.visits;
+            own.self_time = entry.self_time;
+        }
+        ProfileReport { entries }
+    }
+
+    /// Add the time elapsed since the current node was returned to it.
+    fn stop(&mut self) {
+        if let Some((node_ref, start)) = self.current.take() {
+            let entry = self.entries[node_ref.index()].as_mut().unwrap();
+            entry.self_time += start.elapsed();
+        }
+    }
+}
+
+impl<'a, I: Iterator<Item = NodeRef>, T> Iterator for Profiled<'a, I, T> {
+    type Item = NodeRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.stop();
+        let node_ref = self.iter.next()?;
+
+        if self.entries.len() <= node_ref.index() {
+            self.entries.resize(node_ref.index() + 1, None);
+        }
+        self.entries[node_ref.index()]
+            .get_or_insert_with(Default::default)
+            .visits += 1;
+        self.current = Some((node_ref, Instant::now()));
+        Some(node_ref)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
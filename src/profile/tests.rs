@@ -0,0 +1,31 @@
+use std::thread;
+
+use super::*;
+use crate::NodeIteratorExt;
+
+#[test]
+fn profiled_traversal() {
+    let mut tree = Tree::new();
+    let root = tree.root("root").unwrap();
+    let slow = tree.child_node(root, "slow").unwrap();
+    let leaf = tree.child_node(slow, "leaf").unwrap();
+    let fast = tree.child_node(root, "fast").unwrap();
+
+    let mut nodes = tree.depth_first(true).unwrap().profiled(&tree);
+    for node_ref in nodes.by_ref() {
+        if node_ref == leaf {
+            thread::sleep(Duration::from_millis(20));
+        }
+    }
+    let report = nodes.into_report();
+
+    let root_entry = report.get(root).unwrap();
+    assert_eq!((root_entry.visits, root_entry.subtree_visits), (1, 4));
+    assert_eq!(report.get(slow).unwrap().subtree_visits, 2);
+    assert!(report.get(slow).unwrap().subtree_time >= Duration::from_millis(20));
+    assert!(report.get(fast).unwrap().subtree_time < report.get(slow).unwrap().subtree_time);
+    assert_eq!(root_entry.subtree_time, report.total_time());
+
+    let hottest: Vec<NodeRef> = report.hottest(3).into_iter().map(|(n, _)| n).collect();
+    assert_eq!(hottest, vec![root, slow, leaf]);
+}
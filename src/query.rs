@@ -0,0 +1,247 @@
+use super::{invalid_input, not_found, NodeRef, Result, Tree};
+use std::collections::HashMap;
+
+/// Named predicates usable in queries as `[name]`.
+///
+/// ```ignore
+/// let mut predicates = Predicates::new();
+/// predicates.register("hidden", |name: &&str| name.starts_with('.'));
+///
+/// let hidden = tree.query_with("//*[hidden]", &predicates)?;
+/// ```
+pub struct Predicates<'a, T> {
+    predicates: HashMap<String, Predicate<'a, T>>,
+}
+
+type Predicate<'a, T> = Box<dyn Fn(&T) -> bool + 'a>;
+
+impl<'a, T> Predicates<'a, T> {
+    /// Create an empty predicate registry.
+    pub fn new() -> Self {
+        Self {
+            predicates: HashMap::new(),
+        }
+    }
+
+    /// Register a predicate on the node content.
+    ///
+    /// *Arguments:*
+    /// * `name` - Name used to refer to the predicate in queries.
+    /// * `predicate` - Function deciding whether a node is selected.
+    pub fn register(&mut self, name: &str, predicate: impl Fn(&T) -> bool + 'a) {
+        self.predicates
+            .insert(name.to_string(), Box::new(predicate));
+    }
+}
+
+impl<'a, T> Default for Predicates<'a, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Axis {
+    Child,
+    Descendant,
+}
+
+#[derive(Debug, PartialEq)]
+enum Test {
+    Any,
+    Name(String),
+    Current,
+    Parent,
+}
+
+#[derive(Debug, PartialEq)]
+struct Step {
+    axis: Axis,
+    test: Test,
+    predicates: Vec<String>,
+}
+
+impl<T: AsRef<str>> Tree<T> {
+    /// Select nodes with a path expression.
+    ///
+    /// See [Tree::query_with] for the syntax.
+    ///
+    /// *Arguments:*
+    /// * `expression` - The query.
+    ///
+    /// *Returns:* The selected node references in depth-first order.
+    ///            Returns error if the query is invalid or no root node exist.
+    pub fn query(&self, expression: &str) -> Result<Vec<NodeRef>> {
+        self.query_with(expression, &Predicates::new())
+    }
+
+    /// Select nodes with a path expression using named predicates.
+    ///
+    /// Paths start at the root node and are made of steps separated by
+    /// `/`, selecting the children of the current nodes, or `//`, selecting
+    /// all their descendants. A step is either a node value, `*` to match
+    /// any value, `.` for the current nodes or `..` for their parents. Steps
+    /// can be filtered with predicates from the registry, written as
+    /// `[name]`. Multiple paths are combined with `|`.
+    ///
+    /// ```ignore
+    /// let nodes = tree.query_with("/usr/*[large]/lib | //etc", &predicates)?;
+    /// ```
+    ///
+    /// *Arguments:*
+    /// * `expression` - The query.
+    /// * `predicates` - Registry of the predicates used in the query.
+    ///
+    /// *Returns:* The selected node references in depth-first order.
+    ///            Returns error if the query is invalid or no root node exist.
+    pub fn query_with(&self, expression: &str, predicates: &Predicates<T>) -> Result<Vec<NodeRef>> {
+        let root_ref = match self.root {
+            None => return Err(not_found("Cannot query a tree without a root node.")),
+            Some(root_ref) => root_ref,
+        };
+
+        let mut selected = vec![false; self.nodes.len()];
+        for path in expression.split('|') {
+            let steps = parse_path(path)?;
+            for step in &steps {
+                for name in &step.predicates {
+                    if !predicates.predicates.contains_key(name) {
+                        return Err(invalid_input(&format!(
+                            "Unknown query predicate '{}'.",
+                            name
+                        )));
+                    }
+                }
+            }
+
+            let mut current = vec![root_ref];
+            for step in &steps {
+                current = self.query_step(&current, step, predicates);
+            }
+            for node_ref in current {
+                selected[node_ref.index()] = true;
+            }
+        }
+
+        Ok(self
+            .depth_first(true)?
+            .filter(|node_ref| selected[node_ref.index()])
+            .collect())
+    }
+
+    fn query_step(
+        &self,
+        current: &[NodeRef],
+        step: &Step,
+        predicates: &Predicates<T>,
+    ) -> Vec<NodeRef> {
+        let mut seen = vec![false; self.nodes.len()];
+        let mut next = Vec::new();
+
+        let mut select = |node_ref: NodeRef| {
+            if seen[node_ref.index()] {
+                return;
+            }
+            let value = match self.get(node_ref) {
+                None => return,
+                Some(value) => value,
+            };
+            let name_matches = match &step.test {
+                Test::Name(name) => value.as_ref() == name,
+                _ => true,
+            };
+            if name_matches
+                && step
+                    .predicates
+                    .iter()
+                    .all(|name| (predicates.predicates[name])(value))
+            {
+                seen[node_ref.index()] = true;
+                next.push(node_ref);
+            }
+        };
+
+        for node_ref in current {
+            let candidates: Vec<NodeRef> = match (&step.axis, &step.test) {
+                (Axis::Child, Test::Current) => vec![*node_ref],
+                (Axis::Child, Test::Parent) => {
+                    self.get_parent(*node_ref).unwrap().into_iter().collect()
+                }
+                (Axis::Descendant, Test::Current) => {
+                    self.depth_first_of(*node_ref, true).unwrap().collect()
+                }
+                (Axis::Descendant, Test::Parent) => self
+                    .depth_first_of(*node_ref, true)
+                    .unwrap()
+                    .filter_map(|node_ref| self.get_parent(node_ref).unwrap())
+                    .collect(),
+                (Axis::Child, _) => self.get_children(*node_ref).unwrap().collect(),
+                (Axis::Descendant, _) => self.depth_first_of(*node_ref, false).unwrap().collect(),
+            };
+            for candidate in candidates {
+                select(candidate);
+            }
+        }
+
+        next
+    }
+}
+
+fn parse_path(path: &str) -> Result<Vec<Step>> {
+    let mut rest = path.trim();
+    let mut steps = Vec::new();
+
+    if rest.is_empty() {
+        return Err(invalid_input("Invalid query: empty path."));
+    }
+    if rest == "/" {
+        return Ok(steps);
+    }
+
+    while !rest.is_empty() {
+        let axis = if let Some(stripped) = rest.strip_prefix("//") {
+            rest = stripped;
+            Axis::Descendant
+        } else if let Some(stripped) = rest.strip_prefix('/') {
+            rest = stripped;
+            Axis::Child
+        } else if steps.is_empty() {
+            Axis::Child
+        } else {
+            return Err(invalid_input("Invalid query: expected '/'."));
+        };
+
+        let end = rest.find(['/', '[']).unwrap_or(rest.len());
+        let test = match rest[..end].trim() {
+            "" => return Err(invalid_input("Invalid query: empty step.")),
+            "*" => Test::Any,
+            "." => Test::Current,
+            ".." => Test::Parent,
+            name => Test::Name(name.to_string()),
+        };
+        rest = &rest[end..];
+
+        let mut predicates = Vec::new();
+        while let Some(stripped) = rest.strip_prefix('[') {
+            let close = match stripped.find(']') {
+                None => return Err(invalid_input("Invalid query: unclosed predicate.")),
+                Some(close) => close,
+            };
+            predicates.push(stripped[..close].trim().to_string());
+            rest = &stripped[close + 1..];
+        }
+
+        steps.push(Step {
+            axis,
+            test,
+            predicates,
+        });
+    }
+
+    Ok(steps)
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
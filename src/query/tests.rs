@@ -0,0 +1,108 @@
+use super::*;
+use crate::{ErrorKind, TreeError};
+
+fn fs_tree() -> Tree<&'static str> {
+    let mut tree = Tree::new();
+    let root = tree.root("/").unwrap();
+    let usr = tree.child_node(root, "usr").unwrap();
+    let etc = tree.child_node(root, "etc").unwrap();
+    tree.child_node(etc, ".profile").unwrap();
+    let local = tree.child_node(usr, "local").unwrap();
+    tree.child_node(local, "lib").unwrap();
+    let share = tree.child_node(usr, "share").unwrap();
+    tree.child_node(share, "lib").unwrap();
+    tree.child_node(share, "etc").unwrap();
+    tree
+}
+
+fn paths(
+    tree: &Tree<&'static str>,
+    expression: &str,
+    predicates: &Predicates<&str>,
+) -> Vec<String> {
+    tree.query_with(expression, predicates)
+        .unwrap()
+        .into_iter()
+        .map(|node_ref| {
+            let mut path: Vec<&str> = tree
+                .ancestors(node_ref)
+                .unwrap()
+                .map(|ancestor_ref| *tree.get(ancestor_ref).unwrap())
+                .collect();
+            path.reverse();
+            path.push(tree.get(node_ref).unwrap());
+            path.join(" ")
+        })
+        .collect()
+}
+
+#[test]
+fn query_paths() {
+    let tree = fs_tree();
+    let predicates = Predicates::new();
+
+    assert_eq!(
+        paths(&tree, "/usr/*/lib | //etc", &predicates),
+        vec![
+            "/ usr local lib",
+            "/ usr share lib",
+            "/ usr share etc",
+            "/ etc"
+        ]
+    );
+    assert_eq!(paths(&tree, "usr/share/..", &predicates), vec!["/ usr"]);
+    assert_eq!(paths(&tree, "/", &predicates), vec!["/"]);
+    assert_eq!(paths(&tree, "//etc/.", &predicates).len(), 2);
+    assert_eq!(tree.query("//lib").unwrap().len(), 2);
+}
+
+#[test]
+fn query_predicates() {
+    let tree = fs_tree();
+    let mut predicates = Predicates::new();
+    predicates.register("hidden", |name: &&str| name.starts_with('.'));
+    predicates.register("short", |name: &&str| name.len() <= 3);
+
+    assert_eq!(
+        paths(&tree, "//*[hidden]", &predicates),
+        vec!["/ etc .profile"]
+    );
+    assert_eq!(
+        paths(&tree, "/usr//*[short][short]", &predicates),
+        vec!["/ usr local lib", "/ usr share lib", "/ usr share etc"]
+    );
+}
+
+#[test]
+fn query_errors() {
+    let tree = fs_tree();
+
+    assert_eq!(
+        tree.query("/usr//"),
+        Err(TreeError::with_kind(
+            ErrorKind::InvalidInput,
+            "Invalid query: empty step."
+        ))
+    );
+    assert_eq!(
+        tree.query("/usr[big"),
+        Err(TreeError::with_kind(
+            ErrorKind::InvalidInput,
+            "Invalid query: unclosed predicate."
+        ))
+    );
+    assert_eq!(
+        tree.query("//*[big]"),
+        Err(TreeError::with_kind(
+            ErrorKind::InvalidInput,
+            "Unknown query predicate 'big'."
+        ))
+    );
+    assert_eq!(
+        tree.query("usr |"),
+        Err(TreeError::with_kind(
+            ErrorKind::InvalidInput,
+            "Invalid query: empty path."
+        ))
+    );
+}
@@ -0,0 +1,135 @@
+use std::fmt;
+
+use super::Tree;
+
+/// Output format of [Tree::render].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RenderStyle {
+    /// Branches drawn with ASCII characters, like `|--` and `` `-- ``.
+    Ascii,
+    /// Branches drawn with box-drawing characters, like `├──` and `└──`.
+    Unicode,
+    /// Nested Markdown list, indented by two spaces per level.
+    MarkdownList,
+}
+
+impl RenderStyle {
+    /// Get the strings drawn before a node: the continuation of an
+    /// ancestor with more siblings, the padding of an ancestor without,
+    /// the branch to a node and the branch to the last child.
+    fn parts(self) -> [&'static str; 4] {
+        match self {
+            RenderStyle::Ascii => ["|   ", "    ", "|-- ", "`-- "],
+            RenderStyle::Unicode => ["│   ", "    ", "├── ", "└── "],
+            RenderStyle::MarkdownList => ["  ", "  ", "- ", "- "],
+        }
+    }
+
+    fn ellipsis(self) -> &'static str {
+        match self {
+            RenderStyle::Unicode => "…",
+            _ => "...",
+        }
+    }
+}
+
+impl<T: fmt::Display> Tree<T> {
+    /// Write the tree as text, one line per node.
+    ///
+    /// Lines are written directly to `w` as the tree is traversed,
+    /// without building intermediate strings, so that large trees can
+    /// be dumped to logs or files.
+    ///
+    /// ```ignore
+    /// let mut out = String::new();
+    /// tree.render(&mut out, RenderStyle::Unicode)?;
+    /// // /
+    /// // ├── etc
+    /// // └── usr
+    /// //     └── lib
+    /// ```
+    ///
+    /// *Arguments:*
+    /// * `w` - Destination of the text.
+    /// * `style` - The [RenderStyle] of the output.
+    ///
+    /// *Returns:* The result of writing to `w`. Nothing is written
+    ///            if the tree has no root node.
+    pub fn render<W: fmt::Write>(&self, w: &mut W, style: RenderStyle) -> fmt::Result {
+        self.render_to_depth(w, style, usize::MAX)
+    }
+
+    /// Write the tree as text, down to a maximum depth.
+    ///
+    /// Same as [Tree::render], but the children of the nodes at
+    /// `max_depth` are replaced by a single ellipsis line.
+    ///
+    /// *Arguments:*
+    /// * `w` - Destination of the text.
+    /// * `style` - The [RenderStyle] of the output.
+    /// * `max_depth` - Depth of the deepest rendered nodes, the root
+    ///   being at depth 0.
+    ///
+    /// *Returns:* The result of writing to `w`.
+    pub fn render_to_depth<W: fmt::Write>(
+        &self,
+        w: &mut W,
+        style: RenderStyle,
+        max_depth: usize,
+    ) -> fmt::Result {
+        let root_ref = match self.root {
+            None => return Ok(()),
+            Some(root_ref) => root_ref,
+        };
+
+        let [open, closed, branch, last_branch] = style.parts();
+        // Markdown lists have a bullet for the root too.
+        let first_branched = if style == RenderStyle::MarkdownList {
+            0
+        } else {
+            1
+        };
+
+        // For each level above the current node, whether the ancestor
+        // at that level has following siblings.
+        let mut levels: Vec<bool> = Vec::new();
+        let mut stack = vec![(root_ref, 0usize, true)];
+        while let Some((node_ref, depth, last)) = stack.pop() {
+            levels.truncate(depth.saturating_sub(first_branched));
+            if depth >= first_branched {
+                for has_next in &levels {
+                    w.write_str(if *has_next { open } else { closed })?;
+                }
+                w.write_str(if last { last_branch } else { branch })?;
+            }
+            writeln!(w, "{}", self.get(node_ref).unwrap())?;
+            if depth >= first_branched {
+                levels.push(!last);
+            }
+
+            let mut children = self.live_children(node_ref).peekable();
+            if children.peek().is_none() {
+                continue;
+            }
+            if depth == max_depth {
+                for has_next in &levels {
+                    w.write_str(if *has_next { open } else { closed })?;
+                }
+                writeln!(w, "{}{}", last_branch, style.ellipsis())?;
+                continue;
+            }
+
+            let start = stack.len();
+            stack.extend(children.map(|child_ref| (child_ref, depth + 1, false)));
+            stack.last_mut().unwrap().2 = true;
+            stack[start..].reverse();
+        }
+
+        Ok(())
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,54 @@
+use super::*;
+
+fn fs_tree() -> Tree<&'static str> {
+    let mut tree = Tree::new();
+    let root = tree.root("/").unwrap();
+    let etc = tree.child_node(root, "etc").unwrap();
+    tree.child_node(etc, "hosts").unwrap();
+    let usr = tree.child_node(root, "usr").unwrap();
+    let lib = tree.child_node(usr, "lib").unwrap();
+    tree.child_node(lib, "libc.so").unwrap();
+    tree.child_node(usr, "bin").unwrap();
+    tree
+}
+
+fn render(tree: &Tree<&str>, style: RenderStyle, max_depth: usize) -> String {
+    let mut out = String::new();
+    tree.render_to_depth(&mut out, style, max_depth).unwrap();
+    out
+}
+
+#[test]
+fn render_styles() {
+    let tree = fs_tree();
+
+    let mut out = String::new();
+    tree.render(&mut out, RenderStyle::Ascii).unwrap();
+    assert_eq!(
+        out,
+        "/\n|-- etc\n|   `-- hosts\n`-- usr\n    |-- lib\n    |   `-- libc.so\n    `-- bin\n"
+    );
+    assert_eq!(
+        render(&tree, RenderStyle::Unicode, usize::MAX),
+        "/\n├── etc\n│   └── hosts\n└── usr\n    ├── lib\n    │   └── libc.so\n    └── bin\n"
+    );
+    assert_eq!(
+        render(&tree, RenderStyle::MarkdownList, usize::MAX),
+        "- /\n  - etc\n    - hosts\n  - usr\n    - lib\n      - libc.so\n    - bin\n"
+    );
+}
+
+#[test]
+fn render_to_depth() {
+    let tree = fs_tree();
+
+    assert_eq!(
+        render(&tree, RenderStyle::Ascii, 1),
+        "/\n|-- etc\n|   `-- ...\n`-- usr\n    `-- ...\n"
+    );
+    assert_eq!(
+        render(&tree, RenderStyle::MarkdownList, 0),
+        "- /\n  - ...\n"
+    );
+    assert_eq!(render(&Tree::<&str>::new(), RenderStyle::Unicode, 5), "");
+}
@@ -0,0 +1,376 @@
+use std::cmp::Ordering;
+
+use super::{invalid_input, node_not_found, NodeRef, Result, Tree, TreeError};
+
+impl<T> Tree<T> {
+    /// Reverse the order of the children of a node and of all
+    /// its descendants, like a mirror image of the subtree.
+    ///
+    /// This is useful to turn a left-to-right layout into a
+    /// right-to-left one.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the root of the subtree.
+    ///
+    /// *Returns:* Result indicating whether the operations was successful.
+    ///            Returns an error if the node does not exist or is locked.
+    pub fn mirror(&mut self, node_ref: NodeRef) -> Result<()> {
+        let subtree: Vec<NodeRef> = self.depth_first_of(node_ref, true)?.collect();
+        self.check_subtree_unlocked(&subtree)?;
+
+        for node_ref in subtree {
+            self.reverse_children(node_ref);
+        }
+        Ok(())
+    }
+
+    /// Reverse the order of the children of a node, leaving
+    /// the order of their own children unchanged.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the parent node.
+    ///
+    /// *Returns:* Result indicating whether the operations was successful.
+    ///            Returns an error if the node does not exist or is locked.
+    pub fn mirror_shallow(&mut self, node_ref: NodeRef) -> Result<()> {
+        if self.get_node(node_ref).is_none() {
+            return Err(node_not_found(node_ref));
+        }
+        self.check_unlocked(node_ref)?;
+        self.reverse_children(node_ref);
+        Ok(())
+    }
+
+    /// Sort the children of every node to bring the tree in
+    /// a canonical form.
+    ///
+    /// Children are ordered by their values first. Children with equal
+    /// values are ordered by their number of children and then by their
+    /// own children, compared in the same way, so that trees equal up to
+    /// the order of the children become equal after normalization.
+    /// A tree without root node is left unchanged.
+    ///
+    /// *Arguments:*
+    /// * `cmp` - Function comparing two values.
+    ///
+    /// *Returns:* Result indicating whether the operations was successful.
+    ///            Returns an error if a node of the tree is locked.
+    pub fn normalize(&mut self, cmp: impl Fn(&T, &T) -> Ordering) -> Result<()> {
+        let root_ref = match self.root {
+            None => return Ok(()),
+            Some(root_ref) => root_ref,
+        };
+        let order: Vec<NodeRef> = self.depth_first_of(root_ref, true)?.collect();
+        self.check_subtree_unlocked(&order)?;
+
+        // Children are sorted before their parents, so that
+        // subtrees are compared in their canonical form.
+        for node_ref in order.into_iter().rev() {
+            let mut children: Vec<NodeRef> = self.live_children(node_ref).collect();
+            children.sort_by(|a, b| self.compare_subtrees(*a, *b, &cmp));
+            self.set_child_list(node_ref, children);
+        }
+        Ok(())
+    }
+
+    fn compare_subtrees(
+        &self,
+        a_ref: NodeRef,
+        b_ref: NodeRef,
+        cmp: &impl Fn(&T, &T) -> Ordering,
+    ) -> Ordering {
+        let mut stack = vec![(a_ref, b_ref)];
+        while let Some((a_ref, b_ref)) = stack.pop() {
+            let a_children: Vec<NodeRef> = self.live_children(a_ref).collect();
+            let b_children: Vec<NodeRef> = self.live_children(b_ref).collect();
+            let ordering = cmp(self.get(a_ref).unwrap(), self.get(b_ref).unwrap())
+                .then(a_children.len().cmp(&b_children.len()));
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+            stack.extend(a_children.into_iter().zip(b_children).rev());
+        }
+        Ordering::Equal
+    }
+
+    /// Merge chains of nodes with a single child into one node,
+    /// like `a/b/c` in the directory listings of code hosting sites.
+    ///
+    /// A node with a single child is merged with it if the child has
+    /// children itself, so that leaves are kept as separate nodes. The
+    /// merged node keeps the [NodeRef] of the upper node and takes the
+    /// children of the lower one, which is removed.
+    ///
+    /// ```ignore
+    /// // "src" => ["lineartree" => ["lib.rs"]] becomes "src/lineartree" => ["lib.rs"]
+    /// tree.collapse_unary_chains(|upper, lower| {
+    ///     upper.push('/');
+    ///     upper.push_str(&lower);
+    /// })?;
+    /// ```
+    ///
+    /// *Arguments:*
+    /// * `join_fn` - Function merging the content of the lower node
+    ///   into the one of the upper node.
+    ///
+    /// *Returns:* The number of removed nodes. Returns an error if
+    ///            a node of the tree is locked.
+    pub fn collapse_unary_chains(&mut self, mut join_fn: impl FnMut(&mut T, T)) -> Result<usize> {
+        let root_ref = match self.root {
+            None => return Ok(0),
+            Some(root_ref) => root_ref,
+        };
+        let order: Vec<NodeRef> = self.depth_first_of(root_ref, true)?.collect();
+        self.check_subtree_unlocked(&order)?;
+
+        let mut removed = 0;
+        let mut stack = vec![root_ref];
+        while let Some(node_ref) = stack.pop() {
+            loop {
+                let children: Vec<NodeRef> = self.live_children(node_ref).collect();
+                let child_ref = match children[..] {
+                    [child_ref] => child_ref,
+                    _ => break,
+                };
+                let grandchildren: Vec<NodeRef> = self.live_children(child_ref).collect();
+                if grandchildren.is_empty() {
+                    break;
+                }
+
+                self.set_child_list(child_ref, Vec::new());
+                self.set_child_list(node_ref, grandchildren.clone());
+                for grandchild_ref in grandchildren {
+                    self.get_node_mut(grandchild_ref).unwrap().parent = Some(node_ref);
+                }
+
+                let child = self.nodes[child_ref.index()].take().unwrap();
+                join_fn(
+                    &mut self.get_node_mut(node_ref).unwrap().content,
+                    child.content,
+                );
+                self.len -= 1;
+                removed += 1;
+            }
+            let children: Vec<NodeRef> = self.live_children(node_ref).collect();
+            stack.extend(children);
+        }
+        Ok(removed)
+    }
+
+    /// Rotate a subtree to the left around one of the children of
+    /// its root.
+    ///
+    /// The child, called pivot, takes the place of the node in its
+    /// parent, or becomes the root node. The node becomes the first
+    /// child of the pivot and the previous first child of the pivot
+    /// takes the place of the pivot among the children of the node.
+    /// With a binary tree and the right child as pivot, this is the
+    /// usual left rotation of balanced search trees.
+    ///
+    /// ```ignore
+    /// // 1 => [2, 3 => [4, 5]] becomes 3 => [1 => [2, 4], 5]
+    /// let pivot = tree.rotate_left(node, 1)?;
+    /// ```
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the root of the subtree.
+    /// * `child_index` - Position of the pivot among the children of the node.
+    ///
+    /// *Returns:* The [NodeRef] of the pivot, which is the new root of
+    ///            the subtree. Returns an error if the node does not exist,
+    ///            has no child at `child_index` or if the moved nodes
+    ///            are locked.
+    pub fn rotate_left(&mut self, node_ref: NodeRef, child_index: usize) -> Result<NodeRef> {
+        self.rotate(node_ref, child_index, true)
+    }
+
+    /// Rotate a subtree to the right around one of the children of
+    /// its root.
+    ///
+    /// This is the mirror image of [Tree::rotate_left]: the node becomes
+    /// the last child of the pivot and the previous last child of the
+    /// pivot takes the place of the pivot among the children of the node.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the root of the subtree.
+    /// * `child_index` - Position of the pivot among the children of the node.
+    ///
+    /// *Returns:* The [NodeRef] of the pivot, which is the new root of
+    ///            the subtree. Returns an error if the node does not exist,
+    ///            has no child at `child_index` or if the moved nodes
+    ///            are locked.
+    pub fn rotate_right(&mut self, node_ref: NodeRef, child_index: usize) -> Result<NodeRef> {
+        self.rotate(node_ref, child_index, false)
+    }
+
+    /// Swap the positions of two disjoint subtrees.
+    ///
+    /// Each node takes the place of the other among the children
+    /// of its parent, together with all its descendants. If one of
+    /// the nodes is the root node or has no parent, the other one
+    /// takes that role instead.
+    ///
+    /// *Arguments:*
+    /// * `a_ref` - [NodeRef] of the root of the first subtree.
+    /// * `b_ref` - [NodeRef] of the root of the second subtree.
+    ///
+    /// *Returns:* Result indicating whether the operations was successful.
+    ///            Returns an error if a node does not exist, if one
+    ///            subtree contains the other or if a node is locked.
+    pub fn swap_subtrees(&mut self, a_ref: NodeRef, b_ref: NodeRef) -> Result<()> {
+        let context = |error: TreeError| error.during("swap_subtrees").involving(&[a_ref, b_ref]);
+        if a_ref == b_ref {
+            return self.ancestors(a_ref).map(|_| ()).map_err(context);
+        }
+        if self
+            .ancestors(a_ref)
+            .map_err(context)?
+            .any(|ancestor| ancestor == b_ref)
+            || self
+                .ancestors(b_ref)
+                .map_err(context)?
+                .any(|ancestor| ancestor == a_ref)
+        {
+            return Err(context(invalid_input(
+                "Cannot swap a subtree with one containing it.",
+            )));
+        }
+        self.check_unlocked(a_ref).map_err(context)?;
+        self.check_unlocked(b_ref).map_err(context)?;
+
+        let parent_of = |tree: &Self, node_ref: NodeRef| {
+            tree.get_node(node_ref)
+                .unwrap()
+                .parent
+                .filter(|parent_ref| tree.get_node(*parent_ref).is_some())
+        };
+        let a_parent = parent_of(self, a_ref);
+        let b_parent = parent_of(self, b_ref);
+
+        let swapped = |tree: &Self, parent_ref: NodeRef| -> Vec<NodeRef> {
+            tree.live_children(parent_ref)
+                .map(|child_ref| match child_ref {
+                    child_ref if child_ref == a_ref => b_ref,
+                    child_ref if child_ref == b_ref => a_ref,
+                    child_ref => child_ref,
+                })
+                .collect()
+        };
+        let a_unreachable = self.is_unreachable(a_ref);
+        if a_unreachable != self.is_unreachable(b_ref) {
+            let size = |tree: &Self, node_ref| tree.depth_first_of(node_ref, true).unwrap().count();
+            let (a_size, b_size) = (size(self, a_ref), size(self, b_ref));
+            self.unreachable = if a_unreachable {
+                self.unreachable + b_size - a_size
+            } else {
+                self.unreachable + a_size - b_size
+            };
+        }
+
+        let mut lists: Vec<(NodeRef, Vec<NodeRef>)> = Vec::new();
+        for parent_ref in a_parent.iter().chain(b_parent.iter()) {
+            if lists.iter().all(|(listed_ref, _)| listed_ref != parent_ref) {
+                lists.push((*parent_ref, swapped(self, *parent_ref)));
+            }
+        }
+
+        // Unlink both nodes before relinking them, as a node
+        // can only be in one child list at a time.
+        self.unlink_child(a_ref);
+        self.unlink_child(b_ref);
+        for (parent_ref, children) in lists {
+            self.set_child_list(parent_ref, children);
+        }
+
+        if self.root == Some(a_ref) {
+            self.root = Some(b_ref);
+        } else if self.root == Some(b_ref) {
+            self.root = Some(a_ref);
+        }
+        self.get_node_mut(a_ref).unwrap().parent = b_parent;
+        self.get_node_mut(b_ref).unwrap().parent = a_parent;
+        Ok(())
+    }
+
+    fn rotate(&mut self, node_ref: NodeRef, child_index: usize, left: bool) -> Result<NodeRef> {
+        let context = |error: TreeError| error.during("rotate").involving(&[node_ref]);
+        let parent_ref = match self.get_node(node_ref) {
+            None => return Err(context(node_not_found(node_ref))),
+            Some(node) => node.parent,
+        };
+
+        let mut children: Vec<NodeRef> = self.live_children(node_ref).collect();
+        let pivot_ref = match children.get(child_index) {
+            None => return Err(context(invalid_input("Child index out of range."))),
+            Some(pivot_ref) => *pivot_ref,
+        };
+        self.check_unlocked(pivot_ref).map_err(context)?;
+
+        let mut pivot_children: Vec<NodeRef> = self.live_children(pivot_ref).collect();
+        let inner_ref = if left {
+            (!pivot_children.is_empty()).then(|| pivot_children.remove(0))
+        } else {
+            pivot_children.pop()
+        };
+        if let Some(inner_ref) = inner_ref {
+            self.check_unlocked(inner_ref).map_err(context)?;
+        }
+
+        // Take the pivot and the inner child out of their lists first,
+        // so that they can be linked again at their new positions.
+        self.set_child_list(pivot_ref, pivot_children.clone());
+        match inner_ref {
+            None => {
+                children.remove(child_index);
+            }
+            Some(inner_ref) => children[child_index] = inner_ref,
+        }
+        self.set_child_list(node_ref, children);
+
+        match parent_ref {
+            Some(parent_ref) if self.get_node(parent_ref).is_some() => {
+                let siblings = self
+                    .live_children(parent_ref)
+                    .map(|child_ref| {
+                        if child_ref == node_ref {
+                            pivot_ref
+                        } else {
+                            child_ref
+                        }
+                    })
+                    .collect();
+                self.set_child_list(parent_ref, siblings);
+            }
+            _ => {
+                if self.root == Some(node_ref) {
+                    self.root = Some(pivot_ref);
+                }
+            }
+        }
+
+        if left {
+            pivot_children.insert(0, node_ref);
+        } else {
+            pivot_children.push(node_ref);
+        }
+        self.set_child_list(pivot_ref, pivot_children);
+
+        self.get_node_mut(pivot_ref).unwrap().parent = parent_ref;
+        self.get_node_mut(node_ref).unwrap().parent = Some(pivot_ref);
+        if let Some(inner_ref) = inner_ref {
+            self.get_node_mut(inner_ref).unwrap().parent = Some(node_ref);
+        }
+        Ok(pivot_ref)
+    }
+
+    fn reverse_children(&mut self, node_ref: NodeRef) {
+        let mut children: Vec<NodeRef> = self.live_children(node_ref).collect();
+        children.reverse();
+        self.set_child_list(node_ref, children);
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
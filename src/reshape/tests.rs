@@ -0,0 +1,165 @@
+use super::*;
+use crate::{tree, ChildStorage, ErrorKind};
+
+fn values(tree: &Tree<i32>) -> Vec<i32> {
+    tree.depth_first(true)
+        .unwrap()
+        .map(|node_ref| *tree.get(node_ref).unwrap())
+        .collect()
+}
+
+#[test]
+fn mirror() {
+    let mut tree = tree!(1 => [2 => [3, 4], 5 => [6, 7]]);
+    let root = tree.get_root_ref().unwrap();
+    tree.mirror(root).unwrap();
+    assert_eq!(values(&tree), vec![1, 5, 7, 6, 2, 4, 3]);
+
+    let mut linked = Tree::with_child_storage(ChildStorage::LinkedList);
+    let root = linked.root(1).unwrap();
+    let child = linked.child_node(root, 2).unwrap();
+    linked.child_node(child, 3).unwrap();
+    linked.child_node(child, 4).unwrap();
+    linked.child_node(root, 5).unwrap();
+    linked.mirror(root).unwrap();
+    assert_eq!(values(&linked), vec![1, 5, 2, 4, 3]);
+}
+
+#[test]
+fn mirror_shallow() {
+    let mut tree = tree!(1 => [2 => [3, 4], 5 => [6, 7]]);
+    let root = tree.get_root_ref().unwrap();
+    tree.mirror_shallow(root).unwrap();
+    assert_eq!(values(&tree), vec![1, 5, 6, 7, 2, 3, 4]);
+
+    let locked = tree.get_children(root).unwrap().next().unwrap();
+    tree.lock_subtree(locked).unwrap();
+    assert_eq!(tree.mirror(root).unwrap_err().kind(), ErrorKind::Locked);
+    assert_eq!(values(&tree), vec![1, 5, 6, 7, 2, 3, 4]);
+}
+
+#[test]
+fn rotate_left() {
+    let mut tree = tree!(0 => [1 => [2, 3 => [4, 5]]]);
+    let root = tree.get_root_ref().unwrap();
+    let node = tree.get_children(root).unwrap().next().unwrap();
+    let pivot = tree.rotate_left(node, 1).unwrap();
+
+    assert_eq!(tree.get(pivot), Some(&3));
+    assert_eq!(tree.get_parent(pivot).unwrap(), Some(root));
+    assert_eq!(tree.get_parent(node).unwrap(), Some(pivot));
+    assert_eq!(values(&tree), vec![0, 3, 1, 2, 4, 5]);
+
+    assert!(tree.rotate_left(pivot, 2).is_err());
+    assert!(tree.rotate_left(node, 2).is_err());
+}
+
+#[test]
+fn rotate_right() {
+    let mut tree = Tree::with_child_storage(ChildStorage::LinkedList);
+    let root = tree.root(1).unwrap();
+    let pivot = tree.child_node(root, 2).unwrap();
+    tree.child_node(pivot, 3).unwrap();
+    tree.child_node(pivot, 4).unwrap();
+    tree.child_node(root, 5).unwrap();
+
+    assert_eq!(tree.rotate_right(root, 0).unwrap(), pivot);
+    assert_eq!(tree.get_root_ref(), Some(pivot));
+    assert_eq!(tree.get_parent(pivot).unwrap(), None);
+    assert_eq!(values(&tree), vec![2, 3, 1, 4, 5]);
+
+    // Rotating back restores the original shape
+    assert_eq!(tree.rotate_left(pivot, 1).unwrap(), root);
+    assert_eq!(values(&tree), vec![1, 2, 3, 4, 5]);
+
+    tree.lock_subtree(root).unwrap();
+    assert_eq!(
+        tree.rotate_right(root, 0).unwrap_err().kind(),
+        ErrorKind::Locked
+    );
+}
+
+#[test]
+fn swap_subtrees() {
+    let mut tree = tree!(1 => [2 => [3, 4], 5 => [6, 7]]);
+    let order: Vec<NodeRef> = tree.depth_first(true).unwrap().collect();
+    let (two, three, five, seven) = (order[1], order[2], order[4], order[6]);
+
+    tree.swap_subtrees(three, seven).unwrap();
+    assert_eq!(values(&tree), vec![1, 2, 7, 4, 5, 6, 3]);
+    assert_eq!(tree.get_parent(seven).unwrap(), Some(two));
+    assert_eq!(tree.get_parent(three).unwrap(), Some(five));
+
+    tree.swap_subtrees(two, five).unwrap();
+    assert_eq!(values(&tree), vec![1, 5, 6, 3, 2, 7, 4]);
+
+    let error = tree.swap_subtrees(five, three).unwrap_err();
+    assert_eq!(error.operation(), Some("swap_subtrees"));
+    assert!(tree.swap_subtrees(order[0], seven).is_err());
+    assert_eq!(values(&tree), vec![1, 5, 6, 3, 2, 7, 4]);
+}
+
+#[test]
+fn swap_subtrees_detached() {
+    let mut tree = Tree::with_child_storage(ChildStorage::LinkedList);
+    let root = tree.root(1).unwrap();
+    tree.child_node(root, 2).unwrap();
+    let child = tree.child_node(root, 3).unwrap();
+    tree.child_node(root, 4).unwrap();
+    let detached = tree.node(5);
+    tree.child_node(detached, 6).unwrap();
+
+    tree.swap_subtrees(detached, child).unwrap();
+    assert_eq!(values(&tree), vec![1, 2, 5, 6, 4]);
+    assert_eq!(tree.get_parent(child).unwrap(), None);
+
+    tree.swap_subtrees(root, child).unwrap();
+    assert_eq!(tree.get_root_ref(), Some(child));
+    assert_eq!(values(&tree), vec![3]);
+}
+
+#[test]
+fn normalize() {
+    let mut tree = tree!(1 => [3 => [5, 4], 2, 3 => [4]]);
+    let mut other = tree!(1 => [3 => [4], 3 => [4, 5], 2]);
+    tree.normalize(i32::cmp).unwrap();
+    other.normalize(i32::cmp).unwrap();
+    assert_eq!(values(&tree), vec![1, 2, 3, 4, 3, 4, 5]);
+    assert!(tree.structural_eq(&other));
+
+    let mut empty = Tree::<i32>::new();
+    empty.normalize(i32::cmp).unwrap();
+
+    let root = tree.get_root_ref().unwrap();
+    tree.lock_subtree(root).unwrap();
+    assert_eq!(
+        tree.normalize(|a, b| b.cmp(a)).unwrap_err().kind(),
+        ErrorKind::Locked
+    );
+}
+
+#[test]
+fn collapse_unary_chains() {
+    let mut tree = tree!(
+        "src" => ["a" => ["b" => ["c" => ["x", "y"]]], "d" => ["z"]]
+    )
+    .map(|value| value.to_string())
+    .unwrap();
+    let joined = |upper: &mut String, lower: String| {
+        upper.push('/');
+        upper.push_str(&lower);
+    };
+    assert_eq!(tree.collapse_unary_chains(joined).unwrap(), 2);
+    assert_eq!(tree.len(), 6);
+
+    let labels: Vec<&str> = tree
+        .depth_first(true)
+        .unwrap()
+        .map(|node_ref| tree.get(node_ref).unwrap().as_str())
+        .collect();
+    assert_eq!(labels, vec!["src", "a/b/c", "x", "y", "d", "z"]);
+
+    let mut chain = tree!(1 => [2 => [3 => [4]]]);
+    assert_eq!(chain.collapse_unary_chains(|a, b| *a += b).unwrap(), 2);
+    assert_eq!(values(&chain), vec![6, 4]);
+}
@@ -0,0 +1,175 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::iter::FusedIterator;
+
+use super::{node_not_found, NodeRef, Result, Tree};
+
+/// Reusable buffers for traversals.
+///
+/// Traversals allocate a stack or a queue for the nodes still to visit.
+/// When many traversals run in a tight loop, passing the same scratch
+/// buffers to [Tree::depth_first_in] and [Tree::breadth_first_in] avoids
+/// allocating them again for each traversal. Keeping one per thread, for
+/// example in a `thread_local!`, works for parallel workloads.
+///
+/// ```ignore
+/// let mut scratch = TraversalScratch::new();
+/// for query in queries {
+///     let found = tree.depth_first_in(query.start, &mut scratch)?.find(|n| matches(n));
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TraversalScratch {
+    stack: Vec<NodeRef>,
+    queue: VecDeque<NodeRef>,
+}
+
+impl TraversalScratch {
+    /// Create empty buffers, which grow as needed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create buffers able to hold the given number of
+    /// nodes without reallocating.
+    ///
+    /// *Arguments:*
+    /// * `capacity` - Number of node references of each buffer.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            stack: Vec::with_capacity(capacity),
+            queue: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Release the memory held by the buffers.
+    pub fn shrink(&mut self) {
+        self.stack = Vec::new();
+        self.queue = VecDeque::new();
+    }
+}
+
+impl<T> Tree<T> {
+    /// Get an iterator traversing a node and its descendants in
+    /// depth-first order, using the given buffers.
+    ///
+    /// The order is the same as [Tree::depth_first_of].
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the starting node, returned first.
+    /// * `scratch` - The [TraversalScratch] buffers to use.
+    ///
+    /// *Returns:* An iterator returning the node references.
+    ///            Returns error if the start node does not exist.
+    pub fn depth_first_in<'s>(
+        &self,
+        node_ref: NodeRef,
+        scratch: &'s mut TraversalScratch,
+    ) -> Result<ScratchDepthFirst<'_, 's, T>> {
+        if self.get_node(node_ref).is_none() {
+            return Err(node_not_found(node_ref));
+        }
+        scratch.stack.clear();
+        scratch.stack.push(node_ref);
+        Ok(ScratchDepthFirst {
+            tree: self,
+            stack: &mut scratch.stack,
+        })
+    }
+
+    /// Get an iterator traversing a node and its descendants in
+    /// breadth-first order, using the given buffers.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the starting node, returned first.
+    /// * `scratch` - The [TraversalScratch] buffers to use.
+    ///
+    /// *Returns:* An iterator returning the node references level by level.
+    ///            Returns error if the start node does not exist.
+    pub fn breadth_first_in<'s>(
+        &self,
+        node_ref: NodeRef,
+        scratch: &'s mut TraversalScratch,
+    ) -> Result<ScratchBreadthFirst<'_, 's, T>> {
+        if self.get_node(node_ref).is_none() {
+            return Err(node_not_found(node_ref));
+        }
+        scratch.queue.clear();
+        scratch.queue.push_back(node_ref);
+        Ok(ScratchBreadthFirst {
+            tree: self,
+            queue: &mut scratch.queue,
+        })
+    }
+}
+
+/// Depth-first traversal using the buffers of a [TraversalScratch],
+/// created by [Tree::depth_first_in].
+pub struct ScratchDepthFirst<'a, 's, T> {
+    tree: &'a Tree<T>,
+    stack: &'s mut Vec<NodeRef>,
+}
+
+impl<'a, 's, T> Iterator for ScratchDepthFirst<'a, 's, T> {
+    type Item = NodeRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node_ref = self.stack.pop()?;
+        let start = self.stack.len();
+        self.stack.extend(self.tree.live_children(node_ref));
+        self.stack[start..].reverse();
+        Some(node_ref)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let lower = self.stack.len();
+        (lower, Some(self.tree.len.max(lower)))
+    }
+}
+
+impl<'a, 's, T> FusedIterator for ScratchDepthFirst<'a, 's, T> {}
+
+impl<'a, 's, T> fmt::Debug for ScratchDepthFirst<'a, 's, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScratchDepthFirst")
+            .field("stack", &self.stack)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Breadth-first traversal using the buffers of a [TraversalScratch],
+/// created by [Tree::breadth_first_in].
+pub struct ScratchBreadthFirst<'a, 's, T> {
+    tree: &'a Tree<T>,
+    queue: &'s mut VecDeque<NodeRef>,
+}
+
+impl<'a, 's, T> Iterator for ScratchBreadthFirst<'a, 's, T> {
+    type Item = NodeRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node_ref = self.queue.pop_front()?;
+        self.queue.extend(self.tree.live_children(node_ref));
+        Some(node_ref)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let lower = self.queue.len();
+        (lower, Some(self.tree.len.max(lower)))
+    }
+}
+
+impl<'a, 's, T> FusedIterator for ScratchBreadthFirst<'a, 's, T> {}
+
+impl<'a, 's, T> fmt::Debug for ScratchBreadthFirst<'a, 's, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScratchBreadthFirst")
+            .field("queue", &self.queue)
+            .finish_non_exhaustive()
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
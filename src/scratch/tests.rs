@@ -0,0 +1,32 @@
+use super::*;
+
+#[test]
+fn traversals_reuse_scratch() {
+    let mut tree = Tree::new();
+    let root = tree.root(1).unwrap();
+    let a = tree.child_node(root, 2).unwrap();
+    tree.child_node(a, 3).unwrap();
+    tree.child_node(root, 4).unwrap();
+
+    let mut scratch = TraversalScratch::with_capacity(4);
+    let values = |nodes: Vec<NodeRef>| -> Vec<i32> {
+        nodes
+            .iter()
+            .map(|node_ref| *tree.get(*node_ref).unwrap())
+            .collect()
+    };
+
+    let depth_first: Vec<NodeRef> = tree.depth_first_in(root, &mut scratch).unwrap().collect();
+    assert_eq!(values(depth_first), vec![1, 2, 3, 4]);
+    let first = tree.depth_first_in(root, &mut scratch).unwrap().next();
+    assert_eq!(first, Some(root));
+    let again: Vec<NodeRef> = tree.depth_first_in(a, &mut scratch).unwrap().collect();
+    assert_eq!(values(again), vec![2, 3]);
+
+    let breadth_first: Vec<NodeRef> = tree.breadth_first_in(root, &mut scratch).unwrap().collect();
+    assert_eq!(values(breadth_first), vec![1, 2, 4, 3]);
+    assert!(scratch.stack.capacity() >= 4);
+
+    tree.remove(a).unwrap();
+    assert!(tree.depth_first_in(a, &mut scratch).is_err());
+}
@@ -0,0 +1,94 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use super::{NodeRef, Result, Tree};
+
+impl<T> Tree<T> {
+    /// Select the nodes of a subtree with the largest keys.
+    ///
+    /// Only `k` candidates are kept in memory while traversing, so this
+    /// is cheaper than collecting and sorting the whole subtree.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the root of the subtree, included
+    ///   in the selection.
+    /// * `k` - Maximum number of nodes to select.
+    /// * `key_fn` - Function computing the key of the content of a node.
+    ///
+    /// *Returns:* Up to `k` node references sorted by decreasing key,
+    ///            nodes with equal keys in depth-first order. Returns
+    ///            error if the node does not exist.
+    pub fn k_largest<K: Ord>(
+        &self,
+        node_ref: NodeRef,
+        k: usize,
+        key_fn: impl Fn(&T) -> K,
+    ) -> Result<Vec<NodeRef>> {
+        let nodes = self.depth_first_of(node_ref, true)?;
+        if k == 0 {
+            return Ok(Vec::new());
+        }
+
+        // Min-heap of the candidates, where later nodes are evicted
+        // first among equal keys.
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        for (order, node_ref) in nodes.enumerate() {
+            let key = key_fn(self.get(node_ref).unwrap());
+            heap.push(Reverse((key, Reverse(order), node_ref)));
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+
+        Ok(heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((_, _, node_ref))| node_ref)
+            .collect())
+    }
+
+    /// Find the node of a subtree with the smallest key.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the root of the subtree, included
+    ///   in the search.
+    /// * `key_fn` - Function computing the key of the content of a node.
+    ///
+    /// *Returns:* The first node in depth-first order with the smallest
+    ///            key. Returns error if the node does not exist.
+    pub fn min_by_key<K: Ord>(
+        &self,
+        node_ref: NodeRef,
+        key_fn: impl Fn(&T) -> K,
+    ) -> Result<NodeRef> {
+        Ok(self
+            .depth_first_of(node_ref, true)?
+            .min_by_key(|node_ref| key_fn(self.get(*node_ref).unwrap()))
+            .unwrap())
+    }
+
+    /// Find the node of a subtree with the largest key.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the root of the subtree, included
+    ///   in the search.
+    /// * `key_fn` - Function computing the key of the content of a node.
+    ///
+    /// *Returns:* The first node in depth-first order with the largest
+    ///            key. Returns error if the node does not exist.
+    pub fn max_by_key<K: Ord>(
+        &self,
+        node_ref: NodeRef,
+        key_fn: impl Fn(&T) -> K,
+    ) -> Result<NodeRef> {
+        Ok(self
+            .depth_first_of(node_ref, true)?
+            .min_by_key(|node_ref| Reverse(key_fn(self.get(*node_ref).unwrap())))
+            .unwrap())
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
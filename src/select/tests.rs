@@ -0,0 +1,36 @@
+use super::*;
+
+fn scores() -> (Tree<i32>, NodeRef, Vec<NodeRef>) {
+    let mut tree = Tree::new();
+    let root = tree.root(5).unwrap();
+    let a = tree.child_node(root, 8).unwrap();
+    let b = tree.child_node(a, 1).unwrap();
+    let c = tree.child_node(root, 8).unwrap();
+    let d = tree.child_node(c, 9).unwrap();
+    (tree, root, vec![a, b, c, d])
+}
+
+#[test]
+fn k_largest() {
+    let (tree, root, nodes) = scores();
+
+    assert_eq!(
+        tree.k_largest(root, 3, |score| *score).unwrap(),
+        vec![nodes[3], nodes[0], nodes[2]]
+    );
+    assert_eq!(
+        tree.k_largest(nodes[0], 5, |score| *score).unwrap(),
+        vec![nodes[0], nodes[1]]
+    );
+    assert!(tree.k_largest(root, 0, |score| *score).unwrap().is_empty());
+}
+
+#[test]
+fn min_max_by_key() {
+    let (tree, root, nodes) = scores();
+
+    assert_eq!(tree.min_by_key(root, |score| *score), Ok(nodes[1]));
+    assert_eq!(tree.max_by_key(root, |score| *score), Ok(nodes[3]));
+    assert_eq!(tree.max_by_key(nodes[0], |score| -score), Ok(nodes[1]));
+    assert_eq!(tree.max_by_key(root, |score| *score % 4 == 0), Ok(nodes[0]));
+}
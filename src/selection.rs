@@ -0,0 +1,156 @@
+use std::collections::HashSet;
+
+use super::{NodeRef, Result, Tree};
+
+/// Set of selected nodes, with the usual semantics of tree views.
+///
+/// The selection only stores node references, so it can be kept next
+/// to the tree and used with any content type. Methods needing the
+/// structure take the tree as argument.
+///
+/// ```ignore
+/// let mut selection = Selection::new();
+/// selection.add_subtree(&tree, folder)?;
+/// selection.add(file);
+///
+/// // Delete each selected subtree once, top-down
+/// for node_ref in selection.normalized(&tree).in_document_order(&tree)? {
+///     // ...
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Selection {
+    nodes: HashSet<NodeRef>,
+}
+
+impl Selection {
+    /// Create an empty selection.
+    ///
+    /// *Returns:* The new selection.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Select a node.
+    ///
+    /// *Returns:* Whether the node was not selected before.
+    pub fn add(&mut self, node_ref: NodeRef) -> bool {
+        self.nodes.insert(node_ref)
+    }
+
+    /// Deselect a node.
+    ///
+    /// *Returns:* Whether the node was selected.
+    pub fn remove(&mut self, node_ref: NodeRef) -> bool {
+        self.nodes.remove(&node_ref)
+    }
+
+    /// Check whether a node is selected.
+    pub fn contains(&self, node_ref: NodeRef) -> bool {
+        self.nodes.contains(&node_ref)
+    }
+
+    /// Get the number of selected nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Check whether no node is selected.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Deselect all nodes.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+    }
+
+    /// Iterate over the selected nodes, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = NodeRef> + '_ {
+        self.nodes.iter().copied()
+    }
+
+    /// Select a node and all its descendants.
+    ///
+    /// *Arguments:*
+    /// * `tree` - The tree containing the node.
+    /// * `node_ref` - [NodeRef] of the root of the subtree.
+    ///
+    /// *Returns:* Result indicating whether the operations was successful.
+    ///            Returns error if the node does not exist.
+    pub fn add_subtree<T>(&mut self, tree: &Tree<T>, node_ref: NodeRef) -> Result<()> {
+        self.nodes.extend(tree.depth_first_of(node_ref, true)?);
+        Ok(())
+    }
+
+    /// Deselect a node and all its descendants.
+    ///
+    /// *Arguments:*
+    /// * `tree` - The tree containing the node.
+    /// * `node_ref` - [NodeRef] of the root of the subtree.
+    ///
+    /// *Returns:* Result indicating whether the operations was successful.
+    ///            Returns error if the node does not exist.
+    pub fn remove_subtree<T>(&mut self, tree: &Tree<T>, node_ref: NodeRef) -> Result<()> {
+        for descendant_ref in tree.depth_first_of(node_ref, true)? {
+            self.nodes.remove(&descendant_ref);
+        }
+        Ok(())
+    }
+
+    /// Get the selection without the nodes having a selected ancestor.
+    ///
+    /// This is the set of subtrees to act upon, e.g. when moving or
+    /// deleting the selection. Nodes which do not exist in the tree
+    /// anymore are dropped as well.
+    ///
+    /// *Arguments:*
+    /// * `tree` - The tree containing the nodes.
+    ///
+    /// *Returns:* The normalized selection.
+    pub fn normalized<T>(&self, tree: &Tree<T>) -> Selection {
+        let nodes = self
+            .nodes
+            .iter()
+            .filter(|node_ref| match tree.ancestors(**node_ref) {
+                Err(_) => false,
+                Ok(mut ancestors) => !ancestors.any(|ancestor| self.nodes.contains(&ancestor)),
+            })
+            .copied()
+            .collect();
+        Selection { nodes }
+    }
+
+    /// Get the selected nodes in depth-first order.
+    ///
+    /// *Arguments:*
+    /// * `tree` - The tree containing the nodes.
+    ///
+    /// *Returns:* The selected nodes, sorted as by [Tree::sort_refs_in_traversal_order].
+    ///            Returns an error if a node does not exist or if the nodes
+    ///            do not share a common ancestor.
+    pub fn in_document_order<T>(&self, tree: &Tree<T>) -> Result<Vec<NodeRef>> {
+        let mut nodes: Vec<NodeRef> = self.nodes.iter().copied().collect();
+        tree.sort_refs_in_traversal_order(&mut nodes)?;
+        Ok(nodes)
+    }
+}
+
+impl Extend<NodeRef> for Selection {
+    fn extend<I: IntoIterator<Item = NodeRef>>(&mut self, iter: I) {
+        self.nodes.extend(iter);
+    }
+}
+
+impl std::iter::FromIterator<NodeRef> for Selection {
+    fn from_iter<I: IntoIterator<Item = NodeRef>>(iter: I) -> Self {
+        Selection {
+            nodes: iter.into_iter().collect(),
+        }
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,49 @@
+use super::*;
+use crate::tree;
+
+#[test]
+fn subtrees() {
+    let tree = tree!(1 => [2 => [3, 4], 5 => [6]]);
+    let order: Vec<NodeRef> = tree.depth_first(true).unwrap().collect();
+
+    let mut selection = Selection::new();
+    selection.add_subtree(&tree, order[1]).unwrap();
+    assert_eq!(selection.len(), 3);
+    assert!(selection.contains(order[3]));
+
+    selection.add(order[5]);
+    selection.remove_subtree(&tree, order[1]).unwrap();
+    assert_eq!(selection.iter().collect::<Vec<_>>(), vec![order[5]]);
+    assert!(selection.add_subtree(&tree, NodeRef::new(100)).is_err());
+
+    assert!(selection.remove(order[5]));
+    assert!(selection.is_empty());
+}
+
+#[test]
+fn normalized_in_document_order() {
+    let mut tree = tree!(1 => [2 => [3, 4], 5 => [6]]);
+    let order: Vec<NodeRef> = tree.depth_first(true).unwrap().collect();
+
+    let mut selection: Selection = vec![order[5], order[3], order[1], order[4]]
+        .into_iter()
+        .collect();
+    assert_eq!(
+        selection.in_document_order(&tree).unwrap(),
+        vec![order[1], order[3], order[4], order[5]]
+    );
+
+    let normalized = selection.normalized(&tree);
+    assert_eq!(
+        normalized.in_document_order(&tree).unwrap(),
+        vec![order[1], order[4]]
+    );
+
+    tree.remove(order[4]).unwrap();
+    selection.clear();
+    selection.extend(vec![order[4], order[2]]);
+    assert_eq!(
+        selection.normalized(&tree).iter().collect::<Vec<_>>(),
+        vec![order[2]]
+    );
+}
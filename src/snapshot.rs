@@ -0,0 +1,176 @@
+use std::iter;
+use std::ops::Range;
+use std::sync::Arc;
+
+use super::{not_found, NodeRef, Result, Tree};
+
+/// Immutable copy of the structure of a [Tree], without the content.
+///
+/// A snapshot can be sent to another thread and traversed there, for
+/// example to run a background analysis, while the original tree keeps
+/// being modified. The snapshot uses the same [NodeRef] values as the
+/// tree at the time it was taken, so results can be mapped back to the
+/// tree as long as the referenced nodes were not removed meanwhile.
+///
+/// Cloning a snapshot is cheap, as the structure is shared.
+///
+/// Create one with [Tree::snapshot].
+#[derive(Debug, Clone)]
+pub struct TreeSnapshot {
+    inner: Arc<Structure>,
+}
+
+#[derive(Debug)]
+struct Structure {
+    root: Option<NodeRef>,
+    len: usize,
+    alive: Vec<bool>,
+    parents: Vec<Option<NodeRef>>,
+    /// Range of each slot in `children`.
+    offsets: Vec<Range<usize>>,
+    children: Vec<NodeRef>,
+}
+
+impl<T> Tree<T> {
+    /// Copy the structure of the tree into a [TreeSnapshot].
+    ///
+    /// Only the parent and children of each live node are copied, in
+    /// linear time, while the content stays in the tree. References to
+    /// removed nodes are left out of the child lists.
+    ///
+    /// *Returns:* The snapshot.
+    pub fn snapshot(&self) -> TreeSnapshot {
+        let mut structure = Structure {
+            root: self.root,
+            len: self.len,
+            alive: Vec::with_capacity(self.nodes.len()),
+            parents: Vec::with_capacity(self.nodes.len()),
+            offsets: Vec::with_capacity(self.nodes.len()),
+            children: Vec::with_capacity(self.len),
+        };
+
+        for (id, node) in self.nodes.iter().enumerate() {
+            let start = structure.children.len();
+            if let Some(node) = node {
+                structure.children.extend(
+                    self.children_of_node(node)
+                        .filter(|child_ref| {
+                            self.get_node(**child_ref)
+                                .is_some_and(|child| child.parent == Some(NodeRef::new(id)))
+                        })
+                        .copied(),
+                );
+            }
+            structure.alive.push(node.is_some());
+            structure
+                .parents
+                .push(node.as_ref().and_then(|node| node.parent));
+            structure.offsets.push(start..structure.children.len());
+        }
+
+        TreeSnapshot {
+            inner: Arc::new(structure),
+        }
+    }
+}
+
+impl TreeSnapshot {
+    /// Get the number of live nodes when the snapshot was taken.
+    pub fn len(&self) -> usize {
+        self.inner.len
+    }
+
+    /// Check whether the snapshot contains no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.inner.len == 0
+    }
+
+    /// Check whether a node was alive when the snapshot was taken.
+    pub fn contains(&self, node_ref: NodeRef) -> bool {
+        self.inner.alive.get(node_ref.index()) == Some(&true)
+    }
+
+    /// Get the reference of the root node.
+    pub fn get_root_ref(&self) -> Option<NodeRef> {
+        self.inner.root
+    }
+
+    /// Get reference to the parent node.
+    ///
+    /// *Arguments:*
+    /// * `child_ref` - [NodeRef] of the child node.
+    ///
+    /// *Returns:* A reference to the parent node or `None` if no
+    ///            parent exists. Returns error if the node does not exist.
+    pub fn get_parent(&self, child_ref: NodeRef) -> Result<Option<NodeRef>> {
+        if !self.contains(child_ref) {
+            return Err(not_found("Child node does not exist."));
+        }
+        Ok(self.inner.parents[child_ref.index()])
+    }
+
+    /// Get the children of a node.
+    ///
+    /// *Arguments:*
+    /// * `parent_ref` - [NodeRef] of the parent node.
+    ///
+    /// *Returns:* Slice of node references to the children.
+    ///            Returns error if the parent reference is invalid.
+    pub fn get_children(&self, parent_ref: NodeRef) -> Result<&[NodeRef]> {
+        if !self.contains(parent_ref) {
+            return Err(not_found("Parent node does not exist."));
+        }
+        let range = self.inner.offsets[parent_ref.index()].clone();
+        Ok(&self.inner.children[range])
+    }
+
+    /// Get an iterator traversing a node and its descendants
+    /// in depth-first order.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the starting node.
+    /// * `include_start` - If true, iteration starts with the
+    ///   starting node instead of with the first child thereof.
+    ///
+    /// *Returns:* An iterator returning the node references in
+    ///            depth-first order. Returns error if the node does not exist.
+    pub fn depth_first_of(
+        &self,
+        node_ref: NodeRef,
+        include_start: bool,
+    ) -> Result<impl Iterator<Item = NodeRef> + '_> {
+        if !self.contains(node_ref) {
+            return Err(not_found("Node does not exist."));
+        }
+
+        let mut stack = vec![node_ref];
+        let nodes = iter::from_fn(move || {
+            let node_ref = stack.pop()?;
+            let range = self.inner.offsets[node_ref.index()].clone();
+            stack.extend(self.inner.children[range].iter().rev());
+            Some(node_ref)
+        });
+        Ok(nodes.skip(if include_start { 0 } else { 1 }))
+    }
+
+    /// Get an iterator traversing all nodes reachable from the
+    /// root in depth-first order.
+    ///
+    /// *Arguments:*
+    /// * `include_root` - If true, iteration starts with the
+    ///   root node instead of with the first child thereof.
+    ///
+    /// *Returns:* An iterator returning the node references in
+    ///            depth-first order. Returns error if no root node exist.
+    pub fn depth_first(&self, include_root: bool) -> Result<impl Iterator<Item = NodeRef> + '_> {
+        match self.inner.root {
+            None => Err(not_found("Root node does not exist.")),
+            Some(root_ref) => self.depth_first_of(root_ref, include_root),
+        }
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,40 @@
+use std::thread;
+
+use super::*;
+
+#[test]
+fn snapshot_is_independent() {
+    let mut tree = Tree::new();
+    let root = tree.root("root").unwrap();
+    let a = tree.child_node(root, "a").unwrap();
+    let b = tree.child_node(a, "b").unwrap();
+    let c = tree.child_node(root, "c").unwrap();
+
+    let snapshot = tree.snapshot();
+    tree.remove(a).unwrap();
+    tree.child_node(c, "d").unwrap();
+
+    let handle = {
+        let snapshot = snapshot.clone();
+        thread::spawn(move || snapshot.depth_first(true).unwrap().collect::<Vec<_>>())
+    };
+    assert_eq!(handle.join().unwrap(), vec![root, a, b, c]);
+    assert_eq!(snapshot.len(), 4);
+    assert_eq!(snapshot.get_parent(b), Ok(Some(a)));
+    assert_eq!(snapshot.get_children(c).unwrap(), &[]);
+    assert_eq!(tree.snapshot().get_children(root).unwrap(), &[c]);
+}
+
+#[test]
+fn snapshot_skips_stale_children() {
+    let mut tree = Tree::new();
+    let root = tree.root(1).unwrap();
+    let a = tree.child_node(root, 2).unwrap();
+    let b = tree.child_node(root, 3).unwrap();
+    tree.append_child(a, b).unwrap();
+
+    let snapshot = tree.snapshot();
+    assert_eq!(snapshot.get_children(root).unwrap(), &[a]);
+    assert_eq!(snapshot.get_children(a).unwrap(), &[b]);
+    assert!(snapshot.depth_first_of(NodeRef::new(5), true).is_err());
+}
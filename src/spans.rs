@@ -0,0 +1,73 @@
+use std::ops::Range;
+
+use super::{node_not_found, NodeRef, Result, Tree};
+
+impl<T> Tree<T> {
+    /// Record the source byte range of a node.
+    ///
+    /// Spans are stored in an array next to the nodes, so that parsers
+    /// can track where each node of a syntax tree comes from without
+    /// wrapping the content type. The span of a removed node is reset
+    /// when its slot is reused.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    /// * `span` - Byte range of the node in the source.
+    ///
+    /// *Returns:* Result indicating whether the operations was successful.
+    ///            Returns an error if the node does not exist.
+    pub fn set_span(&mut self, node_ref: NodeRef, span: Range<usize>) -> Result<()> {
+        if self.get_node(node_ref).is_none() {
+            return Err(node_not_found(node_ref));
+        }
+        if self.spans.len() <= node_ref.index() {
+            self.spans.resize(self.nodes.len(), None);
+        }
+        self.spans[node_ref.index()] = Some(span);
+        Ok(())
+    }
+
+    /// Get the source byte range of a node.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    ///
+    /// *Returns:* The span set with [Tree::set_span], or `None` if
+    ///            no span was set or the node does not exist.
+    pub fn span(&self, node_ref: NodeRef) -> Option<Range<usize>> {
+        self.get_node(node_ref)?;
+        self.spans.get(node_ref.index())?.clone()
+    }
+
+    /// Find the deepest node whose span contains a byte offset,
+    /// for example to find the syntax node under the cursor.
+    ///
+    /// Only nodes reachable from the root are searched, descending
+    /// into the children whose span contains the offset.
+    ///
+    /// *Arguments:*
+    /// * `offset` - Byte offset in the source.
+    ///
+    /// *Returns:* The deepest matching node, or `None` if the span of
+    ///            the root does not contain the offset.
+    pub fn node_at_offset(&self, offset: usize) -> Option<NodeRef> {
+        let contains = |node_ref: NodeRef| {
+            self.span(node_ref)
+                .is_some_and(|span| span.contains(&offset))
+        };
+
+        let mut node_ref = self.root.filter(|root_ref| contains(*root_ref))?;
+        while let Some(child_ref) = self
+            .children_of(node_ref)
+            .find(|child_ref| contains(**child_ref))
+        {
+            node_ref = *child_ref;
+        }
+        Some(node_ref)
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
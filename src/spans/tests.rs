@@ -0,0 +1,26 @@
+use super::*;
+
+#[test]
+fn spans() {
+    // Syntax tree of "1 + 23"
+    let mut tree = Tree::new();
+    let plus = tree.root("+").unwrap();
+    let one = tree.child_node(plus, "1").unwrap();
+    let twenty_three = tree.child_node(plus, "23").unwrap();
+    tree.set_span(plus, 0..6).unwrap();
+    tree.set_span(one, 0..1).unwrap();
+    tree.set_span(twenty_three, 4..6).unwrap();
+
+    assert_eq!(tree.span(twenty_three), Some(4..6));
+    assert_eq!(tree.node_at_offset(5), Some(twenty_three));
+    assert_eq!(tree.node_at_offset(2), Some(plus));
+    assert_eq!(tree.node_at_offset(6), None);
+
+    tree.remove(one).unwrap();
+    assert_eq!(tree.span(one), None);
+    tree.purge_tombstones_unchecked();
+    let reused = tree.node("2");
+    assert_eq!(reused, one);
+    assert_eq!(tree.span(reused), None);
+    assert!(tree.set_span(NodeRef::new(10), 0..1).is_err());
+}
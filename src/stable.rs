@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use super::{ErrorKind, NodeRef, Result, Tree, TreeError};
+
+/// Stable ids assigned to nodes with [Tree::node_with_id].
+///
+/// Ids of removed nodes are not dropped eagerly: lookups check that
+/// the node still exists and still has the id, and slots get their id
+/// cleared when they are reused.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct StableIds {
+    nodes: HashMap<u64, NodeRef>,
+    ids: Vec<Option<u64>>,
+}
+
+impl StableIds {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub(crate) fn get(&self, index: usize) -> Option<u64> {
+        self.ids.get(index).copied().flatten()
+    }
+
+    /// Forget the id of a slot, when a new node is created in it.
+    pub(crate) fn clear_slot(&mut self, index: usize) {
+        if let Some(id) = self.ids.get_mut(index).and_then(Option::take) {
+            self.forget(id, index);
+        }
+    }
+
+    /// Drop the mapping of an id, unless it was moved to another slot.
+    fn forget(&mut self, id: u64, index: usize) {
+        if self.nodes.get(&id).map(|node_ref| node_ref.index()) == Some(index) {
+            self.nodes.remove(&id);
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.nodes.clear();
+        self.ids.clear();
+    }
+
+    /// Move the ids to the new slots of the nodes, dropping
+    /// the ones of nodes mapped to `None`.
+    pub(crate) fn remap(&mut self, map: &[Option<NodeRef>]) {
+        if self.is_empty() {
+            return;
+        }
+        let old_ids = std::mem::take(&mut self.ids);
+        self.nodes.clear();
+        for (old_index, id) in old_ids.into_iter().enumerate() {
+            if let (Some(id), Some(Some(new_ref))) = (id, map.get(old_index)) {
+                self.set(*new_ref, id);
+            }
+        }
+    }
+
+    fn set(&mut self, node_ref: NodeRef, id: u64) {
+        if self.ids.len() <= node_ref.index() {
+            self.ids.resize(node_ref.index() + 1, None);
+        }
+        if let Some(old_id) = self.ids[node_ref.index()].replace(id) {
+            self.forget(old_id, node_ref.index());
+        }
+        if let Some(old_ref) = self.nodes.insert(id, node_ref) {
+            if old_ref.index() != node_ref.index() && self.get(old_ref.index()) == Some(id) {
+                self.ids[old_ref.index()] = None;
+            }
+        }
+    }
+}
+
+impl<T> Tree<T> {
+    /// Create a node without parent, identified by a stable id.
+    ///
+    /// Unlike node references, which depend on the order in which nodes
+    /// were created, stable ids are chosen by the caller. They are kept
+    /// by [Tree::optimize_layout] and written by [Tree::to_bytes], so
+    /// cross-references stored in documents survive maintenance and
+    /// reloading.
+    ///
+    /// *Arguments:*
+    /// * `id` - The stable id of the node.
+    /// * `content` - Content of the node.
+    ///
+    /// *Returns:* A [NodeRef] object referencing the node.
+    ///            Returns an error of kind [ErrorKind::InvalidInput]
+    ///            if another node has the same id.
+    pub fn node_with_id(&mut self, id: u64, content: T) -> Result<NodeRef> {
+        if let Some(node_ref) = self.node_by_id(id) {
+            return Err(id_in_use(node_ref));
+        }
+        let node_ref = self.node(content);
+        self.stable_ids.set(node_ref, id);
+        Ok(node_ref)
+    }
+
+    /// Assign a stable id to an existing node, replacing its
+    /// previous id if any.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    /// * `id` - The stable id of the node.
+    ///
+    /// *Returns:* Result indicating whether the operations was successful.
+    ///            Returns an error if the node does not exist or if
+    ///            another node has the same id.
+    pub fn set_stable_id(&mut self, node_ref: NodeRef, id: u64) -> Result<()> {
+        if self.get_node(node_ref).is_none() {
+            return Err(super::node_not_found(node_ref));
+        }
+        match self.node_by_id(id) {
+            Some(other_ref) if other_ref != node_ref => Err(id_in_use(other_ref)),
+            _ => {
+                self.stable_ids.set(node_ref, id);
+                Ok(())
+            }
+        }
+    }
+
+    /// Get the stable id of a node.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    ///
+    /// *Returns:* The id or `None` if the node does not exist
+    ///            or has no stable id.
+    pub fn stable_id(&self, node_ref: NodeRef) -> Option<u64> {
+        self.get_node(node_ref)?;
+        self.stable_ids.get(node_ref.index())
+    }
+
+    /// Find a node by its stable id.
+    ///
+    /// *Arguments:*
+    /// * `id` - The stable id of the node.
+    ///
+    /// *Returns:* The node or `None` if no existing node has the id.
+    pub fn node_by_id(&self, id: u64) -> Option<NodeRef> {
+        let node_ref = *self.stable_ids.nodes.get(&id)?;
+        self.get_node(node_ref)?;
+        Some(node_ref)
+    }
+}
+
+fn id_in_use(node_ref: NodeRef) -> TreeError {
+    TreeError::with_kind(ErrorKind::InvalidInput, "Stable id is already used.")
+        .involving(&[node_ref])
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,66 @@
+use super::*;
+
+#[test]
+fn stable_ids() {
+    let mut tree = Tree::new();
+    let root = tree.node_with_id(10, "root").unwrap();
+    tree.set_root(root, false).unwrap();
+    let child = tree.node_with_id(20, "child").unwrap();
+    tree.append_child(root, child).unwrap();
+
+    assert_eq!(tree.node_by_id(20), Some(child));
+    assert_eq!(tree.stable_id(root), Some(10));
+    let error = tree.node_with_id(20, "other").unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    assert_eq!(error.nodes(), &[child]);
+
+    let other = tree.child_node(root, "other").unwrap();
+    assert!(tree.set_stable_id(other, 10).is_err());
+    tree.set_stable_id(other, 30).unwrap();
+    tree.set_stable_id(other, 31).unwrap();
+    assert_eq!(tree.node_by_id(30), None);
+
+    tree.remove(child).unwrap();
+    assert_eq!(tree.node_by_id(20), None);
+    let reused = tree.node("reused");
+    assert_eq!(tree.stable_id(reused), None);
+    tree.node_with_id(20, "again").unwrap();
+}
+
+#[test]
+fn stable_id_moved_before_slot_reuse() {
+    let mut tree = Tree::new();
+    let root = tree.root("root").unwrap();
+    let a = tree.node_with_id(7, "a").unwrap();
+    tree.append_child(root, a).unwrap();
+    let x = tree.child_node(root, "x").unwrap();
+
+    tree.remove(a).unwrap();
+    tree.purge_tombstones_unchecked();
+    tree.set_stable_id(x, 7).unwrap();
+    tree.child_node(root, "c").unwrap();
+
+    assert_eq!(tree.node_by_id(7), Some(x));
+    assert_eq!(tree.stable_id(x), Some(7));
+}
+
+#[test]
+fn stable_ids_survive_compaction_and_serialization() {
+    let mut tree = Tree::new();
+    let root = tree.root(String::from("root")).unwrap();
+    let removed = tree.child_node(root, String::from("removed")).unwrap();
+    let node = tree.node_with_id(7, String::from("node")).unwrap();
+    tree.append_child(root, node).unwrap();
+    tree.remove(removed).unwrap();
+
+    let remap = tree.optimize_layout();
+    let node = remap.get(node).unwrap();
+    assert_eq!(tree.node_by_id(7), Some(node));
+
+    let bytes = tree.to_bytes(|content, out| out.extend_from_slice(content.as_bytes()));
+    let loaded =
+        Tree::from_bytes(&bytes, |bytes| std::str::from_utf8(bytes).map(String::from)).unwrap();
+    let loaded_node = loaded.node_by_id(7).unwrap();
+    assert_eq!(loaded.get(loaded_node).map(String::as_str), Some("node"));
+    assert_eq!(loaded.stable_id(loaded.get_root_ref().unwrap()), None);
+}
@@ -0,0 +1,360 @@
+use std::fmt;
+use std::iter::FusedIterator;
+use std::slice::Iter;
+
+use super::{Node, NodeRef, Tree};
+
+/// How a [Tree] stores the children of its nodes.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum ChildStorage {
+    /// Each node owns a vector of child references. Children can be
+    /// accessed by index in constant time, but each node with children
+    /// needs a heap allocation.
+    #[default]
+    Vec,
+    /// Each node links to its first and last child, and to its previous
+    /// and next sibling. This needs no allocation besides the node itself,
+    /// which saves memory when nodes have few children, while accessing
+    /// a child by index takes linear time.
+    LinkedList,
+}
+
+/// Children of a node, in the representation chosen by the tree.
+#[derive(Debug, Clone)]
+pub(crate) enum ChildList {
+    Vec(Vec<NodeRef>),
+    Linked {
+        first: Option<NodeRef>,
+        last: Option<NodeRef>,
+        len: usize,
+    },
+}
+
+const PREVIOUS: usize = 0;
+const NEXT: usize = 1;
+
+impl ChildList {
+    pub(crate) fn new(storage: ChildStorage) -> Self {
+        match storage {
+            ChildStorage::Vec => ChildList::Vec(Vec::new()),
+            ChildStorage::LinkedList => ChildList::Linked {
+                first: None,
+                last: None,
+                len: 0,
+            },
+        }
+    }
+
+    /// Translate the references of the list with the given mapping.
+    /// References mapped to `None` are dropped.
+    pub(crate) fn remap(&mut self, map: impl Fn(NodeRef) -> Option<NodeRef>) {
+        match self {
+            ChildList::Vec(children) => {
+                *children = children.iter().filter_map(|child| map(*child)).collect();
+            }
+            ChildList::Linked { first, last, .. } => {
+                *first = first.and_then(&map);
+                *last = last.and_then(&map);
+            }
+        }
+    }
+}
+
+impl<T> Tree<T> {
+    /// Create new empty tree structure with the given child storage.
+    ///
+    /// *Arguments:*
+    /// * `storage` - How to store the children of the nodes.
+    ///
+    /// *Returns:* [Tree] struct.
+    pub fn with_child_storage(storage: ChildStorage) -> Self {
+        let mut tree = Self::new();
+        tree.storage = storage;
+        tree
+    }
+
+    /// Get how the tree stores the children of its nodes.
+    pub fn child_storage(&self) -> ChildStorage {
+        self.storage
+    }
+
+    /// Create an empty child list for a new node, reusing the
+    /// allocation left over by [Tree::rebuild] if possible.
+    pub(crate) fn new_child_list(&mut self) -> ChildList {
+        match (self.storage, self.spare_children.pop()) {
+            (ChildStorage::Vec, Some(children)) => ChildList::Vec(children),
+            _ => ChildList::new(self.storage),
+        }
+    }
+
+    /// Iterate over the children of an existing node.
+    pub(crate) fn children_of(&self, parent_ref: NodeRef) -> ChildRefs<'_, T> {
+        self.children_of_node(self.get_node(parent_ref).unwrap())
+    }
+
+    pub(crate) fn children_of_node<'a>(&'a self, node: &'a Node<T>) -> ChildRefs<'a, T> {
+        let inner = match &node.children {
+            ChildList::Vec(children) => Inner::Slice(children.iter()),
+            ChildList::Linked { first, last, len } => Inner::Linked {
+                front: first.as_ref(),
+                back: last.as_ref(),
+                len: *len,
+            },
+        };
+        ChildRefs { tree: self, inner }
+    }
+
+    /// Get the position of a node among the children of its parent.
+    pub(crate) fn child_position(&self, parent_ref: NodeRef, child_ref: NodeRef) -> Option<usize> {
+        self.children_of(parent_ref)
+            .position(|child| *child == child_ref)
+    }
+
+    /// Add a node, which is not in any child list, as last child of a node.
+    pub(crate) fn push_child(&mut self, parent_ref: NodeRef, child_ref: NodeRef) {
+        let previous = match &mut self.get_node_mut(parent_ref).unwrap().children {
+            ChildList::Vec(children) => {
+                children.push(child_ref);
+                return;
+            }
+            ChildList::Linked { first, last, len } => {
+                *len += 1;
+                if first.is_none() {
+                    *first = Some(child_ref);
+                }
+                last.replace(child_ref)
+            }
+        };
+
+        if let Some(previous_ref) = previous {
+            self.get_node_mut(previous_ref).unwrap().siblings[NEXT] = Some(child_ref);
+        }
+        self.get_node_mut(child_ref).unwrap().siblings = [previous, None];
+    }
+
+    /// Remove a node from the child list of its parent, if it is in one.
+    pub(crate) fn unlink_child(&mut self, child_ref: NodeRef) {
+        let parent_ref = match self.get_node(child_ref).unwrap().parent {
+            Some(parent_ref) if self.get_node(parent_ref).is_some() => parent_ref,
+            _ => return,
+        };
+        let [previous, next] = self.get_node(child_ref).unwrap().siblings;
+
+        match &mut self.get_node_mut(parent_ref).unwrap().children {
+            ChildList::Vec(children) => {
+                if let Some(position) = children.iter().position(|c| *c == child_ref) {
+                    children.remove(position);
+                }
+                return;
+            }
+            ChildList::Linked { first, last, len } => {
+                if *first == Some(child_ref) {
+                    *first = next;
+                } else if previous.is_none() {
+                    return;
+                }
+                if *last == Some(child_ref) {
+                    *last = previous;
+                }
+                *len -= 1;
+            }
+        }
+
+        if let Some(previous_ref) = previous {
+            self.get_node_mut(previous_ref).unwrap().siblings[NEXT] = next;
+        }
+        if let Some(next_ref) = next {
+            self.get_node_mut(next_ref).unwrap().siblings[PREVIOUS] = previous;
+        }
+        self.get_node_mut(child_ref).unwrap().siblings = [None, None];
+    }
+
+    /// Put a node, which is not in any child list, at the position
+    /// of a child of a node.
+    pub(crate) fn replace_child(
+        &mut self,
+        parent_ref: NodeRef,
+        old_ref: NodeRef,
+        new_ref: NodeRef,
+    ) {
+        let siblings = self.get_node(old_ref).unwrap().siblings;
+        match &mut self.get_node_mut(parent_ref).unwrap().children {
+            ChildList::Vec(children) => {
+                let position = children.iter().position(|c| *c == old_ref).unwrap();
+                children[position] = new_ref;
+                return;
+            }
+            ChildList::Linked { first, last, .. } => {
+                if *first == Some(old_ref) {
+                    *first = Some(new_ref);
+                }
+                if *last == Some(old_ref) {
+                    *last = Some(new_ref);
+                }
+            }
+        }
+
+        if let Some(previous_ref) = siblings[PREVIOUS] {
+            self.get_node_mut(previous_ref).unwrap().siblings[NEXT] = Some(new_ref);
+        }
+        if let Some(next_ref) = siblings[NEXT] {
+            self.get_node_mut(next_ref).unwrap().siblings[PREVIOUS] = Some(new_ref);
+        }
+        self.get_node_mut(new_ref).unwrap().siblings = siblings;
+        self.get_node_mut(old_ref).unwrap().siblings = [None, None];
+    }
+
+    /// Replace the children of a node. The new children must not be
+    /// in any child list, except the one being replaced.
+    pub(crate) fn set_child_list(&mut self, parent_ref: NodeRef, children: Vec<NodeRef>) {
+        let old: Vec<NodeRef> = self.children_of(parent_ref).copied().collect();
+        for child_ref in old {
+            if self.get_node(child_ref).is_some() {
+                self.unlink_child(child_ref);
+            }
+        }
+        self.get_node_mut(parent_ref).unwrap().children = ChildList::new(self.storage);
+        for child_ref in children {
+            self.push_child(parent_ref, child_ref);
+        }
+    }
+}
+
+/// Iterator over the references stored in the child list of a node.
+///
+/// This is the internal counterpart of [Children], which yields the
+/// stored references without copying them.
+pub(crate) struct ChildRefs<'a, T> {
+    tree: &'a Tree<T>,
+    inner: Inner<'a>,
+}
+
+#[derive(Clone)]
+enum Inner<'a> {
+    Slice(Iter<'a, NodeRef>),
+    Linked {
+        front: Option<&'a NodeRef>,
+        back: Option<&'a NodeRef>,
+        len: usize,
+    },
+}
+
+impl<'a, T> ChildRefs<'a, T> {
+    pub(crate) fn empty(tree: &'a Tree<T>) -> Self {
+        ChildRefs {
+            tree,
+            inner: Inner::Slice([].iter()),
+        }
+    }
+
+    fn step(&mut self, direction: usize) -> Option<&'a NodeRef> {
+        let tree = self.tree;
+        match &mut self.inner {
+            Inner::Slice(iter) if direction == NEXT => iter.next(),
+            Inner::Slice(iter) => iter.next_back(),
+            Inner::Linked { front, back, len } => {
+                if *len == 0 {
+                    return None;
+                }
+                *len -= 1;
+                let cursor = if direction == NEXT { front } else { back };
+                let child_ref = cursor.take()?;
+                *cursor = tree.get_node(*child_ref).unwrap().siblings[direction].as_ref();
+                Some(child_ref)
+            }
+        }
+    }
+}
+
+impl<'a, T> Iterator for ChildRefs<'a, T> {
+    type Item = &'a NodeRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.step(NEXT)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = match &self.inner {
+            Inner::Slice(iter) => iter.len(),
+            Inner::Linked { len, .. } => *len,
+        };
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for ChildRefs<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.step(PREVIOUS)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for ChildRefs<'a, T> {}
+
+impl<'a, T> Clone for ChildRefs<'a, T> {
+    fn clone(&self) -> Self {
+        ChildRefs {
+            tree: self.tree,
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+/// Iterator over the children of a node, created by [Tree::get_children].
+///
+/// The iterator is the same for all the [ChildStorage] kinds and does
+/// not expose how children are stored, so the storage can change
+/// without affecting the public API. It returns node references by
+/// value, knows the number of remaining children and can be traversed
+/// from both ends. Removed and moved children are never returned.
+pub struct Children<'a, T> {
+    refs: ChildRefs<'a, T>,
+}
+
+impl<'a, T> Children<'a, T> {
+    pub(crate) fn new(refs: ChildRefs<'a, T>) -> Self {
+        Children { refs }
+    }
+}
+
+impl<'a, T> Iterator for Children<'a, T> {
+    type Item = NodeRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.refs.next().copied()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.refs.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Children<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.refs.next_back().copied()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Children<'a, T> {}
+
+impl<'a, T> FusedIterator for Children<'a, T> {}
+
+impl<'a, T> Clone for Children<'a, T> {
+    fn clone(&self) -> Self {
+        Children {
+            refs: self.refs.clone(),
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for Children<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Children")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
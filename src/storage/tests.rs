@@ -0,0 +1,75 @@
+use super::*;
+
+fn sample(storage: ChildStorage) -> (Tree<i32>, Vec<NodeRef>) {
+    let mut tree = Tree::with_child_storage(storage);
+    let root = tree.root(0).unwrap();
+    let mut refs = vec![root];
+    for value in 1..5 {
+        refs.push(tree.child_node(root, value).unwrap());
+    }
+    refs.push(tree.child_node(refs[2], 5).unwrap());
+    (tree, refs)
+}
+
+fn children(tree: &Tree<i32>, node_ref: NodeRef) -> Vec<i32> {
+    tree.get_children(node_ref)
+        .unwrap()
+        .map(|child_ref| *tree.get(child_ref).unwrap())
+        .collect()
+}
+
+#[test]
+fn linked_list_storage() {
+    let (mut tree, refs) = sample(ChildStorage::LinkedList);
+    assert_eq!(tree.child_storage(), ChildStorage::LinkedList);
+
+    assert_eq!(children(&tree, refs[0]), vec![1, 2, 3, 4]);
+    let mut iter = tree.get_children(refs[0]).unwrap();
+    assert_eq!(iter.len(), 4);
+    assert_eq!(iter.next_back(), Some(refs[4]));
+    assert_eq!(iter.next(), Some(refs[1]));
+    assert_eq!(iter.len(), 2);
+    assert_eq!(
+        iter.clone().rev().collect::<Vec<_>>(),
+        vec![refs[3], refs[2]]
+    );
+    assert_eq!(iter.by_ref().count(), 2);
+    assert_eq!((iter.next(), iter.next_back(), iter.len()), (None, None, 0));
+
+    tree.remove(refs[3]).unwrap();
+    tree.remove(refs[1]).unwrap();
+    assert_eq!(children(&tree, refs[0]), vec![2, 4]);
+
+    tree.append_child(refs[0], refs[5]).unwrap();
+    assert_eq!(children(&tree, refs[0]), vec![2, 4, 5]);
+    assert_eq!(children(&tree, refs[2]), Vec::<i32>::new());
+    assert_eq!(
+        tree.preceding_siblings(refs[5])
+            .unwrap()
+            .collect::<Vec<_>>(),
+        vec![refs[4], refs[2]]
+    );
+
+    let values: Vec<i32> = tree
+        .depth_first_rev(true)
+        .unwrap()
+        .map(|node_ref| *tree.get(node_ref).unwrap())
+        .collect();
+    assert_eq!(values, vec![0, 5, 4, 2]);
+}
+
+#[test]
+fn storages_agree() {
+    let (vec_tree, _) = sample(ChildStorage::Vec);
+    let (mut linked_tree, _) = sample(ChildStorage::LinkedList);
+    assert!(vec_tree.structural_eq(&linked_tree));
+
+    let remap = linked_tree.optimize_layout();
+    assert!(vec_tree.structural_eq(&linked_tree));
+    let root = linked_tree.get_root_ref().unwrap();
+    assert_eq!(remap.get(root), Some(root));
+    assert_eq!(children(&linked_tree, root), vec![1, 2, 3, 4]);
+
+    let mapped = linked_tree.map(|value| value * 10).unwrap();
+    assert_eq!(mapped.get_children(root).unwrap().count(), 4);
+}
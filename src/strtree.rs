@@ -0,0 +1,128 @@
+use super::{node_not_found, InternedTree, NodeRef, Result};
+
+/// Tree of string labels, such as file system paths or
+/// dotted configuration keys.
+///
+/// Labels are interned, so each distinct label is stored only once,
+/// and paths are addressed with `/` separated strings. The path of a
+/// node is made of the labels of its ancestors, starting below the
+/// root node, followed by its own label: the root itself has an empty
+/// path.
+///
+/// ```ignore
+/// let mut tree = StrTree::new();
+/// let lib = tree.insert_path("usr/local/lib");
+/// tree.insert_path("usr/local/bin");
+///
+/// assert_eq!(tree.full_path(lib)?, "usr/local/lib");
+/// assert_eq!(tree.find_prefix("usr/local/b").len(), 1);
+/// ```
+pub type StrTree = InternedTree<Box<str>>;
+
+impl StrTree {
+    /// Create the nodes of a path, reusing the existing ones.
+    ///
+    /// An empty root node is created if the tree has none.
+    /// Empty segments are ignored.
+    ///
+    /// *Arguments:*
+    /// * `path` - The `/` separated path.
+    ///
+    /// *Returns:* A [NodeRef] to the last node of the path,
+    ///            or to the root if the path is empty.
+    pub fn insert_path(&mut self, path: &str) -> NodeRef {
+        let mut node_ref = match self.tree.get_root_ref() {
+            Some(root_ref) => root_ref,
+            None => self.root(Box::from("")).unwrap(),
+        };
+
+        for segment in path.split('/').filter(|s| !s.is_empty()) {
+            node_ref = match self.child_with_label(node_ref, segment) {
+                Some(child_ref) => child_ref,
+                None => self.child_node(node_ref, Box::from(segment)).unwrap(),
+            };
+        }
+        node_ref
+    }
+
+    /// Get the path of a node.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    ///
+    /// *Returns:* The `/` separated labels from below the topmost
+    ///            ancestor to the node. Returns error if the node
+    ///            does not exist.
+    pub fn full_path(&self, node_ref: NodeRef) -> Result<String> {
+        let label = match self.get(node_ref) {
+            None => return Err(node_not_found(node_ref)),
+            Some(label) => label,
+        };
+
+        let mut labels: Vec<&str> = self
+            .tree
+            .ancestors(node_ref)?
+            .filter_map(|ancestor_ref| self.get(ancestor_ref).map(|label| &**label))
+            .collect();
+        if labels.is_empty() {
+            return Ok(String::new());
+        }
+
+        // The topmost ancestor is not part of the path.
+        labels.pop();
+        labels.reverse();
+        labels.push(label);
+        Ok(labels.join("/"))
+    }
+
+    /// Find the nodes whose path starts with a prefix.
+    ///
+    /// The prefix is matched against the path as a string, so `"usr/l"`
+    /// matches both `"usr/lib"` and `"usr/local"`. Subtrees that cannot
+    /// match are not traversed.
+    ///
+    /// *Arguments:*
+    /// * `prefix` - The path prefix.
+    ///
+    /// *Returns:* The matching node references in depth-first order.
+    pub fn find_prefix(&self, prefix: &str) -> Vec<NodeRef> {
+        let root_ref = match self.tree.get_root_ref() {
+            None => return Vec::new(),
+            Some(root_ref) => root_ref,
+        };
+
+        let mut matches = Vec::new();
+        let mut stack = vec![(root_ref, String::new())];
+        while let Some((node_ref, path)) = stack.pop() {
+            if path.starts_with(prefix) {
+                matches.push(node_ref);
+            } else if !prefix.starts_with(path.as_str()) {
+                continue;
+            }
+
+            for child_ref in self.tree.get_children(node_ref).unwrap().rev() {
+                let label = self.get(child_ref).unwrap();
+                let child_path = if node_ref == root_ref {
+                    label.to_string()
+                } else {
+                    format!("{}/{}", path, label)
+                };
+                stack.push((child_ref, child_path));
+            }
+        }
+        matches
+    }
+
+    fn child_with_label(&self, parent_ref: NodeRef, label: &str) -> Option<NodeRef> {
+        let id = self.lookup(label)?;
+        self.tree
+            .get_children(parent_ref)
+            .ok()?
+            .find(|child_ref| self.tree.get(*child_ref) == Some(&id))
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
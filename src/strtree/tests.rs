@@ -0,0 +1,56 @@
+use super::*;
+
+fn usr_tree() -> (StrTree, NodeRef) {
+    let mut tree = StrTree::new();
+    let lib = tree.insert_path("usr/local/lib");
+    tree.insert_path("usr/local/bin");
+    tree.insert_path("usr/lib");
+    tree.insert_path("etc");
+    (tree, lib)
+}
+
+#[test]
+fn insert_path() {
+    let (mut tree, lib) = usr_tree();
+
+    assert_eq!(tree.tree().len(), 7);
+    assert_eq!(tree.distinct_values(), 7 - 1);
+    assert_eq!(tree.insert_path("/usr//local/lib/"), lib);
+    assert_eq!(tree.insert_path(""), tree.tree().get_root_ref().unwrap());
+    assert_eq!(tree.tree().len(), 7);
+}
+
+#[test]
+fn full_path() {
+    let (tree, lib) = usr_tree();
+    let root = tree.tree().get_root_ref().unwrap();
+
+    assert_eq!(tree.full_path(lib).unwrap(), "usr/local/lib");
+    assert_eq!(tree.full_path(root).unwrap(), "");
+}
+
+#[test]
+fn find_prefix() {
+    let (tree, lib) = usr_tree();
+    let paths = |prefix| -> Vec<String> {
+        tree.find_prefix(prefix)
+            .into_iter()
+            .map(|node_ref| tree.full_path(node_ref).unwrap())
+            .collect()
+    };
+
+    assert_eq!(
+        paths("usr/l"),
+        vec!["usr/local", "usr/local/lib", "usr/local/bin", "usr/lib"]
+    );
+    assert_eq!(tree.find_prefix("usr/local/li"), vec![lib]);
+    assert_eq!(tree.find_prefix("").len(), 7);
+    assert!(tree.find_prefix("var").is_empty());
+}
+
+#[test]
+fn lookup() {
+    let (tree, lib) = usr_tree();
+    assert_eq!(tree.lookup("lib"), tree.tree().get(lib).copied());
+    assert_eq!(tree.lookup("var"), None);
+}
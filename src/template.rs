@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::{ErrorKind, NodeRef, Result, Tree, TreeError};
+
+/// Content of a node of a template, see [Tree::instantiate].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Template<T, K = String> {
+    /// A node copied as is into the instances.
+    Value(T),
+    /// A leaf replaced by the parameter with the given name.
+    Placeholder(K),
+}
+
+impl<T: Clone> Tree<T> {
+    /// Create a tree from a template, replacing its placeholders
+    /// with parameter subtrees.
+    ///
+    /// This is the basic operation behind macro expansion and component
+    /// systems. Each placeholder leaf is replaced by a copy of the tree
+    /// of the corresponding parameter, rooted at the position of the
+    /// placeholder. An empty parameter tree removes the placeholder.
+    /// Nodes not reachable from the root of the template or of the
+    /// parameters are not copied.
+    ///
+    /// ```ignore
+    /// // <li>{label}</li>
+    /// let mut template = Tree::new();
+    /// let li = template.root(Template::Value("li"))?;
+    /// template.child_node(li, Template::Placeholder("label".to_string()))?;
+    ///
+    /// let params = HashMap::from([("label".to_string(), tree!("Home"))]);
+    /// let item = Tree::instantiate(&template, &params)?;
+    /// ```
+    ///
+    /// *Arguments:*
+    /// * `template` - The template tree.
+    /// * `params` - The trees of the parameters, by name.
+    ///
+    /// *Returns:* The instantiated tree, empty if the template is empty.
+    ///            Returns an error of kind [ErrorKind::InvalidInput] if a
+    ///            placeholder has children or no parameter.
+    pub fn instantiate<K: Hash + Eq>(
+        template: &Tree<Template<T, K>>,
+        params: &HashMap<K, Tree<T>>,
+    ) -> Result<Tree<T>> {
+        let mut tree = Tree::new();
+        let root_ref = match template.root {
+            None => return Ok(tree),
+            Some(root_ref) => root_ref,
+        };
+
+        let mut mapping: Vec<Option<NodeRef>> = vec![None; template.nodes.len()];
+        let mut skipped = vec![false; template.nodes.len()];
+        for template_ref in template.depth_first_of(root_ref, true)? {
+            let node = template.get_node(template_ref).unwrap();
+            let parent = node.parent.filter(|_| template_ref != root_ref);
+            if parent.is_some_and(|parent_ref| skipped[parent_ref.index()]) {
+                skipped[template_ref.index()] = true;
+                continue;
+            }
+            let parent = parent.map(|parent_ref| mapping[parent_ref.index()].unwrap());
+
+            let new_ref = match &node.content {
+                Template::Value(content) => Some(tree.instance_node(parent, content.clone())),
+                Template::Placeholder(name) => {
+                    let invalid = |message| {
+                        TreeError::with_kind(ErrorKind::InvalidInput, message)
+                            .during("instantiate")
+                            .involving(&[template_ref])
+                    };
+                    if template.live_children(template_ref).next().is_some() {
+                        return Err(invalid("Placeholder has children."));
+                    }
+                    match params.get(name) {
+                        None => return Err(invalid("Missing parameter for placeholder.")),
+                        Some(param) => tree.copy_instance(parent, param),
+                    }
+                }
+            };
+            match new_ref {
+                None => skipped[template_ref.index()] = true,
+                Some(new_ref) => mapping[template_ref.index()] = Some(new_ref),
+            }
+        }
+        Ok(tree)
+    }
+
+    /// Create a node as child of `parent`, or as root without parent.
+    fn instance_node(&mut self, parent: Option<NodeRef>, content: T) -> NodeRef {
+        match parent {
+            None => self.root(content).unwrap(),
+            Some(parent_ref) => self.child_node(parent_ref, content).unwrap(),
+        }
+    }
+
+    /// Copy the nodes reachable from the root of a parameter under `parent`.
+    fn copy_instance(&mut self, parent: Option<NodeRef>, param: &Tree<T>) -> Option<NodeRef> {
+        let param_root = param.root?;
+        let mut mapping: Vec<Option<NodeRef>> = vec![None; param.nodes.len()];
+        for param_ref in param.depth_first_of(param_root, true).unwrap() {
+            let node = param.get_node(param_ref).unwrap();
+            let parent = match node.parent.filter(|_| param_ref != param_root) {
+                None => parent,
+                Some(parent_ref) => mapping[parent_ref.index()],
+            };
+            mapping[param_ref.index()] = Some(self.instance_node(parent, node.content.clone()));
+        }
+        mapping[param_root.index()]
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
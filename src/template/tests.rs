@@ -0,0 +1,56 @@
+use super::*;
+use crate::tree;
+
+fn values(tree: &Tree<&'static str>) -> Vec<&'static str> {
+    tree.depth_first(true)
+        .unwrap()
+        .map(|node_ref| *tree.get(node_ref).unwrap())
+        .collect()
+}
+
+#[test]
+fn instantiate() {
+    let mut template = Tree::new();
+    let ul = template.root(Template::Value("ul")).unwrap();
+    let li = template.child_node(ul, Template::Value("li")).unwrap();
+    template
+        .child_node(li, Template::Placeholder("first"))
+        .unwrap();
+    template
+        .child_node(ul, Template::Placeholder("rest"))
+        .unwrap();
+    template
+        .child_node(ul, Template::Placeholder("none"))
+        .unwrap();
+    template.child_node(ul, Template::Value("end")).unwrap();
+
+    let mut params = HashMap::new();
+    params.insert("first", tree!("a" => ["b"]));
+    params.insert("rest", tree!("li" => ["c", "d"]));
+    params.insert("none", Tree::new());
+
+    let instance = Tree::instantiate(&template, &params).unwrap();
+    assert_eq!(
+        values(&instance),
+        vec!["ul", "li", "a", "b", "li", "c", "d", "end"]
+    );
+
+    params.remove("rest");
+    let error = Tree::instantiate(&template, &params).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    assert_eq!(error.operation(), Some("instantiate"));
+}
+
+#[test]
+fn instantiate_root_placeholder() {
+    let mut template: Tree<Template<&str, &str>> = Tree::new();
+    let root = template.root(Template::Placeholder("body")).unwrap();
+
+    let mut params = HashMap::new();
+    params.insert("body", tree!("x" => ["y"]));
+    let instance = Tree::instantiate(&template, &params).unwrap();
+    assert_eq!(values(&instance), vec!["x", "y"]);
+
+    template.child_node(root, Template::Value("z")).unwrap();
+    assert!(Tree::instantiate(&template, &params).is_err());
+}
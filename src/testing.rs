@@ -0,0 +1,116 @@
+//! Helpers for testing code which builds or transforms trees.
+//!
+//! The assertions panic with messages describing the nodes involved,
+//! and [snapshot] renders the structure of a tree as text, which can be
+//! compared with an expected string for readable failures.
+
+use std::fmt::Debug;
+
+use super::{NodeRef, Tree};
+
+pub use crate::assert_shape;
+
+/// Assert that a tree has the shape and content of a literal.
+///
+/// The expected tree is written with the syntax of the [tree!](crate::tree)
+/// macro. On failure, the panic message contains the differences
+/// between the trees.
+///
+/// ```ignore
+/// assert_shape!(tree, "/" => ["etc", "usr" => ["bin", "lib"]]);
+/// ```
+#[macro_export]
+macro_rules! assert_shape {
+    ($tree:expr, $($expected:tt)+) => {{
+        let expected = $crate::tree!($($expected)+);
+        let tree = &$tree;
+        if !tree.structural_eq(&expected) {
+            panic!(
+                "assertion failed: tree does not have the expected shape\n{}",
+                tree.diff_display(&expected)
+            );
+        }
+    }};
+}
+
+/// Render the structure of a tree as text.
+///
+/// Each node reachable from the root is rendered on its own line with
+/// its [Debug] representation, indented by two spaces per level.
+///
+/// *Arguments:*
+/// * `tree` - The tree to render.
+///
+/// *Returns:* The rendered tree, empty if the tree has no root node.
+pub fn snapshot<T: Debug>(tree: &Tree<T>) -> String {
+    let mut out = String::new();
+    if let Ok(nodes) = tree.depth_first(true) {
+        for node_ref in nodes {
+            let depth = tree.ancestors(node_ref).unwrap().count();
+            out.push_str(&format!(
+                "{}{:?}\n",
+                "  ".repeat(depth),
+                tree.get(node_ref).unwrap()
+            ));
+        }
+    }
+    out
+}
+
+/// Assert that a node is the parent of another.
+///
+/// *Arguments:*
+/// * `tree` - The tree containing the nodes.
+/// * `child_ref` - [NodeRef] of the child node.
+/// * `parent_ref` - [NodeRef] of the expected parent, or `None`
+///   if the node should have no parent.
+#[track_caller]
+pub fn assert_parent<T: Debug>(tree: &Tree<T>, child_ref: NodeRef, parent_ref: Option<NodeRef>) {
+    let actual = tree.get_parent(child_ref).unwrap_or_else(|_| {
+        panic!("assertion failed: {}", tree.describe(child_ref));
+    });
+    if actual != parent_ref {
+        let describe = |node_ref: Option<NodeRef>| match node_ref {
+            None => "no parent".to_string(),
+            Some(node_ref) => tree.describe(node_ref),
+        };
+        panic!(
+            "assertion failed: unexpected parent of {}\n  expected: {}\n    actual: {}",
+            tree.describe(child_ref),
+            describe(parent_ref),
+            describe(actual),
+        );
+    }
+}
+
+/// Assert that a node has exactly the given children, in order.
+///
+/// *Arguments:*
+/// * `tree` - The tree containing the nodes.
+/// * `parent_ref` - [NodeRef] of the parent node.
+/// * `children` - Slice of [NodeRef] for the expected children.
+#[track_caller]
+pub fn assert_children<T: Debug>(tree: &Tree<T>, parent_ref: NodeRef, children: &[NodeRef]) {
+    let actual: Vec<NodeRef> = match tree.get_children(parent_ref) {
+        Ok(actual) => actual.collect(),
+        Err(_) => panic!("assertion failed: {}", tree.describe(parent_ref)),
+    };
+    if actual != children {
+        let describe = |refs: &[NodeRef]| -> String {
+            refs.iter()
+                .map(|node_ref| format!("\n    {}", tree.describe(*node_ref)))
+                .collect()
+        };
+        panic!(
+            "assertion failed: unexpected children of {}\n  expected:{}\n  actual:{}",
+            tree.describe(parent_ref),
+            describe(children),
+            describe(&actual),
+        );
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
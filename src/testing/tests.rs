@@ -0,0 +1,36 @@
+use super::*;
+use crate::tree;
+
+#[test]
+fn snapshot_and_shape() {
+    let tree = tree! {"/" => ["etc", "usr" => ["bin"]]};
+
+    assert_eq!(
+        snapshot(&tree),
+        "\"/\"\n  \"etc\"\n  \"usr\"\n    \"bin\"\n"
+    );
+    assert_shape!(tree, "/" => ["etc", "usr" => ["bin"]]);
+    assert_eq!(snapshot(&Tree::<i32>::new()), "");
+}
+
+#[test]
+#[should_panic(expected = "tree does not have the expected shape")]
+fn assert_shape_fails() {
+    assert_shape!(tree!(1 => [2, 3]), 1 => [2]);
+}
+
+#[test]
+fn assert_parent_and_children() {
+    let mut tree = Tree::new();
+    let root = tree.root("root").unwrap();
+    let a = tree.child_node(root, "a").unwrap();
+    let b = tree.child_node(root, "b").unwrap();
+
+    assert_parent(&tree, a, Some(root));
+    assert_parent(&tree, root, None);
+    assert_children(&tree, root, &[a, b]);
+
+    let result = std::panic::catch_unwind(|| assert_children(&tree, root, &[b, a]));
+    let message = *result.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.contains("unexpected children of node 0 at depth 0"));
+}
@@ -51,14 +51,14 @@ where
 {
     iterator
         .next()
-        .and_then(|node_ref| tree.get(node_ref).and_then(|value| Some(value.clone())))
+        .and_then(|node_ref| tree.get(node_ref).cloned())
 }
 
 #[test]
 fn new_node() {
     let (tree, node_a, node_b) = tree2();
 
-    assert_eq!(tree.len(), 2);
+    assert_eq!(tree.unreachable_nodes().len(), 2);
     assert_eq!(tree.get(node_a), Some(&"Node A"));
     assert_eq!(tree.get(node_b), Some(&"Node B"));
 }
@@ -79,9 +79,17 @@ fn set_root() {
 fn remove_node() {
     let (mut tree, node_a, node_b) = tree2();
 
-    assert_eq!(tree.remove(node_a), Ok(()));
+    assert_eq!(
+        tree.remove(node_a),
+        Ok(RemovalReport {
+            removed: 1,
+            orphaned: 0,
+            tombstones: 1
+        })
+    );
 
-    assert_eq!(tree.len(), 1);
+    assert_eq!(tree.unreachable_nodes(), vec![node_b]);
+    assert_eq!(tree.slot_count(), 2);
     assert_eq!(tree.get(node_a), None);
     assert_eq!(tree.get(node_b), Some(&"Node B"));
 }
@@ -90,22 +98,111 @@ fn remove_node() {
 fn remove_node_error_already_removed() {
     let (mut tree, node_a, _) = tree2();
 
-    assert_eq!(tree.remove(node_a), Ok(()));
+    assert!(tree.remove(node_a).is_ok());
     assert_eq!(
         tree.remove(node_a),
-        Err(TreeError::new("Node already removed."))
+        Err(TreeError::with_kind(
+            ErrorKind::NotFound,
+            "Node already removed."
+        ))
     );
 }
 
+// Foreign references panic with debug-validate.
 #[test]
+#[cfg(not(all(feature = "debug-validate", debug_assertions)))]
 fn remove_node_error_invalid_ref() {
     let (mut tree1, _, _) = tree2();
     let (_, _, _, node2) = tree3();
 
     assert_eq!(
         tree1.remove(node2),
-        Err(TreeError::new("Invalid node reference."))
+        Err(TreeError::with_kind(
+            ErrorKind::InvalidInput,
+            "Invalid node reference."
+        ))
+    );
+}
+
+#[test]
+fn remove_many() {
+    let (mut tree, node_a, node_b, node_c) = tree3();
+
+    assert_eq!(tree.remove_many(&[node_a, node_c]).unwrap().removed, 2);
+
+    assert_eq!(tree.unreachable_nodes(), vec![node_b]);
+    assert_eq!(tree.get(node_a), None);
+    assert_eq!(tree.get(node_b), Some(&"Node B"));
+    assert_eq!(tree.get(node_c), None);
+}
+
+#[test]
+fn remove_many_error_is_atomic() {
+    let (mut tree, node_a, node_b, node_c) = tree3();
+    tree.remove(node_c).unwrap();
+
+    assert_eq!(
+        tree.remove_many(&[node_a, node_c]),
+        Err(TreeError::with_kind(
+            ErrorKind::NotFound,
+            "Node already removed."
+        ))
+    );
+    assert_eq!(
+        tree.remove_many(&[node_a, node_b, node_a]),
+        Err(TreeError::with_kind(
+            ErrorKind::InvalidInput,
+            "Duplicate node reference."
+        ))
     );
+
+    assert_eq!(tree.unreachable_nodes(), vec![node_a, node_b]);
+    assert_eq!(tree.get(node_a), Some(&"Node A"));
+}
+
+#[test]
+fn purge_tombstones_unchecked() {
+    let mut tree = Tree::new();
+    let nodes: Vec<NodeRef> = (0..5).map(|i| tree.node(i)).collect();
+    tree.remove_many(&[nodes[1], nodes[3], nodes[4]]).unwrap();
+
+    assert_eq!(tree.tombstones(), 3);
+    assert_eq!(tree.purge_tombstones_unchecked(), 3);
+    assert_eq!(tree.tombstones(), 1);
+    assert_eq!(tree.purge_tombstones_unchecked(), 0);
+    assert_eq!(tree.get(nodes[2]), Some(&2));
+
+    let reused = tree.node(10);
+    assert_eq!(reused, nodes[1]);
+    assert_eq!(tree.tombstones(), 0);
+    assert_eq!(tree.node(11), nodes[3]);
+    assert_eq!(tree.unreachable_nodes().len(), 4);
+}
+
+#[test]
+fn purge_tombstones_unchecked_drops_dead_refs() {
+    for storage in [ChildStorage::Vec, ChildStorage::LinkedList] {
+        let mut tree = Tree::with_child_storage(storage);
+        let root = tree.root("r").unwrap();
+        let node_a = tree.child_node(root, "a").unwrap();
+        let node_b = tree.child_node(root, "b").unwrap();
+        let node_d = tree.child_node(node_a, "d").unwrap();
+        tree.remove(node_a).unwrap();
+        tree.purge_tombstones_unchecked();
+
+        let node_c = tree.child_node(root, "c").unwrap();
+        let order: Vec<NodeRef> = tree.depth_first(true).unwrap().collect();
+        assert_eq!(order, vec![root, node_b, node_c]);
+        assert_eq!(tree.get_parent(node_d), Ok(None));
+        assert_eq!(tree.len(), 3);
+        assert_eq!(tree.unreachable_nodes(), vec![node_d]);
+
+        tree.remove(root).unwrap();
+        tree.purge_tombstones_unchecked();
+        assert_eq!(tree.get_root_ref(), None);
+        tree.node("y");
+        assert_eq!(tree.get_root_ref(), None);
+    }
 }
 
 #[test]
@@ -118,6 +215,88 @@ fn get_mut() {
     assert_eq!(tree.get(node), Some(&TestData { field: 4 }));
 }
 
+#[test]
+fn try_get() {
+    let (mut tree, node_c) = nested_tree();
+    let root = tree.try_get_root_ref().unwrap();
+    assert_eq!(tree.try_get(node_c), Ok(&TestData { field: 3 }));
+    assert_eq!(tree.try_get_parent(node_c), Ok(root));
+    tree.try_get_mut(node_c).unwrap().field = 7;
+    assert_eq!(tree.get(node_c), Some(&TestData { field: 7 }));
+
+    let error = tree.try_get_parent(root).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::NotFound);
+    assert_eq!(error.nodes(), &[root]);
+
+    tree.remove(node_c).unwrap();
+    assert_eq!(
+        tree.try_get(node_c).unwrap_err().kind(),
+        ErrorKind::NotFound
+    );
+    assert_eq!(
+        tree.try_get_mut(node_c).unwrap_err().kind(),
+        ErrorKind::NotFound
+    );
+    assert_eq!(
+        tree.try_get_parent(node_c).unwrap_err().kind(),
+        ErrorKind::NotFound
+    );
+    assert_eq!(
+        Tree::<i32>::new().try_get_root_ref().unwrap_err().kind(),
+        ErrorKind::NotFound
+    );
+}
+
+#[test]
+fn update_where() {
+    let (mut tree, node_c) = nested_tree();
+    let detached = tree.node(TestData { field: 8 });
+
+    let count = tree.update_where(|value| value.field % 2 == 0, |value| value.field *= 10);
+    assert_eq!(count, 4);
+    assert_eq!(tree.get(detached), Some(&TestData { field: 80 }));
+    assert_eq!(tree.get(node_c), Some(&TestData { field: 3 }));
+    assert_eq!(tree.update_where(|value| value.field == 0, |_| ()), 0);
+}
+
+#[test]
+fn child_nodes() {
+    let mut tree = Tree::new();
+    let root = tree.root(0).unwrap();
+    let children = tree.child_nodes(root, vec![1, 2, 3]).unwrap();
+    assert_eq!(
+        tree.get_children(root).unwrap().collect::<Vec<_>>(),
+        children
+    );
+
+    tree.lock_subtree(root).unwrap();
+    let error = tree.child_node(root, 4).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Locked);
+    assert_eq!(error.operation(), Some("child_node"));
+    assert!(tree.child_nodes(root, vec![4, 5]).is_err());
+    assert_eq!(tree.len(), 4);
+}
+
+#[test]
+fn sibling_nodes() {
+    let mut tree = Tree::new();
+    let root = tree.root(0).unwrap();
+    let node = tree.child_node(root, 2).unwrap();
+    let after = tree.sibling_node_after(node, 3).unwrap();
+    let before = tree.sibling_node_before(node, 1).unwrap();
+    let last = tree.sibling_node_after(after, 4).unwrap();
+
+    assert_eq!(
+        tree.get_children(root).unwrap().collect::<Vec<_>>(),
+        vec![before, node, after, last]
+    );
+    assert_eq!(tree.get_parent(before).unwrap(), Some(root));
+
+    let error = tree.sibling_node_after(root, 5).unwrap_err();
+    assert_eq!(error.operation(), Some("sibling_node_after"));
+    assert_eq!(tree.len(), 5);
+}
+
 #[test]
 fn append_child() {
     let (mut tree, node_a, node_b, node_c) = tree3();
@@ -127,12 +306,12 @@ fn append_child() {
     let node_d = tree.child_node(node_b, "Node D").unwrap();
 
     let mut children_a = tree.get_children(node_a).unwrap();
-    assert_eq!(*children_a.next().unwrap(), node_b);
-    assert_eq!(*children_a.next().unwrap(), node_c);
+    assert_eq!(children_a.next().unwrap(), node_b);
+    assert_eq!(children_a.next().unwrap(), node_c);
     assert_eq!(children_a.next(), None);
 
     let mut children_b = tree.get_children(node_b).unwrap();
-    assert_eq!(*children_b.next().unwrap(), node_d);
+    assert_eq!(children_b.next().unwrap(), node_d);
     assert_eq!(children_b.next(), None);
 }
 
@@ -143,8 +322,8 @@ fn append_children() {
     tree.append_children(node_a, &[node_b, node_c]).unwrap();
 
     let mut children = tree.get_children(node_a).unwrap();
-    assert_eq!(*children.next().unwrap(), node_b);
-    assert_eq!(*children.next().unwrap(), node_c);
+    assert_eq!(children.next().unwrap(), node_b);
+    assert_eq!(children.next().unwrap(), node_c);
     assert_eq!(children.next(), None);
 }
 
@@ -159,18 +338,105 @@ fn get_parent() {
 }
 
 #[test]
+fn append_tree() {
+    let (mut tree, node_c) = nested_tree();
+    let (other, _) = nested_tree();
+
+    let new_root = tree.append_tree(node_c, other).unwrap();
+
+    assert_eq!(tree.len(), 12);
+    assert_eq!(tree.get_parent(new_root), Ok(Some(node_c)));
+    assert_eq!(
+        tree.depth_first_of(node_c, true)
+            .unwrap()
+            .map(|node_ref| tree.get(node_ref).unwrap().field)
+            .collect::<Vec<i32>>(),
+        vec![3, 5, 6, 1, 2, 4, 3, 5, 6]
+    );
+    assert_eq!(
+        tree.append_tree(node_c, Tree::new()),
+        Err(TreeError::with_kind(
+            ErrorKind::NotFound,
+            "Cannot append a tree without a root node."
+        ))
+    );
+}
+
+#[test]
+fn join() {
+    let (first, _) = nested_tree();
+    let mut second = Tree::new();
+    second.root(TestData { field: 3 }).unwrap();
+    second.node(TestData { field: 9 });
+
+    let joined = Tree::join(vec![first, Tree::new(), second], TestData { field: 0 });
+    assert_eq!(joined.len(), 8);
+    assert_eq!(
+        joined
+            .depth_first(true)
+            .unwrap()
+            .map(|node_ref| joined.get(node_ref).unwrap().field)
+            .collect::<Vec<i32>>(),
+        vec![0, 1, 2, 4, 3, 5, 6, 3]
+    );
+}
+
+// Foreign references panic with debug-validate.
+#[test]
+#[cfg(not(all(feature = "debug-validate", debug_assertions)))]
 fn append_child_error() {
     let (mut tree1, node1, _) = tree2();
     let (_, _, _, node_c2) = tree3();
 
     assert_eq!(
         tree1.append_child(node_c2, node1),
-        Err(TreeError::new("Parent node does not exist."))
+        Err(TreeError::with_kind(
+            ErrorKind::NotFound,
+            "Parent node does not exist."
+        ))
     );
     assert_eq!(
         tree1.append_child(node1, node_c2),
-        Err(TreeError::new("Child node does not exist."))
+        Err(TreeError::with_kind(
+            ErrorKind::NotFound,
+            "Child node does not exist."
+        ))
+    );
+}
+
+#[test]
+fn lock_subtree() {
+    let (mut tree, node_c) = nested_tree();
+    let node_e = tree.nth_in_depth_first(node_c, 1).unwrap().unwrap();
+    let root = tree.get_root_ref().unwrap();
+    let node_x = tree.node(TestData { field: 10 });
+
+    tree.lock_subtree(node_c).unwrap();
+
+    assert_eq!(tree.is_locked(node_e), Ok(true));
+    assert_eq!(tree.is_locked(root), Ok(false));
+
+    let error = tree.append_child(node_e, node_x).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Locked);
+    assert_eq!(
+        tree.remove(node_e),
+        Err(TreeError::with_kind(
+            ErrorKind::Locked,
+            "Node is inside a locked subtree."
+        ))
+    );
+    assert_eq!(
+        tree.remove_many(&[node_x, node_c]).unwrap_err().kind(),
+        ErrorKind::Locked
     );
+    assert!(tree.get(node_x).is_some());
+
+    tree.get_mut(node_e).unwrap().field = 50;
+    assert_eq!(tree.append_child(root, node_x), Ok(()));
+
+    tree.unlock_subtree(node_c).unwrap();
+    assert_eq!(tree.is_locked(node_e), Ok(false));
+    assert!(tree.child_node(node_e, TestData { field: 11 }).is_ok());
 }
 
 #[test]
@@ -209,11 +475,67 @@ fn depth_first_iterator() {
     assert_eq!(next(&tree, &mut iterator), None);
 }
 
+#[test]
+fn depth_first_rev() {
+    let (tree, _) = nested_tree();
+
+    let mut iterator = tree.depth_first_rev(true).unwrap();
+    assert_eq!(next(&tree, &mut iterator), Some(TestData { field: 1 }));
+    assert_eq!(next(&tree, &mut iterator), Some(TestData { field: 3 }));
+    assert_eq!(next(&tree, &mut iterator), Some(TestData { field: 6 }));
+    assert_eq!(next(&tree, &mut iterator), Some(TestData { field: 5 }));
+    assert_eq!(next(&tree, &mut iterator), Some(TestData { field: 2 }));
+    assert_eq!(next(&tree, &mut iterator), Some(TestData { field: 4 }));
+    assert_eq!(next(&tree, &mut iterator), None);
+}
+
+#[test]
+fn depth_first_double_ended() {
+    let (tree, node_c) = nested_tree();
+    let fields = |iterator: &mut dyn Iterator<Item = NodeRef>| {
+        iterator
+            .map(|node_ref| tree.get(node_ref).unwrap().field)
+            .collect::<Vec<i32>>()
+    };
+
+    assert_eq!(
+        fields(&mut tree.depth_first(true).unwrap().rev()),
+        vec![6, 5, 3, 4, 2, 1]
+    );
+    assert_eq!(
+        fields(&mut tree.depth_first(false).unwrap().rev()),
+        vec![6, 5, 3, 4, 2]
+    );
+    assert_eq!(
+        fields(&mut tree.depth_first_of(node_c, true).unwrap().rev()),
+        vec![6, 5, 3]
+    );
+
+    let mut iterator = tree.depth_first(true).unwrap();
+    assert_eq!(next(&tree, &mut iterator), Some(TestData { field: 1 }));
+    assert_eq!(
+        iterator.next_back().map(|n| tree.get(n).unwrap().field),
+        Some(6)
+    );
+    assert_eq!(next(&tree, &mut iterator), Some(TestData { field: 2 }));
+    assert_eq!(
+        iterator.next_back().map(|n| tree.get(n).unwrap().field),
+        Some(5)
+    );
+    assert_eq!(
+        iterator.next_back().map(|n| tree.get(n).unwrap().field),
+        Some(3)
+    );
+    assert_eq!(next(&tree, &mut iterator), Some(TestData { field: 4 }));
+    assert_eq!(next(&tree, &mut iterator), None);
+    assert_eq!(iterator.next_back(), None);
+}
+
 #[test]
 fn map() {
     let (tree, _) = nested_tree();
 
-    let new_tree = tree.map(|value, _, _| value.field * 3).unwrap();
+    let new_tree = tree.map(|value| value.field * 3).unwrap();
 
     let mut iterator = new_tree.depth_first(true).unwrap();
     assert_eq!(next(&new_tree, &mut iterator), Some(3));
@@ -224,3 +546,1044 @@ fn map() {
     assert_eq!(next(&new_tree, &mut iterator), Some(18));
     assert_eq!(next(&new_tree, &mut iterator), None);
 }
+
+#[test]
+fn in_order_of() {
+    /*
+     *        -
+     *        |
+     *    * --+-- 4
+     *    |
+     * 2 -+- 3
+     */
+    let mut tree = Tree::new();
+    let minus = tree.root("-").unwrap();
+    let times = tree.child_node(minus, "*").unwrap();
+    tree.child_node(times, "2").unwrap();
+    tree.child_node(times, "3").unwrap();
+    tree.child_node(minus, "4").unwrap();
+
+    let values = |split_index| {
+        tree.in_order_of(minus, split_index)
+            .unwrap()
+            .map(|node_ref| *tree.get(node_ref).unwrap())
+            .collect::<Vec<&str>>()
+    };
+
+    assert_eq!(values(1), vec!["2", "*", "3", "-", "4"]);
+    assert_eq!(values(0), vec!["-", "*", "2", "3", "4"]);
+    assert_eq!(values(5), vec!["2", "3", "*", "4", "-"]);
+}
+
+#[test]
+fn in_order_of_removed_child() {
+    let mut tree = Tree::with_child_storage(ChildStorage::Vec);
+    let root = tree.root("r").unwrap();
+    let a = tree.child_node(root, "a").unwrap();
+    tree.child_node(root, "b").unwrap();
+    tree.child_node(root, "c").unwrap();
+
+    tree.remove(a).unwrap();
+
+    let values: Vec<&str> = tree
+        .in_order_of(root, 1)
+        .unwrap()
+        .map(|node_ref| *tree.get(node_ref).unwrap())
+        .collect();
+    assert_eq!(values, vec!["b", "r", "c"]);
+}
+
+#[test]
+fn nth_in_depth_first() {
+    let (tree, node_c) = nested_tree();
+    let root = tree.get_root_ref().unwrap();
+
+    let nth = |node_ref, n| {
+        tree.nth_in_depth_first(node_ref, n)
+            .unwrap()
+            .map(|node_ref| tree.get(node_ref).unwrap().field)
+    };
+
+    assert_eq!(nth(root, 0), Some(1));
+    assert_eq!(nth(root, 2), Some(4));
+    assert_eq!(nth(root, 5), Some(6));
+    assert_eq!(nth(root, 6), None);
+    assert_eq!(nth(node_c, 1), Some(5));
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn random_node() {
+    use rand::SeedableRng;
+
+    let (mut tree, node_c) = nested_tree();
+    let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+
+    assert_eq!(Tree::<i32>::new().random_node(&mut rng), None);
+
+    tree.remove(node_c).unwrap();
+    for _ in 0..100 {
+        let node_ref = tree.random_node(&mut rng).unwrap();
+        assert_ne!(node_ref, node_c);
+        assert!(tree.get(node_ref).is_some());
+    }
+}
+
+#[test]
+fn ancestors() {
+    let (tree, node_c) = nested_tree();
+    let node_f = tree.nth_in_depth_first(node_c, 2).unwrap().unwrap();
+
+    let ancestors: Vec<i32> = tree
+        .ancestors(node_f)
+        .unwrap()
+        .map(|node_ref| tree.get(node_ref).unwrap().field)
+        .collect();
+
+    assert_eq!(ancestors, vec![3, 1]);
+    assert_eq!(
+        tree.ancestors(tree.get_root_ref().unwrap()).unwrap().next(),
+        None
+    );
+}
+
+#[test]
+fn ancestors_removed_ancestor() {
+    let (mut tree, node_c) = nested_tree();
+    let node_f = tree.nth_in_depth_first(node_c, 2).unwrap().unwrap();
+
+    tree.remove(node_c).unwrap();
+
+    assert_eq!(tree.ancestors(node_f).unwrap().next(), None);
+    assert_eq!(
+        tree.ancestors_or_self(node_f).unwrap().collect::<Vec<_>>(),
+        vec![node_f]
+    );
+}
+
+#[test]
+fn find_ancestor() {
+    let (tree, node_c) = nested_tree();
+    let node_f = tree.nth_in_depth_first(node_c, 2).unwrap().unwrap();
+    let root = tree.get_root_ref().unwrap();
+
+    assert_eq!(
+        tree.find_ancestor(node_f, |value| value.field < 5),
+        Some(node_c)
+    );
+    assert_eq!(
+        tree.find_ancestor(node_f, |value| value.field == 1),
+        Some(root)
+    );
+    assert_eq!(tree.find_ancestor(node_f, |value| value.field == 6), None);
+    assert_eq!(tree.find_ancestor(root, |_| true), None);
+}
+
+#[test]
+fn find_ancestor_removed_ancestor() {
+    let (mut tree, node_c) = nested_tree();
+    let node_f = tree.nth_in_depth_first(node_c, 2).unwrap().unwrap();
+
+    tree.remove(node_c).unwrap();
+
+    assert_eq!(tree.find_ancestor(node_f, |_| true), None);
+}
+
+#[test]
+fn find_path() {
+    let (tree, node_c) = nested_tree();
+    let root = tree.get_root_ref().unwrap();
+    let node_e = tree.nth_in_depth_first(node_c, 1).unwrap().unwrap();
+
+    assert_eq!(
+        tree.find_path(|value| value.field == 5),
+        Some(vec![root, node_c, node_e])
+    );
+    assert_eq!(
+        tree.find_path(|value| value.field == 3),
+        Some(vec![root, node_c])
+    );
+    assert_eq!(tree.find_path(|value| value.field == 1), Some(vec![root]));
+    assert_eq!(tree.find_path(|_| false), None);
+    assert_eq!(Tree::<i32>::new().find_path(|_| true), None);
+}
+
+#[test]
+fn map_preserves_node_refs() {
+    let (mut tree, node_c) = nested_tree();
+    let node_x = tree.node(TestData { field: 7 });
+    let node_b = tree
+        .nth_in_depth_first(tree.get_root_ref().unwrap(), 1)
+        .unwrap()
+        .unwrap();
+    tree.remove(node_b).unwrap();
+
+    let new_tree = tree.map(|value| value.field * 3).unwrap();
+
+    assert_eq!(new_tree.len(), tree.len());
+    assert_eq!(new_tree.get_root_ref(), tree.get_root_ref());
+    assert_eq!(new_tree.get(node_c), Some(&9));
+    assert_eq!(new_tree.get(node_x), Some(&21));
+    assert_eq!(new_tree.get(node_b), None);
+    assert_eq!(
+        new_tree.get_children(node_c).unwrap().collect::<Vec<_>>(),
+        tree.get_children(node_c).unwrap().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        new_tree.depth_first(true).unwrap().collect::<Vec<_>>(),
+        tree.depth_first(true).unwrap().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn structural_hash() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashSet;
+
+    fn hash(tree: &Tree<i32>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        tree.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let mut tree = Tree::new();
+    let removed = tree.node(0);
+    let root = tree.root(1).unwrap();
+    let child = tree.child_node(root, 2).unwrap();
+    tree.child_node(child, 3).unwrap();
+    tree.remove(removed).unwrap();
+
+    let mut other = Tree::new();
+    let root = other.root(1).unwrap();
+    let child = other.child_node(root, 2).unwrap();
+    other.child_node(child, 3).unwrap();
+
+    assert_eq!(hash(&tree), hash(&other));
+    other.child_node(root, 3).unwrap();
+    assert_ne!(hash(&tree), hash(&other));
+
+    let tree_root = tree.get_root_ref().unwrap();
+    let tree_child = tree.get_children(tree_root).unwrap().next().unwrap();
+    let moved = tree.child_node(tree_child, 3).unwrap();
+    tree.append_child(tree_root, moved).unwrap();
+    assert_eq!(hash(&tree), hash(&other));
+
+    let refs: HashSet<NodeRef> = other.depth_first(true).unwrap().collect();
+    assert_eq!(refs.len(), 4);
+}
+
+#[test]
+fn cmp_document_order() {
+    use std::cmp::Ordering;
+
+    let (mut tree, node_c) = nested_tree();
+    let expected: Vec<NodeRef> = tree.depth_first(true).unwrap().collect();
+
+    let mut refs = expected.clone();
+    refs.reverse();
+    refs.sort_by(|a, b| tree.cmp_document_order(*a, *b).unwrap());
+    assert_eq!(refs, expected);
+    assert_eq!(tree.cmp_document_order(node_c, node_c), Ok(Ordering::Equal));
+
+    let detached = tree.node(TestData { field: 7 });
+    assert_eq!(
+        tree.cmp_document_order(node_c, detached),
+        Err(TreeError::with_kind(
+            ErrorKind::InvalidInput,
+            "Nodes do not share a common ancestor."
+        ))
+    );
+}
+
+#[test]
+fn cmp_document_order_removed_ancestor() {
+    let (mut tree, node_c) = nested_tree();
+    let root = tree.get_root_ref().unwrap();
+    let node_e = tree.nth_in_depth_first(node_c, 1).unwrap().unwrap();
+
+    tree.remove(node_c).unwrap();
+
+    assert_eq!(
+        tree.cmp_document_order(root, node_e),
+        Err(TreeError::with_kind(
+            ErrorKind::InvalidInput,
+            "Nodes do not share a common ancestor."
+        ))
+    );
+}
+
+#[test]
+fn sort_refs_in_traversal_order() {
+    let (mut tree, node_c) = nested_tree();
+    let expected: Vec<NodeRef> = tree.depth_first(true).unwrap().collect();
+
+    let mut refs = vec![
+        expected[5],
+        expected[0],
+        expected[3],
+        expected[1],
+        expected[3],
+    ];
+    tree.sort_refs_in_traversal_order(&mut refs).unwrap();
+    assert_eq!(
+        refs,
+        vec![
+            expected[0],
+            expected[1],
+            expected[3],
+            expected[3],
+            expected[5]
+        ]
+    );
+
+    let detached = tree.node(TestData { field: 7 });
+    let mut refs = vec![detached, node_c];
+    assert!(tree.sort_refs_in_traversal_order(&mut refs).is_err());
+    assert_eq!(refs, vec![detached, node_c]);
+
+    let node_e = tree.nth_in_depth_first(node_c, 1).unwrap().unwrap();
+    tree.remove(node_c).unwrap();
+    let mut refs = vec![node_e, expected[0]];
+    assert!(tree.sort_refs_in_traversal_order(&mut refs).is_err());
+}
+
+#[test]
+fn range() {
+    let (mut tree, node_c) = nested_tree();
+    let order: Vec<NodeRef> = tree.depth_first(true).unwrap().collect();
+
+    let range: Vec<NodeRef> = tree.range(order[4], order[2]).unwrap().collect();
+    assert_eq!(range, order[2..=4].to_vec());
+    let range: Vec<NodeRef> = tree.range(order[0], order[5]).unwrap().collect();
+    assert_eq!(range, order);
+    assert_eq!(tree.range(node_c, node_c).unwrap().count(), 1);
+
+    tree.remove(order[2]).unwrap();
+    let range: Vec<NodeRef> = tree.range(order[1], node_c).unwrap().collect();
+    assert_eq!(range, vec![order[1], node_c]);
+}
+
+#[test]
+fn next_prev_in_preorder() {
+    let (tree, _) = nested_tree();
+    let order: Vec<NodeRef> = tree.depth_first(true).unwrap().collect();
+
+    let mut forward = vec![order[0]];
+    while let Some(next) = tree.next_in_preorder(*forward.last().unwrap()).unwrap() {
+        forward.push(next);
+    }
+    assert_eq!(forward, order);
+
+    let mut backward = vec![order[5]];
+    while let Some(prev) = tree.prev_in_preorder(*backward.last().unwrap()).unwrap() {
+        backward.push(prev);
+    }
+    backward.reverse();
+    assert_eq!(backward, order);
+
+    assert!(tree.next_in_preorder(NodeRef::new(20)).is_err());
+}
+
+#[test]
+fn breadth_first_rev() {
+    let (tree, node_c) = nested_tree();
+    let fields = |nodes: BreadthFirstRevIterator| -> Vec<i32> {
+        nodes
+            .map(|node_ref| tree.get(node_ref).unwrap().field)
+            .collect()
+    };
+
+    assert_eq!(
+        fields(tree.breadth_first_rev().unwrap()),
+        vec![4, 5, 6, 2, 3, 1]
+    );
+    assert_eq!(
+        fields(tree.breadth_first_rev_of(node_c).unwrap()),
+        vec![5, 6, 3]
+    );
+
+    let mut single = Tree::new();
+    let root = single.root(0).unwrap();
+    assert_eq!(
+        single.breadth_first_rev().unwrap().collect::<Vec<_>>(),
+        vec![root]
+    );
+    assert!(Tree::<i32>::new().breadth_first_rev().is_err());
+}
+
+#[test]
+fn breadth_first_with_level() {
+    let (tree, node_c) = nested_tree();
+    let root = tree.get_root_ref().unwrap();
+    let levels = |node_ref, max_level| -> Vec<(i32, usize)> {
+        tree.breadth_first_with_level(node_ref, max_level)
+            .unwrap()
+            .map(|(node_ref, level)| (tree.get(node_ref).unwrap().field, level))
+            .collect()
+    };
+
+    assert_eq!(
+        levels(root, usize::MAX),
+        vec![(1, 0), (2, 1), (3, 1), (4, 2), (5, 2), (6, 2)]
+    );
+    assert_eq!(levels(root, 1), vec![(1, 0), (2, 1), (3, 1)]);
+    assert_eq!(levels(node_c, 0), vec![(3, 0)]);
+}
+
+#[test]
+fn axes() {
+    let (tree, node_c) = nested_tree();
+    let values = |refs: Vec<NodeRef>| -> Vec<i32> {
+        refs.iter()
+            .map(|node_ref| tree.get(*node_ref).unwrap().field)
+            .collect()
+    };
+    let children: Vec<NodeRef> = tree.get_children(node_c).unwrap().collect();
+
+    assert_eq!(
+        values(tree.ancestors_or_self(children[0]).unwrap().collect()),
+        vec![5, 3, 1]
+    );
+    assert_eq!(
+        values(tree.descendants(node_c).unwrap().collect()),
+        vec![5, 6]
+    );
+    assert_eq!(
+        values(tree.descendants_or_self(node_c).unwrap().collect()),
+        vec![3, 5, 6]
+    );
+    assert_eq!(
+        values(tree.following_siblings(children[0]).unwrap().collect()),
+        vec![6]
+    );
+    assert_eq!(
+        values(tree.preceding_siblings(node_c).unwrap().collect()),
+        vec![2]
+    );
+    assert_eq!(tree.following_siblings(node_c).unwrap().count(), 0);
+    let root = tree.get_root_ref().unwrap();
+    assert_eq!(tree.preceding_siblings(root).unwrap().count(), 0);
+}
+
+#[test]
+fn assign_preorder_ids() {
+    let (mut tree, node_c) = nested_tree();
+    let detached = tree.node(TestData { field: 7 });
+
+    let ids = tree.assign_preorder_ids();
+    assert_eq!(ids.len(), 6);
+    assert_eq!(ids[&tree.get_root_ref().unwrap()], 0);
+    assert_eq!(ids[&node_c], 3);
+    assert!(!ids.contains_key(&detached));
+    assert!(Tree::<i32>::new().assign_preorder_ids().is_empty());
+}
+
+#[test]
+fn unchecked_access() {
+    let (mut tree, node_c) = nested_tree();
+
+    unsafe {
+        assert_eq!(tree.get_unchecked(node_c), &TestData { field: 3 });
+        tree.get_unchecked_mut(node_c).field = 30;
+        assert_eq!(
+            tree.children_unchecked(node_c).collect::<Vec<_>>(),
+            tree.get_children(node_c).unwrap().collect::<Vec<_>>()
+        );
+    }
+    assert_eq!(tree.get(node_c), Some(&TestData { field: 30 }));
+}
+
+#[test]
+fn chunked_depth_first() {
+    let (tree, _) = nested_tree();
+    let expected: Vec<NodeRef> = tree.depth_first(true).unwrap().collect();
+
+    let mut chunks = tree.chunked_depth_first(4).unwrap();
+    let mut sizes = Vec::new();
+    let mut nodes = Vec::new();
+    while let Some(chunk) = chunks.next_chunk() {
+        sizes.push(chunk.len());
+        nodes.extend_from_slice(chunk);
+    }
+
+    assert_eq!(sizes, vec![4, 2]);
+    assert_eq!(nodes, expected);
+    assert!(tree.chunked_depth_first(0).is_err());
+}
+
+#[test]
+fn node_ref_niche() {
+    use std::mem::size_of;
+
+    assert_eq!(size_of::<Option<NodeRef>>(), size_of::<NodeRef>());
+    #[cfg(not(all(feature = "debug-validate", debug_assertions)))]
+    assert_eq!(size_of::<NodeRef>(), size_of::<usize>());
+    let (_, node_a, _) = tree2();
+    assert_eq!(format!("{:?}", node_a), "NodeRef { id: 0 }");
+}
+
+#[test]
+fn rebuild() {
+    let (mut tree, _) = nested_tree();
+    let capacity = tree.nodes.capacity();
+
+    for frame in 0..3 {
+        let root = tree.rebuild(|tree| {
+            let root = tree.root(TestData { field: frame }).unwrap();
+            tree.child_node(root, TestData { field: 10 }).unwrap();
+            root
+        });
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree.get(root), Some(&TestData { field: frame }));
+        assert_eq!(tree.get_children(root).unwrap().count(), 1);
+    }
+    assert_eq!(tree.nodes.capacity(), capacity);
+    assert!(tree
+        .spare_children
+        .iter()
+        .all(|children| children.is_empty()));
+}
+
+#[test]
+fn induced_subtree() {
+    let (mut tree, node_c) = nested_tree();
+    let children: Vec<NodeRef> = tree.get_children(node_c).unwrap().collect();
+
+    let induced = tree.induced_subtree(&[children[1]]).unwrap();
+    let values: Vec<i32> = induced
+        .depth_first(true)
+        .unwrap()
+        .map(|node_ref| induced.get(node_ref).unwrap().field)
+        .collect();
+    assert_eq!(values, vec![1, 3, 6]);
+
+    assert!(tree.induced_subtree(&[]).unwrap().is_empty());
+    let detached = tree.node(TestData { field: 7 });
+    assert_eq!(
+        tree.induced_subtree(&[detached]).unwrap_err(),
+        TreeError::with_kind(
+            ErrorKind::InvalidInput,
+            "Node is not reachable from the root."
+        )
+    );
+}
+
+#[test]
+fn unreachable_nodes() {
+    let (mut tree, node_c) = nested_tree();
+    let detached = tree.node(TestData { field: 7 });
+    let orphans: Vec<NodeRef> = tree.get_children(node_c).unwrap().collect();
+    tree.remove(node_c).unwrap();
+
+    assert_eq!(tree.iter_all_nodes().count(), 6);
+    assert_eq!(
+        tree.unreachable_nodes(),
+        vec![orphans[0], orphans[1], detached]
+    );
+}
+
+#[test]
+fn gc() {
+    let (mut tree, node_c) = nested_tree();
+    let detached = tree.node(TestData { field: 7 });
+    let locked = tree.node(TestData { field: 8 });
+    tree.lock_subtree(locked).unwrap();
+    tree.remove(node_c).unwrap();
+
+    assert_eq!(tree.gc(), 3);
+    assert_eq!(tree.get(detached), None);
+    assert_eq!(tree.unreachable_nodes(), vec![locked]);
+    assert_eq!(tree.len(), 3);
+    assert_eq!(tree.gc(), 0);
+}
+
+#[test]
+fn heap_size_estimate() {
+    let mut tree = Tree::new();
+    let root = tree.root(String::from("root")).unwrap();
+    let leaf = tree.child_node(root, String::from("leaf")).unwrap();
+
+    let leaf_size = tree.heap_size_estimate(leaf, String::capacity).unwrap();
+    let root_size = tree.heap_size_estimate(root, String::capacity).unwrap();
+    assert!(leaf_size >= 4 + std::mem::size_of::<String>());
+    assert!(root_size > 2 * leaf_size);
+}
+
+#[test]
+fn error_context() {
+    let (mut tree, node_c) = nested_tree();
+    let node_e = tree.nth_in_depth_first(node_c, 1).unwrap().unwrap();
+    tree.lock_subtree(node_c).unwrap();
+
+    let error = tree.remove(node_e).unwrap_err();
+    assert_eq!(error.operation(), Some("remove"));
+    assert_eq!(error.nodes(), &[node_e]);
+    assert_eq!(error.to_string(), "Node is inside a locked subtree.");
+    assert_eq!(
+        format!("{:#}", error),
+        format!(
+            "Node is inside a locked subtree. (in remove) [nodes: [{:?}]]",
+            node_e
+        )
+    );
+    assert!(error.source().is_none());
+
+    let error = TreeError::new("Invalid input.").with_source(error);
+    assert_eq!(
+        error.source().unwrap().to_string(),
+        "Node is inside a locked subtree."
+    );
+}
+
+#[cfg(feature = "io")]
+#[test]
+fn error_into_io_error() {
+    let (mut tree, node_c) = nested_tree();
+    tree.lock_subtree(node_c).unwrap();
+
+    let error: std::io::Error = tree.remove(node_c).unwrap_err().into();
+    assert_eq!(error.kind(), std::io::ErrorKind::PermissionDenied);
+    assert_eq!(error.to_string(), "Node is inside a locked subtree.");
+
+    let error: std::io::Error = tree
+        .try_get_parent(tree.get_root_ref().unwrap())
+        .unwrap_err()
+        .into();
+    assert_eq!(error.kind(), std::io::ErrorKind::NotFound);
+}
+
+#[test]
+fn clone_to_depth() {
+    let (tree, _) = nested_tree();
+    let root = tree.get_root_ref().unwrap();
+    let field = |tree: &Tree<TestData>| -> Vec<i32> {
+        tree.depth_first(true)
+            .unwrap()
+            .map(|node_ref| tree.get(node_ref).unwrap().field)
+            .collect()
+    };
+
+    let preview = tree.clone_to_depth(root, 1, None).unwrap();
+    assert_eq!(field(&preview), vec![1, 2, 3]);
+
+    let placeholder = Some(TestData { field: 0 });
+    let preview = tree.clone_to_depth(root, 1, placeholder).unwrap();
+    assert_eq!(field(&preview), vec![1, 2, 0, 3, 0]);
+
+    let preview = tree.clone_to_depth(root, 5, None).unwrap();
+    assert!(preview.structural_eq(&tree));
+
+    let (mut tree, node_c) = nested_tree();
+    let children: Vec<NodeRef> = tree.get_children(node_c).unwrap().collect();
+    tree.remove_many(&children).unwrap();
+    let root = tree.get_root_ref().unwrap();
+    let placeholder = Some(TestData { field: 0 });
+    let preview = tree.clone_to_depth(root, 1, placeholder).unwrap();
+    assert_eq!(field(&preview), vec![1, 2, 0, 3]);
+}
+
+#[test]
+fn remove_with() {
+    let fields = |tree: &Tree<TestData>| -> Vec<i32> {
+        tree.depth_first(true)
+            .unwrap()
+            .map(|node_ref| tree.get(node_ref).unwrap().field)
+            .collect()
+    };
+
+    let (mut tree, node_c) = nested_tree();
+    assert_eq!(
+        tree.remove_with(node_c, RemoveBehavior::Orphan),
+        Ok(RemovalReport {
+            removed: 1,
+            orphaned: 2,
+            tombstones: 1
+        })
+    );
+    assert_eq!(fields(&tree), vec![1, 2, 4]);
+    assert_eq!(tree.unreachable_nodes().len(), 2);
+
+    let (mut tree, node_c) = nested_tree();
+    assert_eq!(
+        tree.remove_with(node_c, RemoveBehavior::Recursive),
+        Ok(RemovalReport {
+            removed: 3,
+            orphaned: 0,
+            tombstones: 3
+        })
+    );
+    assert_eq!(fields(&tree), vec![1, 2, 4]);
+    assert_eq!(tree.len(), 3);
+    assert!(tree.unreachable_nodes().is_empty());
+
+    let (mut tree, node_c) = nested_tree();
+    let root = tree.get_root_ref().unwrap();
+    assert_eq!(
+        tree.remove_with(node_c, RemoveBehavior::PromoteChildren),
+        Ok(RemovalReport {
+            removed: 1,
+            orphaned: 0,
+            tombstones: 1
+        })
+    );
+    assert_eq!(fields(&tree), vec![1, 2, 4, 5, 6]);
+    assert_eq!(tree.get_children(root).unwrap().count(), 3);
+    assert!(tree
+        .remove_with(root, RemoveBehavior::PromoteChildren)
+        .is_err());
+    assert_eq!(tree.len(), 5);
+}
+
+#[test]
+fn remove_with_promotes_to_root() {
+    let mut tree = Tree::with_child_storage(ChildStorage::LinkedList);
+    let root = tree.root(1).unwrap();
+    let child = tree.child_node(root, 2).unwrap();
+    tree.child_node(child, 3).unwrap();
+
+    tree.remove_with(root, RemoveBehavior::PromoteChildren)
+        .unwrap();
+    assert_eq!(tree.get_root_ref(), Some(child));
+    assert_eq!(tree.get_parent(child), Ok(None));
+
+    tree.lock_subtree(child).unwrap();
+    let error = tree
+        .remove_with(child, RemoveBehavior::Recursive)
+        .unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Locked);
+}
+
+#[test]
+fn export_copy_values() {
+    let mut tree = Tree::new();
+    let root = tree.root(1.0).unwrap();
+    let child = tree.child_node(root, 2.0).unwrap();
+    tree.child_node(child, 3.0).unwrap();
+    tree.child_node(root, 4.0).unwrap();
+
+    assert_eq!(tree.values_to_vec_preorder(child), Ok(vec![2.0, 3.0]));
+    assert_eq!(
+        tree.values_to_vec_preorder(root),
+        Ok(vec![1.0, 2.0, 3.0, 4.0])
+    );
+
+    let mut buf = [0.0; 5];
+    assert_eq!(tree.write_values_into(&mut buf), Ok(4));
+    assert_eq!(buf, [1.0, 2.0, 3.0, 4.0, 0.0]);
+    let error = tree.write_values_into(&mut buf[..3]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+fn traversal_iterators_are_cloneable_and_fused() {
+    let (tree, node_c) = nested_tree();
+
+    let mut iterator = tree.depth_first(true).unwrap();
+    iterator.next();
+    let copy = iterator.clone();
+    assert_eq!(copy.count(), 5);
+    assert_eq!(iterator.size_hint(), (5, Some(5)));
+    iterator.next_back();
+    assert_eq!(iterator.size_hint(), (4, Some(4)));
+    assert_eq!(iterator.by_ref().count(), 4);
+    assert_eq!(iterator.next(), None);
+    assert_eq!(iterator.size_hint().0, 0);
+
+    let mut iterator = tree.breadth_first_rev().unwrap();
+    assert_eq!(iterator.len(), 6);
+    iterator.nth(3);
+    assert_eq!(iterator.len(), 2);
+    assert_eq!(iterator.by_ref().count(), 2);
+    assert_eq!(iterator.next(), None);
+    assert_eq!(iterator.next(), None);
+    assert_eq!(iterator.len(), 0);
+
+    let ancestors = tree.ancestors(node_c).unwrap();
+    assert!(format!("{:?}", ancestors).starts_with("AncestorsIterator"));
+}
+
+#[test]
+fn walk_skip_subtree() {
+    let (tree, node_c) = nested_tree();
+    let root = tree.get_root_ref().unwrap();
+
+    let mut walk = tree.walk(root).unwrap();
+    assert_eq!(walk.size_hint(), (6, Some(6)));
+    let mut visited = Vec::new();
+    while let Some((node_ref, data)) = walk.next() {
+        visited.push(data.field);
+        if node_ref == node_c || data.field == 2 {
+            walk.skip_subtree();
+        }
+    }
+    assert_eq!(visited, vec![1, 2, 3]);
+    assert_eq!(walk.size_hint(), (0, Some(0)));
+    assert_eq!(walk.next(), None);
+
+    let mut walk = tree.walk(root).unwrap();
+    walk.next();
+    walk.next();
+    assert_eq!(walk.size_hint(), (4, Some(4)));
+    walk.skip_subtree();
+    assert_eq!(walk.size_hint(), (3, Some(3)));
+
+    let all: Vec<i32> = tree
+        .walk(node_c)
+        .unwrap()
+        .map(|(_, data)| data.field)
+        .collect();
+    assert_eq!(all, vec![3, 5, 6]);
+}
+
+#[test]
+fn depth_first_or_empty() {
+    let tree: Tree<i32> = Tree::new();
+    assert!(tree.depth_first(true).is_err());
+    assert_eq!(tree.depth_first_or_empty(true).count(), 0);
+    assert_eq!(tree.depth_first_or_empty(true).next_back(), None);
+
+    let (tree, _) = nested_tree();
+    assert_eq!(tree.depth_first_or_empty(true).count(), 6);
+    assert_eq!(tree.depth_first_or_empty(false).count(), 5);
+}
+
+#[test]
+fn map_rootless_tree() {
+    let mut tree = Tree::new();
+    let fragment = tree.node(1);
+    let child = tree.child_node(fragment, 2).unwrap();
+
+    assert!(tree.map(|value| value * 10).is_err());
+    let mapped = tree.map_all(|value| value * 10);
+    assert_eq!(mapped.get_root_ref(), None);
+    assert_eq!(mapped.get(child), Some(&20));
+    assert_eq!(mapped.get_parent(child), Ok(Some(fragment)));
+}
+
+#[test]
+fn retain() {
+    let fields = |tree: &Tree<TestData>| -> Vec<i32> {
+        tree.depth_first(true)
+            .unwrap()
+            .map(|node_ref| tree.get(node_ref).unwrap().field)
+            .collect()
+    };
+
+    let (mut tree, node_c) = nested_tree();
+    let fragment = tree.node(TestData { field: 7 });
+    tree.child_node(fragment, TestData { field: 8 }).unwrap();
+
+    assert_eq!(
+        tree.retain(|data| data.field != 3 && data.field != 7),
+        Ok(5)
+    );
+    assert_eq!(fields(&tree), vec![1, 2, 4]);
+    assert!(tree.get(node_c).is_none());
+    assert_eq!(tree.len(), 3);
+
+    let node_b = tree
+        .get_children(tree.get_root_ref().unwrap())
+        .unwrap()
+        .next()
+        .unwrap();
+    tree.lock_subtree(node_b).unwrap();
+    let error = tree.retain(|data| data.field != 4).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Locked);
+    assert_eq!(error.operation(), Some("retain"));
+    assert_eq!(tree.len(), 3);
+
+    tree.unlock_subtree(node_b).unwrap();
+    assert_eq!(tree.retain(|data| data.field != 1), Ok(3));
+    assert_eq!(tree.get_root_ref(), None);
+    assert!(tree.is_empty());
+}
+
+#[test]
+fn clone_preserves_node_refs() {
+    let (mut tree, node_c) = nested_tree();
+    let node_b = tree
+        .get_children(tree.get_root_ref().unwrap())
+        .unwrap()
+        .next()
+        .unwrap();
+    tree.remove_with(node_b, RemoveBehavior::Recursive).unwrap();
+    let detached = tree.node(TestData { field: 7 });
+
+    let copy = tree.clone();
+    for node_ref in tree.iter_all_nodes() {
+        assert_eq!(copy.get(node_ref), tree.get(node_ref));
+        assert_eq!(copy.get_parent(node_ref), tree.get_parent(node_ref));
+    }
+    assert_eq!(copy.get(detached).unwrap().field, 7);
+    assert!(copy.get(node_b).is_none());
+
+    let (compacted, remap) = tree.clone_compacted();
+    assert_eq!(compacted.len(), tree.len());
+    assert_eq!(compacted.tombstones(), 0);
+    let new_c = remap.get(node_c).unwrap();
+    assert_eq!(compacted.get(new_c).unwrap().field, 3);
+    assert_eq!(
+        compacted.get(remap.get(detached).unwrap()).unwrap().field,
+        7
+    );
+    assert_eq!(remap.get(node_b), None);
+}
+
+#[test]
+fn into_map() {
+    struct NotClone(i32);
+
+    let mut tree = Tree::with_child_storage(ChildStorage::LinkedList);
+    let root = tree.root(NotClone(1)).unwrap();
+    let child = tree.child_node(root, NotClone(2)).unwrap();
+    let detached = tree.node(NotClone(3));
+
+    let mapped = tree.into_map(|NotClone(value)| value.to_string());
+    assert_eq!(mapped.get(child).map(String::as_str), Some("2"));
+    assert_eq!(mapped.get(detached).map(String::as_str), Some("3"));
+    assert_eq!(mapped.get_parent(child), Ok(Some(root)));
+    assert_eq!(mapped.child_storage(), ChildStorage::LinkedList);
+    assert_eq!(mapped.len(), 2);
+}
+
+#[test]
+fn remove_many_counts_orphans_once() {
+    let (mut tree, node_c) = nested_tree();
+    let root = tree.get_root_ref().unwrap();
+    let node_e = tree.get_children(node_c).unwrap().next().unwrap();
+    tree.child_node(node_e, TestData { field: 7 }).unwrap();
+
+    let report = tree.remove_many(&[root, node_e]).unwrap();
+    assert_eq!(report.removed, 2);
+    assert_eq!(report.orphaned, 5);
+    assert_eq!(tree.len(), 0);
+    assert_eq!(tree.slot_count(), report.orphaned + report.tombstones);
+    assert_eq!(tree.unreachable_nodes().len(), report.orphaned);
+}
+
+#[test]
+fn len_skips_detached_nodes() {
+    let mut tree = Tree::new();
+    let root = tree.root("r").unwrap();
+    let detached = tree.node("x");
+    tree.child_node(detached, "y").unwrap();
+    assert_eq!(tree.len(), 1);
+
+    tree.append_child(root, detached).unwrap();
+    assert_eq!(tree.len(), 3);
+
+    let other = tree.node("z");
+    tree.set_root(other, true).unwrap();
+    assert_eq!(tree.len(), 1);
+    tree.set_root(detached, true).unwrap();
+    assert_eq!(tree.len(), 2);
+    tree.set_root(root, true).unwrap();
+    assert_eq!(tree.len(), 3);
+}
+
+#[test]
+fn len_counts_reachable_nodes() {
+    let fields = |tree: &Tree<TestData>| -> Vec<i32> {
+        tree.depth_first(true)
+            .unwrap()
+            .map(|node_ref| tree.get(node_ref).unwrap().field)
+            .collect()
+    };
+
+    for storage in [ChildStorage::Vec, ChildStorage::LinkedList] {
+        let mut tree = Tree::with_child_storage(storage);
+        let root = tree.root(TestData { field: 1 }).unwrap();
+        let node_b = tree.child_node(root, TestData { field: 2 }).unwrap();
+        let node_c = tree.child_node(root, TestData { field: 3 }).unwrap();
+        let node_e = tree.child_node(node_c, TestData { field: 5 }).unwrap();
+        let node_f = tree.child_node(node_c, TestData { field: 6 }).unwrap();
+        tree.child_node(node_f, TestData { field: 7 }).unwrap();
+
+        tree.remove(node_c).unwrap();
+        assert_eq!(tree.len(), 2);
+        tree.child_node(node_e, TestData { field: 8 }).unwrap();
+        assert_eq!(tree.len(), 2);
+
+        tree.append_child(node_b, node_e).unwrap();
+        assert_eq!(fields(&tree), vec![1, 2, 5, 8]);
+        assert_eq!(tree.len(), 4);
+
+        tree.remove(node_f).unwrap();
+        assert_eq!(tree.len(), 4);
+        tree.purge_tombstones_unchecked();
+        tree.optimize_layout();
+        assert_eq!(tree.len(), 4);
+        assert_eq!(tree.gc(), 1);
+        assert_eq!(tree.len(), 4);
+        assert_eq!(tree.len(), fields(&tree).len());
+    }
+}
+
+#[test]
+fn get_children_removed_child() {
+    for storage in [ChildStorage::Vec, ChildStorage::LinkedList] {
+        let mut tree = Tree::with_child_storage(storage);
+        let root = tree.root("r").unwrap();
+        let node_a = tree.child_node(root, "a").unwrap();
+        let node_b = tree.child_node(root, "b").unwrap();
+        let node_c = tree.child_node(root, "c").unwrap();
+
+        tree.remove(node_a).unwrap();
+        tree.remove(node_c).unwrap();
+
+        let children = tree.get_children(root).unwrap();
+        assert_eq!(children.len(), 1);
+        assert_eq!(children.collect::<Vec<_>>(), vec![node_b]);
+    }
+}
+
+#[test]
+fn get_children_same_for_all_storages() {
+    let children = |tree: &Tree<&'static str>, node_ref| {
+        let children = tree.get_children(node_ref).unwrap();
+        let len = children.len();
+        let values: Vec<&str> = children.map(|child| *tree.get(child).unwrap()).collect();
+        assert_eq!(values.len(), len);
+        values
+    };
+
+    for storage in [ChildStorage::Vec, ChildStorage::LinkedList] {
+        let mut tree = Tree::with_child_storage(storage);
+        let root = tree.root("r").unwrap();
+        let nodes: Vec<NodeRef> = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|value| tree.child_node(root, *value).unwrap())
+            .collect();
+        tree.child_node(nodes[0], "f").unwrap();
+
+        tree.remove_with(nodes[0], RemoveBehavior::PromoteChildren)
+            .unwrap();
+        assert_eq!(children(&tree, root), vec!["f", "b", "c", "d", "e"]);
+        tree.remove_many(&[nodes[1], nodes[3]]).unwrap();
+        assert_eq!(children(&tree, root), vec!["f", "c", "e"]);
+        tree.retain(|value| *value != "c").unwrap();
+        assert_eq!(children(&tree, root), vec!["f", "e"]);
+        tree.remove_with(nodes[4], RemoveBehavior::Recursive)
+            .unwrap();
+        assert_eq!(children(&tree, root), vec!["f"]);
+    }
+}
+
+#[test]
+fn append_child_moves_child() {
+    for storage in [ChildStorage::Vec, ChildStorage::LinkedList] {
+        let mut tree = Tree::with_child_storage(storage);
+        let root = tree.root("r").unwrap();
+        let parent_1 = tree.child_node(root, "p1").unwrap();
+        let parent_2 = tree.child_node(root, "p2").unwrap();
+        let child = tree.child_node(parent_1, "x").unwrap();
+        tree.append_child(parent_2, child).unwrap();
+
+        let order: Vec<NodeRef> = tree.depth_first(true).unwrap().collect();
+        assert_eq!(order, vec![root, parent_1, parent_2, child]);
+        assert_eq!(tree.get_children(parent_1).unwrap().count(), 0);
+
+        let (compacted, _) = tree.clone_compacted();
+        assert_eq!(compacted.len(), 4);
+    }
+}
@@ -0,0 +1,49 @@
+//! Instrumentation of bulk operations.
+//!
+//! With the `tracing` feature, each bulk operation runs inside a
+//! `lineartree` span at debug level and emits an event when it
+//! completes, with the number of nodes in the tree, the number of
+//! affected nodes and the duration in microseconds. Without the
+//! feature, [Operation] is empty and compiles to nothing.
+
+#[cfg(feature = "tracing")]
+use std::time::Instant;
+
+/// Guard measuring a bulk operation, from [Operation::start]
+/// to [Operation::finish].
+pub(crate) struct Operation {
+    #[cfg(feature = "tracing")]
+    span: tracing::span::EnteredSpan,
+    #[cfg(feature = "tracing")]
+    start: Instant,
+}
+
+impl Operation {
+    /// Start measuring an operation on a tree with `nodes` live nodes.
+    #[inline]
+    #[allow(unused_variables)]
+    pub(crate) fn start(name: &'static str, nodes: usize) -> Self {
+        Self {
+            #[cfg(feature = "tracing")]
+            span: tracing::debug_span!("lineartree", operation = name, nodes).entered(),
+            #[cfg(feature = "tracing")]
+            start: Instant::now(),
+        }
+    }
+
+    /// Report the completion of the operation, which
+    /// added, moved or removed `affected` nodes.
+    #[inline]
+    #[allow(unused_variables)]
+    pub(crate) fn finish(self, affected: usize) {
+        #[cfg(feature = "tracing")]
+        {
+            let _span = self.span;
+            tracing::debug!(
+                affected,
+                elapsed_us = self.start.elapsed().as_micros() as u64,
+                "completed"
+            );
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use super::{NodeRef, Result, Tree};
+
+/// Entry popped from a [TraversalStack].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct StackEntry {
+    /// The node to process.
+    pub node_ref: NodeRef,
+    /// Depth of the node, as given when it was pushed.
+    pub depth: usize,
+    /// Whether the entry was pushed with [TraversalStack::push_exit],
+    /// to process the node after its children.
+    pub exit: bool,
+}
+
+/// Explicit stack to write recursive algorithms as loops.
+///
+/// Recursion over deep trees can overflow the call stack. With this
+/// stack, the recursive calls become pushes and the loop pops the next
+/// node to process, so the depth of the tree is only limited by memory.
+/// Pushing an exit entry before the children allows processing a node
+/// again once all its descendants are done, like the code after the
+/// recursive calls.
+///
+/// ```ignore
+/// // Compute the size of each subtree
+/// let mut sizes = HashMap::new();
+/// let mut stack = TraversalStack::with_start(root);
+/// while let Some(entry) = stack.pop() {
+///     if entry.exit {
+///         let size = 1 + tree.get_children(entry.node_ref)?.map(|c| sizes[&c]).sum::<usize>();
+///         sizes.insert(entry.node_ref, size);
+///     } else {
+///         stack.push_exit(entry.node_ref, entry.depth);
+///         stack.push_children(&tree, entry.node_ref, entry.depth)?;
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TraversalStack {
+    entries: Vec<StackEntry>,
+}
+
+impl TraversalStack {
+    /// Create an empty stack.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a stack containing a starting node at depth 0.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the starting node.
+    pub fn with_start(node_ref: NodeRef) -> Self {
+        let mut stack = Self::new();
+        stack.push(node_ref, 0);
+        stack
+    }
+
+    /// Push a node to process.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    /// * `depth` - Depth of the node.
+    pub fn push(&mut self, node_ref: NodeRef, depth: usize) {
+        self.entries.push(StackEntry {
+            node_ref,
+            depth,
+            exit: false,
+        });
+    }
+
+    /// Push a node to process again after the entries pushed later.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    /// * `depth` - Depth of the node.
+    pub fn push_exit(&mut self, node_ref: NodeRef, depth: usize) {
+        self.entries.push(StackEntry {
+            node_ref,
+            depth,
+            exit: true,
+        });
+    }
+
+    /// Push the children of a node, so that they are popped in order,
+    /// one level deeper than the node.
+    ///
+    /// *Arguments:*
+    /// * `tree` - The tree containing the node.
+    /// * `node_ref` - [NodeRef] of the node.
+    /// * `depth` - Depth of the node.
+    ///
+    /// *Returns:* The number of pushed children.
+    ///            Returns error if the node does not exist.
+    pub fn push_children<T>(
+        &mut self,
+        tree: &Tree<T>,
+        node_ref: NodeRef,
+        depth: usize,
+    ) -> Result<usize> {
+        tree.get_children(node_ref)?;
+        let start = self.entries.len();
+        for child_ref in tree.live_children(node_ref) {
+            self.push(child_ref, depth + 1);
+        }
+        self.entries[start..].reverse();
+        Ok(self.entries.len() - start)
+    }
+
+    /// Pop the next entry to process.
+    ///
+    /// *Returns:* The entry or `None` if the stack is empty.
+    pub fn pop(&mut self) -> Option<StackEntry> {
+        self.entries.pop()
+    }
+
+    /// Get the entry that will be popped next.
+    pub fn peek(&self) -> Option<&StackEntry> {
+        self.entries.last()
+    }
+
+    /// Get the number of entries on the stack.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check whether the stack is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Remove all entries, keeping the allocated memory.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,38 @@
+use std::collections::HashMap;
+
+use super::*;
+
+#[test]
+fn traversal_stack() {
+    let mut tree = Tree::new();
+    let root = tree.root(1).unwrap();
+    let a = tree.child_node(root, 2).unwrap();
+    tree.child_node(a, 3).unwrap();
+    tree.child_node(root, 4).unwrap();
+
+    let mut sums = HashMap::new();
+    let mut order = Vec::new();
+    let mut stack = TraversalStack::with_start(root);
+    while let Some(entry) = stack.pop() {
+        if entry.exit {
+            let sum = *tree.get(entry.node_ref).unwrap()
+                + tree
+                    .get_children(entry.node_ref)
+                    .unwrap()
+                    .map(|child_ref| sums[&child_ref])
+                    .sum::<i32>();
+            sums.insert(entry.node_ref, sum);
+        } else {
+            order.push((*tree.get(entry.node_ref).unwrap(), entry.depth));
+            stack.push_exit(entry.node_ref, entry.depth);
+            stack
+                .push_children(&tree, entry.node_ref, entry.depth)
+                .unwrap();
+        }
+    }
+
+    assert_eq!(order, vec![(1, 0), (2, 1), (3, 2), (4, 1)]);
+    assert_eq!(sums[&root], 10);
+    assert_eq!(sums[&a], 5);
+    assert!(stack.is_empty());
+}
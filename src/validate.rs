@@ -0,0 +1,100 @@
+//! Checks of node references, enabled by the `debug-validate` feature
+//! in debug builds.
+//!
+//! Each tree gets a unique id and a generation counter for each slot,
+//! which is incremented when the slot is reused by a new node. The
+//! references returned by [Tree::node](crate::Tree::node) are stamped
+//! with both, so that using a reference with the wrong tree, or after
+//! its slot was reused, panics instead of silently accessing another
+//! node. Without the feature, or in release builds, the types of this
+//! module are empty and the checks compile to nothing.
+
+#[cfg(all(feature = "debug-validate", debug_assertions))]
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use super::NodeRef;
+
+#[cfg(all(feature = "debug-validate", debug_assertions))]
+static NEXT_TREE_ID: AtomicU32 = AtomicU32::new(1);
+
+/// Origin of a node reference. References created internally from an
+/// index are not stamped and are not checked.
+#[derive(Debug, Copy, Clone, Default)]
+pub(crate) struct Stamp {
+    #[cfg(all(feature = "debug-validate", debug_assertions))]
+    tree: u32,
+    #[cfg(all(feature = "debug-validate", debug_assertions))]
+    generation: u32,
+}
+
+/// Identity of a tree and generations of its slots.
+#[derive(Debug, Clone)]
+pub(crate) struct Registry {
+    #[cfg(all(feature = "debug-validate", debug_assertions))]
+    tree: u32,
+    #[cfg(all(feature = "debug-validate", debug_assertions))]
+    generations: Vec<u32>,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for Registry {
+    fn default() -> Self {
+        Self {
+            #[cfg(all(feature = "debug-validate", debug_assertions))]
+            tree: NEXT_TREE_ID.fetch_add(1, Ordering::Relaxed),
+            #[cfg(all(feature = "debug-validate", debug_assertions))]
+            generations: Vec::new(),
+        }
+    }
+}
+
+impl Registry {
+    /// Stamp the reference of a node just created in a slot.
+    #[inline]
+    #[allow(unused_mut)]
+    pub(crate) fn stamp(&mut self, mut node_ref: NodeRef) -> NodeRef {
+        #[cfg(all(feature = "debug-validate", debug_assertions))]
+        {
+            let index = node_ref.index();
+            if index < self.generations.len() {
+                self.generations[index] += 1;
+            } else {
+                self.generations.resize(index + 1, 0);
+            }
+            node_ref.stamp = Stamp {
+                tree: self.tree,
+                generation: self.generations[index],
+            };
+        }
+        node_ref
+    }
+
+    /// Panic if a stamped reference belongs to another tree,
+    /// or to a previous node in a reused slot.
+    #[inline]
+    #[allow(unused_variables)]
+    pub(crate) fn check(&self, node_ref: NodeRef) {
+        #[cfg(all(feature = "debug-validate", debug_assertions))]
+        {
+            let stamp = node_ref.stamp;
+            if stamp.tree == 0 {
+                return;
+            }
+            assert!(
+                stamp.tree == self.tree,
+                "{:?} belongs to another tree.",
+                node_ref
+            );
+            assert!(
+                self.generations.get(node_ref.index()) == Some(&stamp.generation),
+                "{:?} refers to a removed node whose slot was reused.",
+                node_ref
+            );
+        }
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(all(test, feature = "debug-validate", debug_assertions))]
+mod tests;
@@ -0,0 +1,35 @@
+use crate::Tree;
+
+#[test]
+#[should_panic(expected = "belongs to another tree")]
+fn ref_of_another_tree() {
+    let mut tree = Tree::new();
+    let mut other = Tree::new();
+    tree.root(1).unwrap();
+    let other_root = other.root(2).unwrap();
+
+    tree.get(other_root);
+}
+
+#[test]
+#[should_panic(expected = "slot was reused")]
+fn ref_to_reused_slot() {
+    let mut tree = Tree::new();
+    let root = tree.root(1).unwrap();
+    let child = tree.child_node(root, 2).unwrap();
+    tree.remove(child).unwrap();
+    tree.purge_tombstones_unchecked();
+    let reused = tree.node(3);
+    assert_eq!(reused, child);
+
+    tree.get(reused);
+    tree.get(child);
+}
+
+#[test]
+fn clone_accepts_refs() {
+    let mut tree = Tree::new();
+    let root = tree.root(1).unwrap();
+    let clone = tree.clone();
+    assert_eq!(clone.get(root), Some(&1));
+}
@@ -0,0 +1,119 @@
+//! Conversion of parsed configuration values into trees.
+//!
+//! Each node contains a key and a value. Keys of maps become the keys of
+//! the child nodes, while array items are keyed by their index. The root
+//! node has an empty key. Scalars are stored in leaf nodes, while nodes
+//! created from maps and arrays hold an empty map or array, so that the
+//! content is not duplicated across levels.
+
+use super::{NodeRef, Tree};
+
+fn build<V>(value: V, mut split: impl FnMut(V) -> (V, Vec<(String, V)>)) -> Tree<(String, V)> {
+    let mut tree = Tree::new();
+    let mut stack: Vec<(String, V, Option<NodeRef>)> = vec![(String::new(), value, None)];
+
+    while let Some((key, value, parent_ref)) = stack.pop() {
+        let (value, children) = split(value);
+        let node_ref = match parent_ref {
+            None => tree.root((key, value)).unwrap(),
+            Some(parent_ref) => tree.child_node(parent_ref, (key, value)).unwrap(),
+        };
+        stack.extend(
+            children
+                .into_iter()
+                .rev()
+                .map(|(key, value)| (key, value, Some(node_ref))),
+        );
+    }
+
+    tree
+}
+
+fn indexed<V>(items: Vec<V>) -> Vec<(String, V)> {
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(index, item)| (index.to_string(), item))
+        .collect()
+}
+
+#[cfg(feature = "json")]
+impl Tree<(String, serde_json::Value)> {
+    /// Create a tree from a parsed JSON value.
+    ///
+    /// Requires the `json` feature.
+    ///
+    /// *Arguments:*
+    /// * `value` - The JSON value to convert.
+    ///
+    /// *Returns:* A tree with one node per object entry and array item.
+    pub fn from_json_value(value: serde_json::Value) -> Self {
+        use serde_json::{Map, Value};
+
+        build(value, |value| match value {
+            Value::Object(map) => (Value::Object(Map::new()), map.into_iter().collect()),
+            Value::Array(items) => (Value::Array(Vec::new()), indexed(items)),
+            scalar => (scalar, Vec::new()),
+        })
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl Tree<(String, serde_yaml::Value)> {
+    /// Create a tree from a parsed YAML value.
+    ///
+    /// Keys which are not strings are converted to their YAML
+    /// representation. Requires the `yaml` feature.
+    ///
+    /// *Arguments:*
+    /// * `value` - The YAML value to convert.
+    ///
+    /// *Returns:* A tree with one node per mapping entry and sequence item.
+    pub fn from_yaml_value(value: serde_yaml::Value) -> Self {
+        use serde_yaml::{Mapping, Value};
+
+        let key_to_string = |key: Value| match key {
+            Value::String(key) => key,
+            key => serde_yaml::to_string(&key)
+                .map(|key| key.trim_end().to_string())
+                .unwrap_or_default(),
+        };
+
+        build(value, |value| match value {
+            Value::Mapping(map) => (
+                Value::Mapping(Mapping::new()),
+                map.into_iter()
+                    .map(|(key, value)| (key_to_string(key), value))
+                    .collect(),
+            ),
+            Value::Sequence(items) => (Value::Sequence(Vec::new()), indexed(items)),
+            scalar => (scalar, Vec::new()),
+        })
+    }
+}
+
+#[cfg(feature = "toml")]
+impl Tree<(String, toml::Value)> {
+    /// Create a tree from a parsed TOML value.
+    ///
+    /// Requires the `toml` feature.
+    ///
+    /// *Arguments:*
+    /// * `value` - The TOML value to convert.
+    ///
+    /// *Returns:* A tree with one node per table entry and array item.
+    pub fn from_toml_value(value: toml::Value) -> Self {
+        use toml::{Table, Value};
+
+        build(value, |value| match value {
+            Value::Table(table) => (Value::Table(Table::new()), table.into_iter().collect()),
+            Value::Array(items) => (Value::Array(Vec::new()), indexed(items)),
+            scalar => (scalar, Vec::new()),
+        })
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,62 @@
+use super::*;
+
+fn keys<V>(tree: &Tree<(String, V)>) -> Vec<String> {
+    tree.depth_first(true)
+        .unwrap()
+        .map(|node_ref| tree.get(node_ref).unwrap().0.clone())
+        .collect()
+}
+
+#[test]
+fn build_nested() {
+    #[derive(Debug, PartialEq)]
+    enum Value {
+        Leaf(i32),
+        List(Vec<Value>),
+    }
+
+    let value = Value::List(vec![Value::Leaf(1), Value::List(vec![Value::Leaf(2)])]);
+    let tree = build(value, |value| match value {
+        Value::List(items) => (Value::List(Vec::new()), indexed(items)),
+        leaf => (leaf, Vec::new()),
+    });
+
+    assert_eq!(keys(&tree), vec!["", "0", "1", "0"]);
+    let leaf = tree
+        .nth_in_depth_first(tree.get_root_ref().unwrap(), 3)
+        .unwrap()
+        .unwrap();
+    assert_eq!(tree.get(leaf).unwrap().1, Value::Leaf(2));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn from_json_value() {
+    let value = serde_json::json!({"server": {"port": 80, "hosts": ["a", "b"]}});
+    let tree = Tree::from_json_value(value);
+
+    assert_eq!(keys(&tree), vec!["", "server", "hosts", "0", "1", "port"]);
+    let port = tree
+        .nth_in_depth_first(tree.get_root_ref().unwrap(), 5)
+        .unwrap()
+        .unwrap();
+    assert_eq!(tree.get(port).unwrap().1, serde_json::json!(80));
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn from_yaml_value() {
+    let value: serde_yaml::Value = serde_yaml::from_str("server:\n  port: 80\n  1: one\n").unwrap();
+    let tree = Tree::from_yaml_value(value);
+
+    assert_eq!(keys(&tree), vec!["", "server", "port", "1"]);
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn from_toml_value() {
+    let value: toml::Value = toml::from_str("[server]\nport = 80\nhosts = [\"a\"]\n").unwrap();
+    let tree = Tree::from_toml_value(value);
+
+    assert_eq!(keys(&tree), vec!["", "server", "hosts", "0", "port"]);
+}
@@ -0,0 +1,188 @@
+use super::{node_not_found, NodeRef, RemovalReport, Result, Tree};
+
+/// Tree recording in which version each node last changed.
+///
+/// Every change made through the methods of this type increments the
+/// version of the tree and stamps the changed nodes with it. A node
+/// changes when it is created, when its content is updated or when its
+/// position changes, that is when it gets a new parent or loses it. This
+/// makes it possible to process only what changed since a previous pass,
+/// like re-rendering the modified parts of a user interface. For this
+/// reason, the underlying tree can only be read with [VersionedTree::tree].
+///
+/// ```ignore
+/// let mut tree = VersionedTree::new();
+/// let root = tree.root("ul")?;
+/// let rendered = tree.version();
+///
+/// let item = tree.child_node(root, "li")?;
+/// assert_eq!(tree.changed_since(rendered).collect::<Vec<_>>(), vec![item]);
+/// ```
+pub struct VersionedTree<T> {
+    tree: Tree<T>,
+    versions: Vec<u64>,
+    version: u64,
+}
+
+impl<T> VersionedTree<T> {
+    /// Create a new empty tree at version 0.
+    ///
+    /// *Returns:* The new tree.
+    pub fn new() -> Self {
+        Self::from_tree(Tree::new())
+    }
+
+    /// Track the changes of an existing tree.
+    ///
+    /// The existing nodes are considered unchanged since version 0.
+    ///
+    /// *Arguments:*
+    /// * `tree` - The tree to track.
+    ///
+    /// *Returns:* The versioned tree, at version 0.
+    pub fn from_tree(tree: Tree<T>) -> Self {
+        Self {
+            versions: vec![0; tree.nodes.len()],
+            tree,
+            version: 0,
+        }
+    }
+
+    /// Get the current version of the tree.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Get the version in which a node last changed.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    ///
+    /// *Returns:* The version or `None` if the node does not exist.
+    pub fn changed_at(&self, node_ref: NodeRef) -> Option<u64> {
+        self.tree.get(node_ref)?;
+        self.versions.get(node_ref.index()).copied()
+    }
+
+    /// Get an iterator over the live nodes which changed after a version.
+    ///
+    /// Nodes are yielded in the order of the underlying vector. Removed
+    /// nodes are not yielded, but the nodes they left without parent are.
+    ///
+    /// *Arguments:*
+    /// * `version` - The version, usually obtained with [VersionedTree::version]
+    ///   at the end of a previous pass.
+    ///
+    /// *Returns:* The iterator over the changed nodes.
+    pub fn changed_since(&self, version: u64) -> impl Iterator<Item = NodeRef> + '_ {
+        self.versions
+            .iter()
+            .enumerate()
+            .filter(move |(_, changed)| **changed > version)
+            .map(|(id, _)| NodeRef::new(id))
+            .filter(move |node_ref| self.tree.get_node(*node_ref).is_some())
+    }
+
+    /// Get the content of a node.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    ///
+    /// *Returns:* The content or `None` if the node does not exist.
+    pub fn get(&self, node_ref: NodeRef) -> Option<&T> {
+        self.tree.get(node_ref)
+    }
+
+    /// Change the content of a node.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the node.
+    /// * `update_fn` - Function changing the content.
+    ///
+    /// *Returns:* Result indicating whether the operations was successful.
+    ///            Returns an error if the node does not exist.
+    pub fn update(&mut self, node_ref: NodeRef, update_fn: impl FnOnce(&mut T)) -> Result<()> {
+        match self.tree.get_mut(node_ref) {
+            None => return Err(node_not_found(node_ref)),
+            Some(content) => update_fn(content),
+        }
+        self.version += 1;
+        self.stamp(node_ref);
+        Ok(())
+    }
+
+    /// Create a root node, see [Tree::root].
+    pub fn root(&mut self, content: T) -> Result<NodeRef> {
+        let root_ref = self.tree.root(content)?;
+        self.version += 1;
+        self.stamp(root_ref);
+        Ok(root_ref)
+    }
+
+    /// Create a node without parent, see [Tree::node].
+    pub fn node(&mut self, content: T) -> NodeRef {
+        let node_ref = self.tree.node(content);
+        self.version += 1;
+        self.stamp(node_ref);
+        node_ref
+    }
+
+    /// Create a node and append it to a parent node, see [Tree::child_node].
+    pub fn child_node(&mut self, parent: NodeRef, content: T) -> Result<NodeRef> {
+        let child_ref = self.tree.child_node(parent, content)?;
+        self.version += 1;
+        self.stamp(child_ref);
+        Ok(child_ref)
+    }
+
+    /// Add child node to a node, see [Tree::append_child].
+    pub fn append_child(&mut self, parent_ref: NodeRef, child_ref: NodeRef) -> Result<()> {
+        self.tree.append_child(parent_ref, child_ref)?;
+        self.version += 1;
+        self.stamp(child_ref);
+        Ok(())
+    }
+
+    /// Remove a node from the tree, see [Tree::remove].
+    ///
+    /// The children of the removed node are left without parent,
+    /// which counts as a change of their position.
+    pub fn remove(&mut self, node_ref: NodeRef) -> Result<RemovalReport> {
+        let children: Vec<NodeRef> = self.tree.live_children(node_ref).collect();
+        let report = self.tree.remove(node_ref)?;
+        self.version += 1;
+        for child_ref in children {
+            self.stamp(child_ref);
+        }
+        Ok(report)
+    }
+
+    /// Get the underlying tree.
+    pub fn tree(&self) -> &Tree<T> {
+        &self.tree
+    }
+
+    /// Drop the versions and get the underlying tree back.
+    pub fn into_tree(self) -> Tree<T> {
+        self.tree
+    }
+
+    /// Record that a node changed in the current version.
+    fn stamp(&mut self, node_ref: NodeRef) {
+        if self.versions.len() <= node_ref.index() {
+            self.versions.resize(self.tree.nodes.len(), 0);
+        }
+        self.versions[node_ref.index()] = self.version;
+    }
+}
+
+impl<T> Default for VersionedTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
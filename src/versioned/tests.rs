@@ -0,0 +1,45 @@
+use super::*;
+
+#[test]
+fn changed_since() {
+    let mut tree = VersionedTree::new();
+    let root = tree.root(1).unwrap();
+    let a = tree.child_node(root, 2).unwrap();
+    let b = tree.child_node(a, 3).unwrap();
+    let c = tree.child_node(root, 4).unwrap();
+    let rendered = tree.version();
+    assert_eq!(rendered, 4);
+    assert_eq!(tree.changed_since(rendered).count(), 0);
+    assert_eq!(
+        tree.changed_since(0).collect::<Vec<_>>(),
+        vec![root, a, b, c]
+    );
+
+    tree.update(c, |content| *content = 5).unwrap();
+    assert_eq!(tree.changed_at(c), Some(5));
+    assert_eq!(tree.changed_since(rendered).collect::<Vec<_>>(), vec![c]);
+
+    tree.append_child(c, b).unwrap();
+    assert_eq!(tree.changed_since(rendered).collect::<Vec<_>>(), vec![b, c]);
+
+    let rendered = tree.version();
+    tree.remove(c).unwrap();
+    assert_eq!(tree.changed_since(rendered).collect::<Vec<_>>(), vec![b]);
+    assert_eq!(tree.changed_at(c), None);
+    assert!(tree.update(c, |content| *content = 6).is_err());
+    assert_eq!(tree.version(), rendered + 1);
+}
+
+#[test]
+fn from_tree() {
+    let mut tree = Tree::new();
+    let root = tree.root("a").unwrap();
+    let child = tree.child_node(root, "b").unwrap();
+
+    let mut tree = VersionedTree::from_tree(tree);
+    assert_eq!(tree.version(), 0);
+    assert_eq!(tree.changed_at(child), Some(0));
+
+    let added = tree.child_node(child, "c").unwrap();
+    assert_eq!(tree.changed_since(0).collect::<Vec<_>>(), vec![added]);
+}
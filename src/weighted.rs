@@ -0,0 +1,151 @@
+use std::cmp::Ordering;
+use std::ops::Add;
+
+use super::{NodeRef, Result, Tree};
+
+/// For each node index, the weight of the heaviest path starting
+/// at the node and the child continuing it.
+type Heaviest<W> = Vec<Option<(W, Option<NodeRef>)>>;
+
+impl<T> Tree<T> {
+    /// Compute the weight of the heaviest path from a node down to a leaf.
+    ///
+    /// The weight of a path is the sum of the weights of its nodes,
+    /// including the starting node and the leaf. With a weight of one
+    /// per node, this is the number of levels of the subtree.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the root of the subtree.
+    /// * `weight_fn` - Function computing the weight of the content of a node.
+    ///
+    /// *Returns:* The weight of the heaviest path. Returns error
+    ///            if the node does not exist.
+    pub fn weighted_height<W>(&self, node_ref: NodeRef, weight_fn: impl Fn(&T) -> W) -> Result<W>
+    where
+        W: Copy + PartialOrd + Add<Output = W>,
+    {
+        let heaviest = self.heaviest_paths(node_ref, weight_fn)?;
+        Ok(heaviest[node_ref.index()].unwrap().0)
+    }
+
+    /// Find the heaviest path from a node down to a leaf, like the
+    /// critical path of a tree of tasks weighted by their duration.
+    ///
+    /// See [Tree::weighted_height] for the weight of a path.
+    ///
+    /// *Arguments:*
+    /// * `node_ref` - [NodeRef] of the root of the subtree.
+    /// * `weight_fn` - Function computing the weight of the content of a node.
+    ///
+    /// *Returns:* The nodes of the path, starting with `node_ref`, and its
+    ///            weight. Among paths of equal weight, the first in
+    ///            depth-first order is returned. Returns error if the
+    ///            node does not exist.
+    pub fn max_path<W>(
+        &self,
+        node_ref: NodeRef,
+        weight_fn: impl Fn(&T) -> W,
+    ) -> Result<(Vec<NodeRef>, W)>
+    where
+        W: Copy + PartialOrd + Add<Output = W>,
+    {
+        let heaviest = self.heaviest_paths(node_ref, weight_fn)?;
+        let (weight, _) = heaviest[node_ref.index()].unwrap();
+
+        let mut path = vec![node_ref];
+        while let Some((_, Some(child_ref))) = heaviest[path.last().unwrap().index()] {
+            path.push(child_ref);
+        }
+        Ok((path, weight))
+    }
+
+    /// Cut the tree into chunks of bounded weight, to distribute the
+    /// processing of a large tree or to paginate it.
+    ///
+    /// Each chunk is made of a chunk root and its descendants, except
+    /// the ones belonging to chunks further down. The weight of a chunk
+    /// is the sum of the weights of its nodes and does not exceed
+    /// `max_weight`, unless a single node is heavier than that. Chunks
+    /// are chosen bottom-up, cutting the heaviest children first, which
+    /// keeps the number of chunks minimal.
+    ///
+    /// *Arguments:*
+    /// * `max_weight` - The maximum weight of a chunk.
+    /// * `weight_fn` - Function computing the weight of the content of a node.
+    ///
+    /// *Returns:* The roots of the chunks in depth-first order, starting
+    ///            with the root node. The vector is empty if no root
+    ///            node exists.
+    pub fn partition<W>(&self, max_weight: W, weight_fn: impl Fn(&T) -> W) -> Vec<NodeRef>
+    where
+        W: Copy + PartialOrd + Add<Output = W>,
+    {
+        let root_ref = match self.root {
+            None => return Vec::new(),
+            Some(root_ref) => root_ref,
+        };
+        let order: Vec<NodeRef> = self.depth_first_of(root_ref, true).unwrap().collect();
+        let mut remaining: Vec<Option<W>> = vec![None; self.nodes.len()];
+        let mut cut = vec![false; self.nodes.len()];
+
+        // Descendants come before their ancestors in reverse preorder
+        for node_ref in order.iter().rev() {
+            let mut children: Vec<(W, NodeRef)> = self
+                .live_children(*node_ref)
+                .map(|child_ref| (remaining[child_ref.index()].unwrap(), child_ref))
+                .collect();
+            children.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+            let mut weight = weight_fn(self.get(*node_ref).unwrap());
+            for (child_weight, child_ref) in children {
+                if weight + child_weight <= max_weight {
+                    weight = weight + child_weight;
+                } else {
+                    cut[child_ref.index()] = true;
+                }
+            }
+            remaining[node_ref.index()] = Some(weight);
+        }
+
+        order
+            .into_iter()
+            .filter(|node_ref| *node_ref == root_ref || cut[node_ref.index()])
+            .collect()
+    }
+
+    /// Compute the heaviest paths starting at each node of a subtree.
+    fn heaviest_paths<W>(
+        &self,
+        node_ref: NodeRef,
+        weight_fn: impl Fn(&T) -> W,
+    ) -> Result<Heaviest<W>>
+    where
+        W: Copy + PartialOrd + Add<Output = W>,
+    {
+        let order: Vec<NodeRef> = self.depth_first_of(node_ref, true)?.collect();
+        let mut heaviest: Heaviest<W> = vec![None; self.nodes.len()];
+
+        // Descendants come before their ancestors in reverse preorder
+        for node_ref in order.into_iter().rev() {
+            let mut best: Option<(W, NodeRef)> = None;
+            for child_ref in self.live_children(node_ref) {
+                let (child_weight, _) = heaviest[child_ref.index()].unwrap();
+                if best.is_none_or(|(weight, _)| child_weight > weight) {
+                    best = Some((child_weight, child_ref));
+                }
+            }
+
+            let weight = weight_fn(self.get(node_ref).unwrap());
+            heaviest[node_ref.index()] = Some(match best {
+                None => (weight, None),
+                Some((child_weight, child_ref)) => (weight + child_weight, Some(child_ref)),
+            });
+        }
+        Ok(heaviest)
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
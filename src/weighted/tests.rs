@@ -0,0 +1,52 @@
+use super::*;
+use crate::tree;
+
+#[test]
+fn weighted_height() {
+    let tree = tree!(1 => [2 => [3], 4 => [1, 1]]);
+    let root = tree.get_root_ref().unwrap();
+    assert_eq!(tree.weighted_height(root, |_| 1).unwrap(), 3);
+    assert_eq!(tree.weighted_height(root, |weight| *weight).unwrap(), 6);
+    assert_eq!(
+        tree.weighted_height(root, |weight| *weight as f64 / 2.0)
+            .unwrap(),
+        3.0
+    );
+}
+
+#[test]
+fn max_path() {
+    let tree = tree!(1 => [2 => [3], 4 => [1, 2]]);
+    let order: Vec<NodeRef> = tree.depth_first(true).unwrap().collect();
+
+    let (path, weight) = tree.max_path(order[0], |weight| *weight).unwrap();
+    assert_eq!(path, vec![order[0], order[3], order[5]]);
+    assert_eq!(weight, 7);
+
+    // Ties go to the first path in depth-first order
+    let (path, weight) = tree.max_path(order[0], |_| 1).unwrap();
+    assert_eq!(path, vec![order[0], order[1], order[2]]);
+    assert_eq!(weight, 3);
+
+    assert_eq!(
+        tree.max_path(order[2], |weight| *weight).unwrap(),
+        (vec![order[2]], 3)
+    );
+}
+
+#[test]
+fn partition() {
+    let tree = tree!(1 => [2 => [3, 4], 5 => [6], 7]);
+    let order: Vec<NodeRef> = tree.depth_first(true).unwrap().collect();
+
+    assert_eq!(tree.partition(100, |_| 1), vec![order[0]]);
+
+    // The root keeps the lightest child, 7
+    let chunks = tree.partition(3, |_| 1);
+    assert_eq!(chunks, vec![order[0], order[1], order[4]]);
+
+    let chunks = tree.partition(10, |weight| *weight);
+    assert_eq!(chunks, vec![order[0], order[1], order[5], order[6]]);
+    assert_eq!(tree.partition(1, |_| 1).len(), tree.len());
+    assert!(Tree::<i32>::new().partition(1, |_| 1).is_empty());
+}
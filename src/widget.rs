@@ -0,0 +1,142 @@
+//! Collapsible tree view widget for `egui`.
+//!
+//! This module is available with the `egui` feature.
+
+use std::collections::HashSet;
+use std::fmt::Display;
+
+use super::{NodeRef, Tree};
+
+/// Expansion and selection state of a [TreeView].
+///
+/// The state is kept by the application between frames,
+/// next to the tree it refers to.
+#[derive(Debug, Clone, Default)]
+pub struct TreeViewState {
+    expanded: HashSet<NodeRef>,
+    selected: Option<NodeRef>,
+}
+
+impl TreeViewState {
+    /// Create a new state, with all nodes collapsed and none selected.
+    ///
+    /// *Returns:* The new state.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the selected node.
+    pub fn selected(&self) -> Option<NodeRef> {
+        self.selected
+    }
+
+    /// Select a node, or clear the selection with `None`.
+    pub fn select(&mut self, node_ref: Option<NodeRef>) {
+        self.selected = node_ref;
+    }
+
+    /// Check whether the children of a node are shown.
+    pub fn is_expanded(&self, node_ref: NodeRef) -> bool {
+        self.expanded.contains(&node_ref)
+    }
+
+    /// Show or hide the children of a node.
+    pub fn set_expanded(&mut self, node_ref: NodeRef, expanded: bool) {
+        if expanded {
+            self.expanded.insert(node_ref);
+        } else {
+            self.expanded.remove(&node_ref);
+        }
+    }
+
+    /// Expand all ancestors of a node, so that the node is shown.
+    ///
+    /// *Arguments:*
+    /// * `tree` - The tree containing the node.
+    /// * `node_ref` - [NodeRef] of the node to reveal.
+    pub fn reveal<T>(&mut self, tree: &Tree<T>, node_ref: NodeRef) {
+        if let Ok(ancestors) = tree.ancestors(node_ref) {
+            self.expanded.extend(ancestors);
+        }
+    }
+}
+
+/// Collapsible view of a tree, showing one row per node.
+///
+/// Nodes with children have a button to expand or collapse them, and
+/// clicking on the label of a node selects it. The view is created
+/// for every frame, like other `egui` widgets.
+///
+/// ```ignore
+/// egui::ScrollArea::vertical().show(ui, |ui| {
+///     if let Some(node_ref) = TreeView::new(&tree, &mut state).show(ui) {
+///         // The node was clicked in this frame
+///     }
+/// });
+/// ```
+pub struct TreeView<'a, T> {
+    tree: &'a Tree<T>,
+    state: &'a mut TreeViewState,
+}
+
+impl<'a, T: Display> TreeView<'a, T> {
+    /// Create a view of a tree.
+    ///
+    /// *Arguments:*
+    /// * `tree` - The tree to show, starting from its root node.
+    /// * `state` - The [TreeViewState] updated by the view.
+    ///
+    /// *Returns:* The new view.
+    pub fn new(tree: &'a Tree<T>, state: &'a mut TreeViewState) -> Self {
+        Self { tree, state }
+    }
+
+    /// Add the view to a `Ui`.
+    ///
+    /// *Arguments:*
+    /// * `ui` - The `egui` user interface to add the rows to.
+    ///
+    /// *Returns:* The node whose label was clicked in this frame, if any.
+    pub fn show(self, ui: &mut egui::Ui) -> Option<NodeRef> {
+        let Self { tree, state } = self;
+        let mut stack: Vec<(NodeRef, usize)> = tree.root.iter().map(|root| (*root, 0)).collect();
+        let mut clicked = None;
+
+        while let Some((node_ref, depth)) = stack.pop() {
+            let has_children = tree.live_children(node_ref).next().is_some();
+            let content = tree.get(node_ref).unwrap();
+
+            ui.push_id(node_ref.index(), |ui| {
+                ui.horizontal(|ui| {
+                    let indent = ui.spacing().indent;
+                    ui.add_space(depth as f32 * indent);
+
+                    let expanded = state.is_expanded(node_ref);
+                    if !has_children {
+                        ui.add_space(indent);
+                    } else if ui.small_button(if expanded { "-" } else { "+" }).clicked() {
+                        state.set_expanded(node_ref, !expanded);
+                    }
+
+                    let selected = state.selected == Some(node_ref);
+                    if ui.selectable_label(selected, content.to_string()).clicked() {
+                        state.selected = Some(node_ref);
+                        clicked = Some(node_ref);
+                    }
+                });
+            });
+
+            if has_children && state.is_expanded(node_ref) {
+                let children: Vec<NodeRef> = tree.live_children(node_ref).collect();
+                stack.extend(children.into_iter().rev().map(|child| (child, depth + 1)));
+            }
+        }
+
+        clicked
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
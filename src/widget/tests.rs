@@ -0,0 +1,41 @@
+use super::*;
+use crate::tree;
+
+fn run(tree: &Tree<i32>, state: &mut TreeViewState) {
+    let ctx = egui::Context::default();
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            assert_eq!(TreeView::new(tree, state).show(ui), None);
+        });
+    });
+}
+
+#[test]
+fn state() {
+    let tree = tree!(1 => [2 => [3]]);
+    let order: Vec<NodeRef> = tree.depth_first(true).unwrap().collect();
+    let mut state = TreeViewState::new();
+    assert!(!state.is_expanded(order[0]));
+
+    state.reveal(&tree, order[2]);
+    assert!(state.is_expanded(order[0]));
+    assert!(state.is_expanded(order[1]));
+    assert!(!state.is_expanded(order[2]));
+
+    state.set_expanded(order[1], false);
+    assert!(!state.is_expanded(order[1]));
+
+    state.select(Some(order[1]));
+    assert_eq!(state.selected(), Some(order[1]));
+}
+
+#[test]
+fn show() {
+    let tree = tree!(1 => [2 => [3], 4]);
+    let mut state = TreeViewState::new();
+    run(&tree, &mut state);
+
+    state.reveal(&tree, tree.depth_first(true).unwrap().nth(2).unwrap());
+    run(&tree, &mut state);
+    run(&Tree::new(), &mut state);
+}
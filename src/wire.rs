@@ -0,0 +1,251 @@
+use std::convert::TryInto;
+use std::error::Error;
+
+use super::{ErrorKind, NodeRef, Result, Tree, TreeError};
+
+/// Version of the binary format written by [Tree::to_bytes].
+pub const FORMAT_VERSION: u16 = 1;
+
+const MAGIC: &[u8; 4] = b"LTRE";
+const NO_PARENT: u32 = u32::MAX;
+
+type Migration = Box<dyn Fn(&[u8]) -> Result<Vec<u8>>>;
+
+/// Conversions of the binary format of older versions, used by
+/// [Tree::from_bytes_with_migrations].
+///
+/// Each migration converts the body of a dump, everything after the
+/// version header, from one version to the next. Migrations are chained,
+/// so a dump written with version 1 is loaded by a later version through
+/// the migrations from 1 to 2, 2 to 3 and so on.
+///
+/// ```ignore
+/// let migrations = Migrations::new().add(1, |body| upgrade_v1_body(body));
+/// let tree = Tree::from_bytes_with_migrations(&bytes, decode, &migrations)?;
+/// ```
+#[derive(Default)]
+pub struct Migrations {
+    steps: Vec<(u16, Migration)>,
+}
+
+impl Migrations {
+    /// Create an empty set of migrations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the migration of the body from a version to the next.
+    ///
+    /// *Arguments:*
+    /// * `from_version` - The version of the body given to `migrate`.
+    /// * `migrate` - Function converting the body to `from_version + 1`.
+    ///
+    /// *Returns:* The migrations, for chaining.
+    pub fn add(
+        mut self,
+        from_version: u16,
+        migrate: impl Fn(&[u8]) -> Result<Vec<u8>> + 'static,
+    ) -> Self {
+        self.steps.push((from_version, Box::new(migrate)));
+        self
+    }
+}
+
+impl<T> Tree<T> {
+    /// Serialize the tree into a versioned binary format.
+    ///
+    /// The dump starts with a magic number and [FORMAT_VERSION], followed
+    /// by the nodes reachable from the root in depth-first order, each
+    /// with the position of its parent and its encoded content. Node
+    /// references are not preserved. If some nodes have a stable id, set
+    /// with [Tree::node_with_id], the nodes are followed by the number
+    /// of ids and the position and id of each of those nodes.
+    ///
+    /// *Arguments:*
+    /// * `encode` - Function appending the bytes of a content to a buffer.
+    ///
+    /// *Returns:* The bytes of the dump.
+    ///
+    /// *Panics:* If the tree or an encoded content has more
+    ///           than `u32::MAX - 1` nodes or bytes.
+    pub fn to_bytes(&self, mut encode: impl FnMut(&T, &mut Vec<u8>)) -> Vec<u8> {
+        let order: Vec<NodeRef> = match self.depth_first(true) {
+            Ok(nodes) => nodes.collect(),
+            Err(_) => Vec::new(),
+        };
+        let mut positions = vec![NO_PARENT; self.nodes.len()];
+        for (position, node_ref) in order.iter().enumerate() {
+            positions[node_ref.index()] = to_u32(position);
+        }
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&to_u32(order.len()).to_le_bytes());
+
+        let mut content = Vec::new();
+        for node_ref in &order {
+            let node = self.get_node(*node_ref).unwrap();
+            let parent = node.parent.map_or(NO_PARENT, |p| positions[p.index()]);
+            content.clear();
+            encode(&node.content, &mut content);
+
+            bytes.extend_from_slice(&parent.to_le_bytes());
+            bytes.extend_from_slice(&to_u32(content.len()).to_le_bytes());
+            bytes.extend_from_slice(&content);
+        }
+
+        let stable_ids: Vec<(usize, u64)> = order
+            .iter()
+            .enumerate()
+            .filter_map(|(position, node_ref)| Some((position, self.stable_id(*node_ref)?)))
+            .collect();
+        if !stable_ids.is_empty() {
+            bytes.extend_from_slice(&to_u32(stable_ids.len()).to_le_bytes());
+            for (position, id) in stable_ids {
+                bytes.extend_from_slice(&to_u32(position).to_le_bytes());
+                bytes.extend_from_slice(&id.to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// Load a tree serialized with [Tree::to_bytes].
+    ///
+    /// Dumps of older versions are rejected, use
+    /// [Tree::from_bytes_with_migrations] to load them.
+    ///
+    /// *Arguments:*
+    /// * `bytes` - The bytes of the dump.
+    /// * `decode` - Function decoding the content of a node.
+    ///
+    /// *Returns:* The tree. Returns an error of kind [ErrorKind::InvalidInput]
+    ///            if the dump is malformed, or the error returned by
+    ///            `decode` as [Error::source].
+    pub fn from_bytes<E: Error + Send + Sync + 'static>(
+        bytes: &[u8],
+        decode: impl FnMut(&[u8]) -> std::result::Result<T, E>,
+    ) -> Result<Self> {
+        Self::from_bytes_with_migrations(bytes, decode, &Migrations::new())
+    }
+
+    /// Load a tree serialized with [Tree::to_bytes] by any
+    /// version, applying migrations as needed.
+    ///
+    /// *Arguments:*
+    /// * `bytes` - The bytes of the dump.
+    /// * `decode` - Function decoding the content of a node.
+    /// * `migrations` - Conversions of the older versions of the format.
+    ///
+    /// *Returns:* The tree. Returns an error of kind [ErrorKind::InvalidInput]
+    ///            if the dump is malformed, written by a newer version
+    ///            or if a migration is missing.
+    pub fn from_bytes_with_migrations<E: Error + Send + Sync + 'static>(
+        bytes: &[u8],
+        mut decode: impl FnMut(&[u8]) -> std::result::Result<T, E>,
+        migrations: &Migrations,
+    ) -> Result<Self> {
+        if bytes.len() < 6 || &bytes[..4] != MAGIC {
+            return Err(invalid("Not a tree dump."));
+        }
+        let mut version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        if version > FORMAT_VERSION {
+            return Err(invalid(&format!("Unsupported format version {}.", version)));
+        }
+
+        let mut body = bytes[6..].to_vec();
+        while version < FORMAT_VERSION {
+            let migrate = match migrations.steps.iter().find(|(from, _)| *from == version) {
+                None => return Err(invalid(&format!("No migration from version {}.", version))),
+                Some((_, migrate)) => migrate,
+            };
+            body = migrate(&body)?;
+            version += 1;
+        }
+
+        let mut reader = Reader { bytes: &body };
+        let len = reader.u32()? as usize;
+        let mut values = Vec::with_capacity(len.min(body.len()));
+        let mut parents = Vec::with_capacity(len.min(body.len()));
+        let mut children = Vec::with_capacity(len.min(body.len()));
+        for position in 0..len {
+            let parent = match reader.u32()? {
+                NO_PARENT => None,
+                parent if parent as usize >= position => {
+                    return Err(invalid("Parent does not precede its child."));
+                }
+                parent => Some(parent as usize),
+            };
+            let content_len = reader.u32()? as usize;
+            let content = decode(reader.take(content_len)?)
+                .map_err(|error| invalid("Cannot decode node content.").with_source(error))?;
+
+            values.push(content);
+            parents.push(parent);
+            children.push(Vec::new());
+            if let Some(parent) = parent {
+                children[parent].push(position);
+            }
+        }
+
+        let mut stable_ids = Vec::new();
+        if !reader.bytes.is_empty() {
+            for _ in 0..reader.u32()? {
+                let position = reader.u32()? as usize;
+                if position >= len {
+                    return Err(invalid("Stable id of a node out of range."));
+                }
+                stable_ids.push((position, reader.u64()?));
+            }
+        }
+        if !reader.bytes.is_empty() {
+            return Err(invalid("Unexpected data after the last node."));
+        }
+
+        let mut tree = Self::try_from_parts(values, parents, children)?;
+        for (position, id) in stable_ids {
+            tree.set_stable_id(NodeRef::new(position), id)
+                .map_err(|_| invalid("Stable id used by several nodes."))?;
+        }
+        Ok(tree)
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.bytes.len() < len {
+            return Err(invalid("Unexpected end of data."));
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+}
+
+fn invalid(message: &str) -> TreeError {
+    TreeError::with_kind(ErrorKind::InvalidInput, message)
+}
+
+fn to_u32(value: usize) -> u32 {
+    match value {
+        value if value < NO_PARENT as usize => value as u32,
+        _ => panic!("Tree is too large to serialize."),
+    }
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
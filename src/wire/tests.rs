@@ -0,0 +1,68 @@
+use std::str::{self, Utf8Error};
+
+use super::*;
+
+fn encode(content: &String, out: &mut Vec<u8>) {
+    out.extend_from_slice(content.as_bytes());
+}
+
+fn decode(bytes: &[u8]) -> std::result::Result<String, Utf8Error> {
+    str::from_utf8(bytes).map(String::from)
+}
+
+fn sample() -> Tree<String> {
+    let mut tree = Tree::new();
+    let root = tree.root(String::from("root")).unwrap();
+    let a = tree.child_node(root, String::from("a")).unwrap();
+    tree.child_node(a, String::from("b")).unwrap();
+    tree.child_node(root, String::from("c")).unwrap();
+    tree
+}
+
+#[test]
+fn roundtrip() {
+    let tree = sample();
+    let bytes = tree.to_bytes(encode);
+    assert_eq!(&bytes[..4], b"LTRE");
+
+    let loaded = Tree::from_bytes(&bytes, decode).unwrap();
+    assert!(loaded.structural_eq(&tree));
+    assert_eq!(
+        Tree::from_bytes(&Tree::<String>::new().to_bytes(encode), decode)
+            .unwrap()
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn invalid_dumps() {
+    let bytes = sample().to_bytes(encode);
+
+    let error = Tree::from_bytes(&bytes[..bytes.len() - 1], decode).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    assert_eq!(error.to_string(), "Unexpected end of data.");
+
+    let mut newer = bytes.clone();
+    newer[4] = 99;
+    let error = Tree::from_bytes(&newer, decode).unwrap_err();
+    assert_eq!(error.to_string(), "Unsupported format version 99.");
+
+    let mut not_utf8 = bytes.clone();
+    let last = not_utf8.len() - 1;
+    not_utf8[last] = 0xff;
+    let error = Tree::from_bytes(&not_utf8, decode).unwrap_err();
+    assert!(error.source().is_some());
+}
+
+#[test]
+fn migrations() {
+    let mut old = sample().to_bytes(encode);
+    old[4] = 0;
+    let error = Tree::from_bytes(&old, decode).unwrap_err();
+    assert_eq!(error.to_string(), "No migration from version 0.");
+
+    let migrations = Migrations::new().add(0, |body| Ok(body.to_vec()));
+    let tree = Tree::from_bytes_with_migrations(&old, decode, &migrations).unwrap();
+    assert!(tree.structural_eq(&sample()));
+}
@@ -0,0 +1,135 @@
+//! Building document trees from XML with `quick-xml`.
+//!
+//! This module is available with the `xml` feature.
+
+use std::io::BufRead;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use super::{ErrorKind, NodeRef, Result, Tree, TreeError};
+
+/// A node of an XML document.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum XmlNode {
+    /// The whole document, used as root node.
+    Document,
+    /// An element, with its attributes in document order.
+    Element {
+        /// Qualified name of the element, like `svg:rect`.
+        name: String,
+        /// Pairs of qualified names and unescaped values.
+        attributes: Vec<(String, String)>,
+    },
+    /// Unescaped text or CDATA content.
+    Text(String),
+    /// The content of a comment.
+    Comment(String),
+}
+
+impl Tree<XmlNode> {
+    /// Create a tree from an XML document.
+    ///
+    /// Text consisting only of whitespace is skipped and other text
+    /// is trimmed. Declarations, processing instructions and document
+    /// types are skipped. Requires the `xml` feature.
+    ///
+    /// *Arguments:*
+    /// * `text` - The XML document.
+    ///
+    /// *Returns:* A tree with an [XmlNode::Document] root node.
+    ///            Returns an error if the document is not well-formed.
+    pub fn from_xml_str(text: &str) -> Result<Self> {
+        let mut reader = Reader::from_str(text);
+        reader.config_mut().trim_text(true);
+        Self::from_xml_reader(&mut reader)
+    }
+
+    /// Create a tree from the events of a `quick-xml` reader.
+    ///
+    /// The reader is consumed until the end of the input, with the
+    /// configuration chosen by the caller, e.g. to keep whitespace.
+    /// Requires the `xml` feature.
+    ///
+    /// *Arguments:*
+    /// * `reader` - The reader to take the events from.
+    ///
+    /// *Returns:* A tree with an [XmlNode::Document] root node.
+    ///            Returns an error if the document is not well-formed.
+    pub fn from_xml_reader<R: BufRead>(reader: &mut Reader<R>) -> Result<Self> {
+        let mut tree = Tree::new();
+        let mut stack: Vec<NodeRef> = vec![tree.root(XmlNode::Document).unwrap()];
+        let mut buf = Vec::new();
+
+        loop {
+            buf.clear();
+            let event = reader
+                .read_event_into(&mut buf)
+                .map_err(|error| invalid(reader.error_position()).with_source(error))?;
+            let parent_ref = *stack.last().unwrap();
+            let content = match event {
+                Event::Start(start) => {
+                    let element = element(reader, &start)?;
+                    stack.push(tree.child_node(parent_ref, element).unwrap());
+                    continue;
+                }
+                Event::End(_) => {
+                    if stack.len() == 1 {
+                        return Err(invalid(reader.buffer_position()));
+                    }
+                    stack.pop();
+                    continue;
+                }
+                Event::Empty(start) => element(reader, &start)?,
+                Event::Text(text) => XmlNode::Text(
+                    text.unescape()
+                        .map_err(|error| invalid(reader.buffer_position()).with_source(error))?
+                        .into_owned(),
+                ),
+                Event::CData(data) => XmlNode::Text(decode(reader, &data)?),
+                Event::Comment(comment) => XmlNode::Comment(decode(reader, &comment)?),
+                Event::Eof if stack.len() == 1 => return Ok(tree),
+                Event::Eof => return Err(invalid(reader.buffer_position())),
+                _ => continue,
+            };
+            tree.child_node(parent_ref, content).unwrap();
+        }
+    }
+}
+
+fn element<R>(reader: &Reader<R>, start: &BytesStart) -> Result<XmlNode> {
+    let mut attributes = Vec::new();
+    for attribute in start.attributes() {
+        let attribute =
+            attribute.map_err(|error| invalid(reader.buffer_position()).with_source(error))?;
+        let value = attribute
+            .decode_and_unescape_value(reader.decoder())
+            .map_err(|error| invalid(reader.buffer_position()).with_source(error))?;
+        attributes.push((decode(reader, attribute.key.as_ref())?, value.into_owned()));
+    }
+
+    Ok(XmlNode::Element {
+        name: decode(reader, start.name().as_ref())?,
+        attributes,
+    })
+}
+
+fn decode<R>(reader: &Reader<R>, bytes: &[u8]) -> Result<String> {
+    reader
+        .decoder()
+        .decode(bytes)
+        .map(|text| text.into_owned())
+        .map_err(|error| invalid(reader.buffer_position()).with_source(error))
+}
+
+fn invalid(position: u64) -> TreeError {
+    TreeError::with_kind(
+        ErrorKind::InvalidInput,
+        &format!("Invalid XML at byte {}.", position),
+    )
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,52 @@
+use super::*;
+
+fn element(name: &str, attributes: &[(&str, &str)]) -> XmlNode {
+    XmlNode::Element {
+        name: name.to_string(),
+        attributes: attributes
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect(),
+    }
+}
+
+#[test]
+fn from_xml_str() {
+    let tree = Tree::from_xml_str(
+        r#"<?xml version="1.0"?>
+        <ul class="menu">
+            <li>Home &amp; garden</li>
+            <!-- separator -->
+            <li><![CDATA[<b>]]></li>
+            <hr/>
+        </ul>"#,
+    )
+    .unwrap();
+
+    let nodes: Vec<&XmlNode> = tree
+        .depth_first(true)
+        .unwrap()
+        .map(|node_ref| tree.get(node_ref).unwrap())
+        .collect();
+    assert_eq!(
+        nodes,
+        vec![
+            &XmlNode::Document,
+            &element("ul", &[("class", "menu")]),
+            &element("li", &[]),
+            &XmlNode::Text("Home & garden".to_string()),
+            &XmlNode::Comment(" separator ".to_string()),
+            &element("li", &[]),
+            &XmlNode::Text("<b>".to_string()),
+            &element("hr", &[]),
+        ]
+    );
+}
+
+#[test]
+fn from_xml_str_invalid() {
+    for invalid in &["<a><b></a>", "<a>", "</a>", "<a x=1/>"] {
+        let error = Tree::from_xml_str(invalid).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    }
+}
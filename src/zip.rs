@@ -0,0 +1,69 @@
+use super::{not_found, NodeRef, Result, Tree};
+
+/// A position visited by [Tree::walk_together].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum WalkPair {
+    /// Nodes at the same position in both trees.
+    Matched(NodeRef, NodeRef),
+    /// Subtree root present only in the first tree.
+    OnlySelf(NodeRef),
+    /// Subtree root present only in the other tree.
+    OnlyOther(NodeRef),
+}
+
+impl<T> Tree<T> {
+    /// Traverse two trees in lockstep.
+    ///
+    /// Nodes are paired by position, starting from the roots and matching
+    /// the children of paired nodes by index, in depth-first order. When
+    /// paired nodes have a different number of children, the extra
+    /// children are reported as [WalkPair::OnlySelf] or [WalkPair::OnlyOther]
+    /// and their descendants are not visited.
+    ///
+    /// *Arguments:*
+    /// * `other` - The tree to walk together with this one.
+    /// * `visit` - Function called for each [WalkPair].
+    ///
+    /// *Returns:* Result indicating whether the operations was successful.
+    ///            Returns an error if one of the trees has no root node.
+    pub fn walk_together<U>(&self, other: &Tree<U>, mut visit: impl FnMut(WalkPair)) -> Result<()> {
+        let (root_ref, other_root_ref) = match (self.root, other.root) {
+            (Some(root_ref), Some(other_root_ref)) => (root_ref, other_root_ref),
+            _ => return Err(not_found("Cannot walk a tree without a root node.")),
+        };
+
+        let mut stack = vec![WalkPair::Matched(root_ref, other_root_ref)];
+        while let Some(pair) = stack.pop() {
+            visit(pair);
+
+            if let WalkPair::Matched(node_ref, other_ref) = pair {
+                let children = live_children(self, node_ref);
+                let other_children = live_children(other, other_ref);
+
+                let len = children.len().max(other_children.len());
+                for i in (0..len).rev() {
+                    stack.push(match (children.get(i), other_children.get(i)) {
+                        (Some(child), Some(other_child)) => WalkPair::Matched(*child, *other_child),
+                        (Some(child), None) => WalkPair::OnlySelf(*child),
+                        (None, Some(other_child)) => WalkPair::OnlyOther(*other_child),
+                        (None, None) => unreachable!(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn live_children<T>(tree: &Tree<T>, node_ref: NodeRef) -> Vec<NodeRef> {
+    tree.get_children(node_ref)
+        .unwrap()
+        .filter(|child_ref| tree.get(*child_ref).is_some())
+        .collect()
+}
+
+// Tests
+// ==================================================================
+#[cfg(test)]
+mod tests;
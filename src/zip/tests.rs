@@ -0,0 +1,40 @@
+use super::*;
+use crate::{ErrorKind, TreeError};
+
+#[test]
+fn walk_together() {
+    let mut tree = Tree::new();
+    let a = tree.root("a").unwrap();
+    let b = tree.child_node(a, "b").unwrap();
+    let c = tree.child_node(b, "c").unwrap();
+    let d = tree.child_node(a, "d").unwrap();
+
+    let mut other = Tree::new();
+    let a2 = other.root(1).unwrap();
+    let b2 = other.child_node(a2, 2).unwrap();
+    let d2 = other.child_node(a2, 4).unwrap();
+    let e2 = other.child_node(d2, 5).unwrap();
+    let f2 = other.child_node(e2, 6).unwrap();
+
+    let mut pairs = Vec::new();
+    tree.walk_together(&other, |pair| pairs.push(pair)).unwrap();
+
+    assert_eq!(
+        pairs,
+        vec![
+            WalkPair::Matched(a, a2),
+            WalkPair::Matched(b, b2),
+            WalkPair::OnlySelf(c),
+            WalkPair::Matched(d, d2),
+            WalkPair::OnlyOther(e2),
+        ]
+    );
+    assert!(!pairs.contains(&WalkPair::OnlyOther(f2)));
+    assert_eq!(
+        tree.walk_together(&Tree::<i32>::new(), |_| ()),
+        Err(TreeError::with_kind(
+            ErrorKind::NotFound,
+            "Cannot walk a tree without a root node."
+        ))
+    );
+}